@@ -3,23 +3,38 @@ use bitgrep::printers::output::SimpleOutput;
 use bitgrep::printers::simple_printer::SimplePrinter;
 use bitgrep::scanner::Scanner;
 use bitgrep::workers::native_processor::NativeProcessor;
+use bitgrep::workers::processors::Processor;
 use bitgrep::{common::Endianness, filters::configuration::Configuration};
-use std::fs::File;
 use std::io::{self};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 use criterion::{criterion_group, criterion_main, Criterion};
 
-fn run_scanner(file_path: &PathBuf, configuration: &Configuration<f64>) {
+fn run_scanner(file_path: &Path, configuration: &Configuration<f64>) {
     let filter = configuration.create_filter();
     let processor = NativeProcessor::new(Endianness::Little);
-    let file = SourceFile::new(file_path.clone(), File::open(file_path).unwrap());
+    // Mapped like the cli does with regular files, so reading doesn't hide the scan itself
+    let file = SourceFile::open(file_path.to_owned()).unwrap();
 
     // Suppress output, this does not test buffered writing but also doesn't spam output
     let io_writer = io::empty();
     let printer = SimplePrinter::new(SimpleOutput::new(), io_writer);
 
-    let scanner = Scanner::new(file, Box::new(processor), filter.unwrap(), printer);
+    let scanner = Scanner::new(file, processor, filter.unwrap(), printer);
+    scanner.scan().expect("should complete successfuly");
+}
+
+/// Same as `run_scanner()` with every filter and the processor as trait objects,
+/// used to compare dynamic dispatch to the static filter tree
+fn run_dyn_scanner(file_path: &Path, configuration: &Configuration<f64>) {
+    let filter = configuration.create_dyn_filter();
+    let processor: Box<dyn Processor<f64>> = Box::new(NativeProcessor::new(Endianness::Little));
+    let file = SourceFile::open(file_path.to_owned()).unwrap();
+
+    let io_writer = io::empty();
+    let printer = SimplePrinter::new(SimpleOutput::new(), io_writer);
+
+    let scanner = Scanner::new(file, processor, filter.unwrap(), printer);
     scanner.scan().expect("should complete successfuly");
 }
 
@@ -50,7 +65,7 @@ fn scanner_random_8k_literal_benchmark(c: &mut Criterion) {
     let path = this_directory.join(FILE_NAME);
 
     let configuration = Configuration {
-        literal: Some(33.248462071692536),
+        literals: vec![33.248_462_071_692_536],
         ..Default::default()
     };
 
@@ -62,9 +77,55 @@ fn scanner_random_8k_literal_benchmark(c: &mut Criterion) {
     );
 }
 
+fn scanner_random_8k_literal_set_benchmark(c: &mut Criterion) {
+    const FILE_NAME: &str = "random.dat";
+
+    let this_directory = Path::new(file!()).parent().unwrap();
+    let path = this_directory.join(FILE_NAME);
+
+    let configuration = Configuration {
+        literals: (0..500)
+            .map(|x| 33.248_462_071_692_536 + f64::from(x))
+            .collect(),
+        ..Default::default()
+    };
+
+    c.bench_function(
+        format!("scanner.scan() 500 literals 8k random file [{FILE_NAME}]").as_str(),
+        |b| {
+            b.iter(|| run_scanner(&path, &configuration));
+        },
+    );
+}
+
+fn scanner_random_8k_dispatch_benchmark(c: &mut Criterion) {
+    const FILE_NAME: &str = "random.dat";
+
+    let this_directory = Path::new(file!()).parent().unwrap();
+    let path = this_directory.join(FILE_NAME);
+
+    let configuration = Configuration {
+        minimum: Some(30.1000),
+        maximum: Some(35.12345),
+        exclude_zero: true,
+        ..Default::default()
+    };
+
+    let mut group = c.benchmark_group(format!("scanner.scan() dispatch [{FILE_NAME}]"));
+    group.bench_function("static", |b| {
+        b.iter(|| run_scanner(&path, &configuration));
+    });
+    group.bench_function("dyn", |b| {
+        b.iter(|| run_dyn_scanner(&path, &configuration));
+    });
+    group.finish();
+}
+
 criterion_group!(
     benches,
     scanner_random_8k_minmax_benchmark,
-    scanner_random_8k_literal_benchmark
+    scanner_random_8k_literal_benchmark,
+    scanner_random_8k_literal_set_benchmark,
+    scanner_random_8k_dispatch_benchmark
 );
 criterion_main!(benches);
@@ -1,24 +1,81 @@
-use std::cell::RefCell;
 use std::error::Error;
-use std::fs::File;
-use std::io::{self, stdin, BufWriter, IsTerminal};
-use std::path::PathBuf;
-use std::rc::Rc;
+use std::fmt::Display;
+use std::io::{self, stdin, BufWriter, IsTerminal, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
 use bitgrep::common::{DataType, Endianness, SourceFile, DEFAULT_BUFFER_SIZE};
+use bitgrep::files::FileWalker;
 use bitgrep::filters::configuration::{Configuration, EntropyConfig};
-use bitgrep::printers::output::SimpleOutput;
+use bitgrep::filters::expression::{Expression, Operator};
+use bitgrep::filters::filter::{Filter, IncludeAll};
+use bitgrep::filters::text_number::TextNumberFilter;
+use bitgrep::filters::tree::FilterTree;
+use bitgrep::hex::HexPattern;
+use bitgrep::layout::StructLayout;
+use bitgrep::parallel::{run_ordered, split_chunks};
+use bitgrep::printers::color_output::ColorOutput;
+use bitgrep::printers::csv_output::{Column, CsvOutput};
+use bitgrep::printers::json_output::JsonOutput;
+use bitgrep::printers::output::{ContextSize, SimpleOutput, Stringifier};
+use bitgrep::printers::printer::Printer;
 use bitgrep::printers::simple_printer::SimplePrinter;
+use bitgrep::printers::summary_printer::{Summary, SummaryPrinter};
+use bitgrep::rules::Rules;
 use bitgrep::scanner::Scanner;
-use bitgrep::types::compare::Compare;
-use bitgrep::workers::entropy_processor::EntropyProcessor;
+use bitgrep::types::apple_time::{Cocoa, HfsPlus};
+use bitgrep::types::compare::{Compare, Tolerance};
+use bitgrep::types::datetime::parse_iso8601;
+use bitgrep::types::dos_time::Dos;
+use bitgrep::types::gps_time::Gps;
+use bitgrep::types::guid::Guid;
+use bitgrep::types::hex_match::HexMatch;
+use bitgrep::types::ip::{IpAddress, IpRange, Ipv4, Ipv6};
+use bitgrep::types::regex_match::RegexMatch;
+use bitgrep::types::rule_match::RuleMatch;
+use bitgrep::types::string_match::{StringEncoding, StringMatch};
+use bitgrep::types::struct_match::StructMatch;
+use bitgrep::types::text_number::TextNumber;
+use bitgrep::types::timestamp::{Timestamp, Unix32, Unix64, Unix64Ms, Unix64Ns, Unix64Us};
+use bitgrep::types::timestamp_match::TimestampMatch;
+use bitgrep::types::windows_time::{DotnetTicks, Filetime, OleDate, SystemTime};
+use bitgrep::workers::any_timestamp_processor::AnyTimestampProcessor;
+use bitgrep::workers::entropy_processor::{EntropyConsumer, EntropyProcessor};
+use bitgrep::workers::guid_processor::GuidProcessor;
+use bitgrep::workers::hex_processor::HexProcessor;
 use bitgrep::workers::native_processor::NativeProcessor;
+use bitgrep::workers::prefilter::Prefilter;
 use bitgrep::workers::processors::Processor;
-use clap::error::ErrorKind::InvalidValue;
+use bitgrep::workers::regex_search::RegexSearch;
+use bitgrep::workers::string_processor::StringProcessor;
+use bitgrep::workers::struct_processor::StructProcessor;
+use bitgrep::workers::text_number_processor::TextNumberProcessor;
+use bitgrep::workers::timestamp_decoder::all_decoders;
+use clap::error::ErrorKind::{ArgumentConflict, InvalidValue, Io};
 use clap::error::{ContextKind, ContextValue};
 use clap::CommandFactory;
 use clap::Parser;
+use regex::bytes::RegexBuilder;
+
+/// Files larger than this are split to chunks when scanning with multiple threads
+const PARALLEL_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(PartialEq, Debug, Clone, Copy, clap::ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, clap::ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+    Tsv,
+}
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -26,23 +83,148 @@ use clap::Parser;
     clap::ArgGroup::new("required-filters")
         .required(true)
         .multiple(true)
-        .args(&["min", "max", "literal"]),
+        .args(&["min", "max", "literal", "literals_file", "expr", "date_min", "date_max", "hex", "string", "regex", "plausible_guids", "cidr", "struct_layout", "rules"]),
+))]
+#[clap(group(
+    clap::ArgGroup::new("date-range")
+        .multiple(true)
+        .args(&["date_min", "date_max"]),
 ))]
+#[allow(clippy::struct_excessive_bools)]
 struct Args {
-    /// Path to file, use - to read from stdin (must not be a tty)
-    #[arg(short, long)]
-    file: PathBuf,
+    /// Paths to files or directories, use - to read from stdin (must not be a tty)
+    #[arg(short, long, num_args = 1.., required = true)]
+    file: Vec<PathBuf>,
+
+    /// Recursively scan directories
+    #[arg(long, short = 'r')]
+    recursive: bool,
+
+    /// Only scan files in directories matching a glob pattern (e.g. '*.dat'), can be repeated
+    #[arg(long, short = 'g')]
+    glob: Vec<String>,
+
+    /// Skip files and directories matching a glob pattern, can be repeated
+    #[arg(long)]
+    exclude: Vec<String>,
+
+    /// Data type of value in rust numerical data types, or a timestamp type
+    #[clap(
+        long = "data-type",
+        short = 'd',
+        required_unless_present_any = ["any_timestamp", "hex", "string", "regex", "struct_layout", "rules"]
+    )]
+    data_type: Option<DataType>,
+
+    /// Search every timestamp type in both endians for --date-min/--date-max
+    #[arg(
+        long,
+        requires = "date-range",
+        conflicts_with_all = ["data_type", "min", "max", "literal", "literals_file", "expr", "exclude_zero", "exclude_literal", "max_entropy", "endianness"],
+        long_help = "Search every timestamp type in both endians for --date-min/--date-max in a single pass.
+Every match is printed with the encoding it matched, e.g. unix32-le or filetime-be.
+The first encoding that matches at an offset wins, in the order of --data-type."
+    )]
+    any_timestamp: bool,
+
+    /// Search a hex byte pattern, e.g. '0AAD[33-4A]DF'
+    #[arg(
+        long,
+        conflicts_with_all = ["data_type", "any_timestamp", "min", "max", "literal", "literals_file", "expr", "date_min", "date_max", "exclude_zero", "exclude_literal", "max_entropy", "endianness"],
+        long_help = "Search a hex byte pattern, e.g. '0AAD[33-4A]DF'. Whitespace is ignored.
+  0A        an exact byte
+  ?A 0? ??  nibble wildcards
+  [33-4A]   an inclusive range of bytes
+  {4} {2,8} a gap of any 4 bytes, or 2 to 8 bytes
+  (0A|0B0C) alternations, also allowed without parentheses at the top level
+Alternatives are tried in order and gaps are as short as possible, the first match at an offset is printed."
+    )]
+    hex: Option<String>,
+
+    /// Search a string in UTF-8, UTF-16LE and UTF-16BE
+    #[arg(
+        long,
+        value_parser = clap::builder::NonEmptyStringValueParser::new(),
+        conflicts_with_all = ["data_type", "any_timestamp", "hex", "min", "max", "literal", "literals_file", "expr", "date_min", "date_max", "exclude_zero", "exclude_literal", "max_entropy", "endianness"],
+        long_help = "Search a string in UTF-8, UTF-16LE and UTF-16BE at the same time.
+Every match is printed with the encoding it matched: utf8, utf16le or utf16be."
+    )]
+    string: Option<String>,
+
+    /// Ignore case when searching --string
+    #[arg(long, short = 'i')]
+    ignore_case: bool,
+
+    /// Search a regex in the raw bytes, or in UTF-16 text with --regex-encoding
+    #[arg(
+        long,
+        conflicts_with_all = ["data_type", "any_timestamp", "hex", "string", "min", "max", "literal", "literals_file", "expr", "date_min", "date_max", "exclude_zero", "exclude_literal", "endianness"],
+        long_help = "Search a regex, matches are printed with their offset and length and don't overlap.
+Raw bytes are matched without Unicode, so \\xFF matches a single byte, use (?u) to enable it.
+With --regex-encoding utf16le or utf16be the data is decoded from UTF-16 at both even and odd offsets
+and the regex matches the decoded text. Can be used with --max-entropy.
+In streams, matches are cut at 4096 bytes."
+    )]
+    regex: Option<String>,
+
+    /// Search records of a struct layout, e.g. '{ id: u32 ==7, lat: f64 >=40 && <=41 }'
+    #[arg(
+        long = "struct",
+        conflicts_with_all = ["data_type", "any_timestamp", "hex", "string", "regex", "min", "max", "literal", "literals_file", "expr", "date_min", "date_max", "exclude_zero", "exclude_literal", "max_entropy", "as_string", "plausible_guids", "cidr"],
+        long_help = "Search records of consecutive fields, e.g. '{ id: u32 ==7, lat: f64 be >=40 && <=41, lon: f64 }'.
+Every field is 'name: type [le|be] [filter]', the type is a --data-type and the filter is an --expr of that type.
+Fields are read in --endian unless they have their own, a record matches if every field passes its filter."
+    )]
+    struct_layout: Option<String>,
+
+    /// Search every rule of a JSON rules file in a single scan
+    #[arg(
+        long,
+        conflicts_with_all = ["data_type", "any_timestamp", "hex", "string", "regex", "struct_layout", "min", "max", "literal", "literals_file", "expr", "date_min", "date_max", "exclude_zero", "exclude_literal", "max_entropy", "as_string", "plausible_guids", "cidr"],
+        long_help = "Search every rule of a JSON rules file in a single scan, see the README for the format.
+Every match is printed with the name of the rule that matched, the first matching rule wins at an offset.
+Files that don't start with the magic of the rules file are skipped."
+    )]
+    rules: Option<PathBuf>,
+
+    /// Decode the data before matching --regex
+    #[clap(value_enum, long)]
+    regex_encoding: Option<StringEncoding>,
+
+    /// Match numbers written as text instead of binary values
+    #[arg(
+        long,
+        conflicts_with_all = ["any_timestamp", "hex", "string", "date_min", "date_max", "endianness"],
+        long_help = "Match numbers written as text in ASCII, UTF-16LE and UTF-16BE instead of binary values.
+Decimal (-12, 10.722, .5), scientific (1.5e-3) and hex (0x1F) numbers are parsed to --data-type
+and filtered with the regular filters. Numbers that are part of a word or a version (abc123, 1.2.3) are not matched.
+With --data-type ipv4, dotted quads (192.168.1.10) are matched instead."
+    )]
+    as_string: bool,
+
+    /// List every plausible v1/v4 GUID with --data-type guid
+    #[arg(
+        long,
+        long_help = "List every plausible GUID with --data-type guid, in the Microsoft and RFC 4122 layouts.
+A GUID is plausible if its variant is RFC 4122 and its version is 1 (time based) or 4 (random)."
+    )]
+    plausible_guids: bool,
 
-    /// Data type of value in rust numerical data types
-    #[clap(long = "data-type", short = 'd')]
-    data_type: DataType,
+    /// An IP range to match, e.g. 192.168.1.0/24, can be repeated
+    #[arg(
+        long,
+        conflicts_with_all = ["literal", "literals_file"],
+        long_help = "An IP range to match with --data-type ipv4 or ipv6, can be repeated to match any of several ranges.
+Ranges are CIDRs (192.168.1.0/24, 2001:db8::/32), dotted ranges (192.168.1.0-192.168.3.255) or single addresses."
+    )]
+    cidr: Vec<String>,
 
     /// Minimum value to match
     #[arg(
         long,
         short = 'm',
         allow_hyphen_values = true,
-        conflicts_with = "literal"
+        conflicts_with_all = ["literal", "literals_file"]
     )]
     min: Option<String>,
 
@@ -51,19 +233,54 @@ struct Args {
         long,
         short = 'M',
         allow_hyphen_values = true,
-        conflicts_with = "literal"
+        conflicts_with_all = ["literal", "literals_file"]
     )]
     max: Option<String>,
 
-    /// A specific value to match
+    /// A specific value to match, can be repeated
     #[arg(long,
         short = 'l',
         allow_hyphen_values = true,
         conflicts_with_all= ["min", "max"],
-        long_help = "A specific value to match.
-In floating point datatypes, this uses a ULPS of 4 to detect floating point values which are approximately equal"
+        long_help = "A specific value to match, can be repeated to match any of several values.
+In floating point datatypes, values are approximately equal (ULPS of 4 by default, see --ulps)"
 )]
-    literal: Option<String>,
+    literal: Vec<String>,
+
+    /// A file of values to match, one per line
+    #[arg(
+        long,
+        long_help = "A file of values to match, one per line. Empty lines and lines starting with # are ignored.
+Matches the same as passing every value with --literal, can be used together with --literal."
+    )]
+    literals_file: Option<PathBuf>,
+
+    /// A boolean filter expression, e.g. '(>=10 && <=20) || ==99'
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        long_help = "A boolean filter expression, e.g. '(>=10 && <=20) || ==99'.
+Supports the comparisons >=, <=, >, <, == and !=, combined with &&, || and ! and grouped with parentheses.
+A value without a comparison is matched with ==, equality uses the same approximate comparison as --literal.
+Other filters are combined with the expression using &&."
+    )]
+    expr: Option<String>,
+
+    /// Earliest date to match, for timestamp data types (e.g. 2021-03-01T10:30:00Z)
+    #[arg(
+        long,
+        long_help = "Earliest date to match as an ISO-8601 date (e.g. 2021-03-01, 2021-03-01T10:30:00Z or 2021-03-01T12:30:00+02:00).
+Only for timestamp data types, values are compared by the time they represent, the same way for every timestamp type."
+    )]
+    date_min: Option<String>,
+
+    /// Latest date to match, for timestamp data types (e.g. 2021-03-01T10:30:00Z)
+    #[arg(
+        long,
+        long_help = "Latest date to match as an ISO-8601 date (e.g. 2021-03-01, 2021-03-01T10:30:00Z or 2021-03-01T12:30:00+02:00).
+Only for timestamp data types, values are compared by the time they represent, the same way for every timestamp type."
+    )]
+    date_max: Option<String>,
 
     /// Filters by maximum entropy
     #[arg(
@@ -87,21 +304,145 @@ Currently this only filters absolute values (i.e. 0x0), no approximation for flo
         long,
         allow_hyphen_values = true,
         long_help = "A specific value to exclude.
-In floating point datatypes, this uses a ULPS of 4 to detect floating point values which are approximately equal"
+In floating point datatypes, values are approximately equal (ULPS of 4 by default, see --ulps)"
     )]
     exclude_literal: Option<String>,
 
+    /// Maximum ULPS distance between approximately equal floats
+    #[arg(
+        long,
+        default_value_t = 4,
+        long_help = "Maximum ULPS (units in the last place) distance between approximately equal floats.
+Applies to --literal, --literals-file, --exclude-literal and == or != in --expr. 0 only matches exact values."
+    )]
+    ulps: u32,
+
+    /// Maximum absolute difference between approximately equal floats
+    #[arg(
+        long,
+        long_help = "Maximum absolute difference between approximately equal floats, checked before ULPS.
+Defaults to the machine epsilon of the data type, use 0 to only compare values near zero by ULPS."
+    )]
+    abs_epsilon: Option<f64>,
+
+    /// Maximum relative difference between approximately equal floats
+    #[arg(
+        long,
+        long_help = "Maximum relative difference between approximately equal floats, as a fraction of the larger value.
+e.g. 0.01 matches values within 1% of a literal. Checked in addition to ULPS."
+    )]
+    rel_epsilon: Option<f64>,
+
+    /// Number of threads to scan with, 0 uses all available cores
+    #[arg(
+        long,
+        short = 'j',
+        default_value_t = 1,
+        long_help = "Number of threads to scan with, 0 uses all available cores.
+Multiple files are scanned at the same time and large files are split to chunks.
+Output is ordered the same as a single threaded scan."
+    )]
+    threads: usize,
+
     /// Endianness of searched value
     #[clap(value_enum, long = "endian", short = 'e', default_value_t = Endianness::Little)]
     endianness: Endianness,
+
+    /// Print a hex dump of NUM bytes after every match
+    #[arg(long, short = 'A', value_name = "NUM")]
+    after_context: Option<usize>,
+
+    /// Print a hex dump of NUM bytes before every match
+    #[arg(long, short = 'B', value_name = "NUM")]
+    before_context: Option<usize>,
+
+    /// Print a hex dump of NUM bytes before and after every match
+    #[arg(
+        long,
+        short = 'C',
+        value_name = "NUM",
+        long_help = "Print a hex dump of NUM bytes before and after every match, -A and -B override it.
+The dump is in rows of 16 bytes like hexdump -C, the bytes of the match are enclosed in brackets."
+    )]
+    context: Option<usize>,
+
+    /// When to print in color
+    #[arg(
+        long,
+        value_enum,
+        value_name = "WHEN",
+        default_value_t = ColorChoice::Auto,
+        long_help = "When to print in color: paths, offsets, types, values and matched bytes each get their own color.
+auto only colors a terminal and never colors when the NO_COLOR environment variable is set."
+    )]
+    color: ColorChoice,
+
+    /// Format of the printed matches
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t = OutputFormat::Text,
+        long_help = "Format of the printed matches. json prints a JSON object per line with the path, offset,
+type, endianness, value, hex, entropy and rule of every match, fields that don't apply are null.
+With -A, -B or -C the bytes around the match are added in hex as before and after.
+csv and tsv print a header and a row of the --columns of every match, fields that don't apply are empty."
+    )]
+    output: OutputFormat,
+
+    /// Columns of csv and tsv output
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = [Column::Path, Column::Offset, Column::Type, Column::Value, Column::Hex],
+        long_help = "Comma separated columns of csv and tsv output, in order.
+before and after are the bytes around the match in hex, captured with -A, -B or -C."
+    )]
+    columns: Vec<Column>,
+
+    /// Print the amount of matches of every file instead of the matches
+    #[arg(
+        long,
+        short = 'c',
+        conflicts_with_all = ["files_with_matches", "files_without_match", "quiet"]
+    )]
+    count: bool,
+
+    /// Print the paths of files with matches instead of the matches
+    #[arg(
+        long,
+        conflicts_with_all = ["files_without_match", "quiet"],
+        long_help = "Print the paths of files with matches instead of the matches.
+Unlike grep there's no -l, it's the short flag of --literal."
+    )]
+    files_with_matches: bool,
+
+    /// Print the paths of files without matches instead of the matches
+    #[arg(long, short = 'L', conflicts_with = "quiet")]
+    files_without_match: bool,
+
+    /// Print nothing, matches are only reported by the exit code
+    #[arg(
+        long,
+        short = 'q',
+        long_help = "Print nothing, matches are only reported by the exit code.
+The exit code is 0 when something matched, 1 when nothing matched and 2 when a file failed,
+with --quiet a match is a success even if a file failed."
+    )]
+    quiet: bool,
 }
 
-fn parse_num<T: FromStr>(num: Option<String>) -> Option<T> {
+fn parse_num<T>(num: Option<String>) -> Option<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
     let num = num?;
 
     let converted = T::from_str(num.as_str());
-    if converted.is_err() {
-        let mut err = Args::command().error(InvalidValue, "Failed parsing number");
+    if let Err(err) = &converted {
+        let mut err = Args::command().error(InvalidValue, format!("Failed parsing value: {err}"));
         err.insert(ContextKind::InvalidValue, ContextValue::String(num));
         err.exit();
     }
@@ -109,8 +450,203 @@ fn parse_num<T: FromStr>(num: Option<String>) -> Option<T> {
     return converted.ok();
 }
 
+fn parse_tolerance(args: &Args) -> Tolerance {
+    return Tolerance {
+        max_ulps: args.ulps,
+        abs_epsilon: args.abs_epsilon,
+        rel_epsilon: args.rel_epsilon,
+    };
+}
+
+/// Bytes to print around every match, None without `-A`, `-B` or `-C`
+fn parse_context(args: &Args) -> Option<ContextSize> {
+    if args.after_context.is_none() && args.before_context.is_none() && args.context.is_none() {
+        return None;
+    }
+
+    return Some(ContextSize {
+        before: args.before_context.or(args.context).unwrap_or(0),
+        after: args.after_context.or(args.context).unwrap_or(0),
+    });
+}
+
+/// Whether to print in color, `auto` colors terminals unless `NO_COLOR` is set
+fn use_color(args: &Args) -> bool {
+    return match args.color {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            io::stdout().is_terminal()
+                && std::env::var_os("NO_COLOR").map_or(true, |value| value.is_empty())
+        }
+    };
+}
+
+/// Summary of every file with `-c`, `--files-with-matches`, `-L` or `-q`
+fn summary(args: &Args) -> Option<Summary> {
+    if args.count {
+        return Some(Summary::Count);
+    }
+
+    if args.files_with_matches {
+        return Some(Summary::FilesWithMatches);
+    }
+
+    if args.files_without_match {
+        return Some(Summary::FilesWithoutMatch);
+    }
+
+    if args.quiet {
+        return Some(Summary::Quiet);
+    }
+
+    return None;
+}
+
+/// Prints a summary of the file at `path` with a summary option, otherwise every match
+fn printer<'w, T: Display + 'static>(
+    args: &Args,
+    path: &Path,
+    writer: &'w mut dyn Write,
+) -> Box<dyn Printer<T> + 'w> {
+    if let Some(summary) = summary(args) {
+        return Box::new(SummaryPrinter::new(summary, path, writer));
+    }
+
+    return Box::new(SimplePrinter::new(stringifier(args), writer));
+}
+
+/// Rows of the `--columns` with `--output csv` or `--output tsv`
+fn table_output(args: &Args) -> Option<CsvOutput> {
+    return match args.output {
+        OutputFormat::Csv => Some(CsvOutput::csv(args.columns.clone())),
+        OutputFormat::Tsv => Some(CsvOutput::tsv(args.columns.clone())),
+        OutputFormat::Text | OutputFormat::Json => None,
+    };
+}
+
+/// Prints JSON lines with `--output json`, rows with `--output csv` or `--output tsv`,
+/// otherwise in color with `--color` and a hex dump of the context after every match
+/// with `-A`, `-B` or `-C`
+fn stringifier<T: Display>(args: &Args) -> Box<dyn Stringifier<T>> {
+    let hex_dump = parse_context(args).is_some();
+
+    if let Some(table) = table_output(args) {
+        return Box::new(table);
+    }
+
+    if args.output == OutputFormat::Json {
+        if hex_dump {
+            return Box::new(JsonOutput::with_context());
+        }
+
+        return Box::new(JsonOutput::new());
+    }
+
+    return match (use_color(args), hex_dump) {
+        (true, true) => Box::new(ColorOutput::with_hex_dump()),
+        (true, false) => Box::new(ColorOutput::new()),
+        (false, true) => Box::new(SimpleOutput::with_hex_dump()),
+        (false, false) => Box::new(SimpleOutput::new()),
+    };
+}
+
+/// Whether matches are printed with their entropy
+fn prints_entropy(args: &Args) -> bool {
+    if summary(args).is_some() {
+        return false;
+    }
+
+    return match args.output {
+        OutputFormat::Text => false,
+        OutputFormat::Json => true,
+        OutputFormat::Csv | OutputFormat::Tsv => args.columns.contains(&Column::Entropy),
+    };
+}
+
+/// An entropy processor when filtering by entropy or printing the entropy of matches
+fn entropy_processor<T: Send>(
+    args: &Args,
+    max_entropy: Option<f64>,
+) -> Option<Arc<Mutex<EntropyProcessor<T>>>> {
+    let needed = max_entropy.is_some() || prints_entropy(args);
+
+    // TODO(danilan): unite all buffer size usages to a single place
+    return needed.then(|| Arc::new(Mutex::new(EntropyProcessor::new(DEFAULT_BUFFER_SIZE))));
+}
+
+/// Parses all `--literal` values and the values in `--literals-file`,
+/// exits if the literals file has no values to match
+fn parse_literals<T>(args: &Args) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let mut values = args.literal.clone();
+
+    if let Some(path) = &args.literals_file {
+        let content = std::fs::read_to_string(path).unwrap_or_else(|err| {
+            let message = format!("Failed reading literals file {}: {err}", path.display());
+            Args::command().error(Io, message).exit();
+        });
+
+        values.extend(
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string),
+        );
+
+        if values.is_empty() {
+            let message = format!("No literals in literals file {}", path.display());
+            Args::command().error(InvalidValue, message).exit();
+        }
+    }
+
+    return values
+        .into_iter()
+        .filter_map(|value| parse_num(Some(value)))
+        .collect();
+}
+
+/// Parses `--date-min` or `--date-max` to nanoseconds since the Unix epoch
+fn parse_date(date: Option<&String>) -> Option<i128> {
+    let date = date?;
+
+    return match parse_iso8601(date) {
+        Ok(nanos) => Some(nanos),
+        Err(err) => {
+            let mut err =
+                Args::command().error(InvalidValue, format!("Failed parsing date: {err}"));
+            err.insert(
+                ContextKind::InvalidValue,
+                ContextValue::String(date.clone()),
+            );
+            err.exit();
+        }
+    };
+}
+
+fn parse_expression<T: FromStr>(expression: Option<&String>) -> Option<Expression<T>> {
+    let expression = expression?;
+
+    let parsed = Expression::parse(expression);
+    if let Err(err) = &parsed {
+        let mut err =
+            Args::command().error(InvalidValue, format!("Failed parsing expression: {err}"));
+        err.insert(
+            ContextKind::InvalidValue,
+            ContextValue::String(expression.clone()),
+        );
+        err.exit();
+    }
+
+    return parsed.ok();
+}
+
 fn open_file<'a>(path: PathBuf) -> Result<SourceFile<'a>, Box<dyn Error>> {
-    if path == PathBuf::from("-") {
+    if path == Path::new("-") {
         if io::stdin().is_terminal() {
             let err =
                 Args::command().error(InvalidValue, "using stdin is not supported in TTY mode");
@@ -119,11 +655,71 @@ fn open_file<'a>(path: PathBuf) -> Result<SourceFile<'a>, Box<dyn Error>> {
             ::std::process::exit(2);
         }
 
-        return Ok(SourceFile::new(PathBuf::from("<stdin>"), stdin().lock()));
+        return Ok(SourceFile::new(PathBuf::from("<stdin>"), stdin()));
     }
 
-    let file = File::open(&path)?;
-    return Ok(SourceFile::new(path, file));
+    return Ok(SourceFile::open(path)?);
+}
+
+/// A unit of scanning work, either a whole file or a chunk of a large file
+struct ScanJob {
+    path: PathBuf,
+    chunk: Option<Range<usize>>,
+}
+
+/// Splits large regular files to chunks that can be scanned in parallel
+fn plan_jobs(paths: Vec<PathBuf>) -> Vec<ScanJob> {
+    let mut jobs = Vec::with_capacity(paths.len());
+
+    for path in paths {
+        let file_size = std::fs::metadata(&path)
+            .ok()
+            .filter(|metadata| metadata.is_file() && path != Path::new("-"))
+            .and_then(|metadata| usize::try_from(metadata.len()).ok());
+
+        match file_size {
+            Some(size) if size > PARALLEL_CHUNK_SIZE => {
+                jobs.extend(
+                    split_chunks(size, PARALLEL_CHUNK_SIZE)
+                        .into_iter()
+                        .map(|chunk| ScanJob {
+                            path: path.clone(),
+                            chunk: Some(chunk),
+                        }),
+                );
+            }
+            _ => jobs.push(ScanJob { path, chunk: None }),
+        }
+    }
+
+    return jobs;
+}
+
+fn thread_count(threads: usize) -> usize {
+    if threads != 0 {
+        return threads;
+    }
+
+    return std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+}
+
+fn parse_config<T>(args: &Args, minimum: Option<T>, maximum: Option<T>) -> Configuration<T>
+where
+    T: Compare + 'static,
+    <T as std::str::FromStr>::Err: std::error::Error,
+{
+    return Configuration {
+        literals: parse_literals(args),
+        minimum,
+        maximum,
+        entropy: None,
+        exclude_zero: args.exclude_zero,
+        exclude_literal: parse_num::<T>(args.exclude_literal.clone()),
+        expression: parse_expression(args.expr.as_ref()),
+        tolerance: parse_tolerance(args),
+        date_minimum: parse_date(args.date_min.as_ref()),
+        date_maximum: parse_date(args.date_max.as_ref()),
+    };
 }
 
 fn run<T>(args: &Args) -> Result<(), Box<dyn Error>>
@@ -131,19 +727,422 @@ where
     T: Compare + 'static,
     <T as std::str::FromStr>::Err: std::error::Error,
 {
-    let processor = NativeProcessor::<T>::new(args.endianness);
+    let config = parse_config(
+        args,
+        parse_num::<T>(args.min.clone()),
+        parse_num::<T>(args.max.clone()),
+    );
 
-    let min = parse_num::<T>(args.min.clone());
-    let max = parse_num::<T>(args.max.clone());
-    let literal = parse_num(args.literal.clone());
-    let exclude_literal = parse_num::<T>(args.exclude_literal.clone());
+    if args.as_string {
+        return scan_files(args, false, |job, writer| {
+            return scan_text_job(job, &config, args, writer, TextNumberProcessor::new());
+        });
+    }
 
-    // TODO(danilan): unite all buffer size usages to a single place
-    let entropy_producer = args.max_entropy.map(|_| {
-        Rc::new(RefCell::new(EntropyProcessor::<T>::new(
-            DEFAULT_BUFFER_SIZE,
-        )))
+    return scan_files(args, may_split(args, true), |job, writer| {
+        let processor = NativeProcessor::<T>::new(args.endianness);
+        return scan_job(job, &config, args, writer, processor);
+    });
+}
+
+/// Scans GUIDs, literals are searched in both layouts
+fn run_guid(args: &Args) -> Result<(), Box<dyn Error>> {
+    let config = parse_config::<Guid>(
+        args,
+        parse_num(args.min.clone()),
+        parse_num(args.max.clone()),
+    );
+    let processor = GuidProcessor::new(&config.literals, args.plausible_guids, args.endianness);
+
+    return scan_files(args, may_split(args, true), |job, writer| {
+        return scan_job(job, &config, args, writer, processor.clone());
+    });
+}
+
+/// Scans IP addresses, `--min` and `--max` also take ranges and `--cidr` ranges
+/// are matched with an expression
+fn run_ip<A>(args: &Args) -> Result<(), Box<dyn Error>>
+where
+    A: IpAddress + Compare + 'static,
+    <A as std::str::FromStr>::Err: std::error::Error,
+{
+    let mut config = parse_config(
+        args,
+        parse_num::<IpRange<A>>(args.min.clone()).map(|range| range.first()),
+        parse_num::<IpRange<A>>(args.max.clone()).map(|range| range.last()),
+    );
+
+    let ranges: Vec<Expression<A>> = args
+        .cidr
+        .iter()
+        .filter_map(|cidr| parse_num::<IpRange<A>>(Some(cidr.clone())))
+        .map(|range| {
+            Expression::And(vec![
+                Expression::Compare(Operator::GreaterEqual, range.first()),
+                Expression::Compare(Operator::LessEqual, range.last()),
+            ])
+        })
+        .collect();
+
+    if !ranges.is_empty() {
+        let ranges = Expression::Or(ranges);
+        config.expression = Some(match config.expression {
+            Some(expression) => Expression::And(vec![expression, ranges]),
+            None => ranges,
+        });
+    }
+
+    if args.as_string {
+        return scan_files(args, false, |job, writer| {
+            return scan_text_job(
+                job,
+                &config,
+                args,
+                writer,
+                TextNumberProcessor::dotted_quads(),
+            );
+        });
+    }
+
+    return scan_files(args, may_split(args, true), |job, writer| {
+        let processor = NativeProcessor::<A>::new(args.endianness);
+        return scan_job(job, &config, args, writer, processor);
+    });
+}
+
+/// Whether large files may be split to chunks that are scanned separately.
+///
+/// Chunks only overlap by the size of a single value, so files are not split
+/// when values don't have a fixed size since a match can be longer than the overlap
+/// between chunks. Context is cut at the bounds of the scanned data and summaries
+/// are printed per file, so these files aren't split either.
+fn may_split(args: &Args, fixed_size: bool) -> bool {
+    return fixed_size && parse_context(args).is_none() && summary(args).is_none();
+}
+
+/// Scans every file in the arguments with `scan`, which returns the amount of matches,
+/// on multiple threads if requested. Large files are split to chunks when `split_files` is set,
+/// see [`may_split`].
+///
+/// Exits like grep, with 1 when nothing matched and 2 when a file failed.
+fn scan_files<S>(args: &Args, split_files: bool, scan: S) -> Result<(), Box<dyn Error>>
+where
+    S: Fn(&ScanJob, &mut dyn Write) -> Result<usize, Box<dyn Error>> + Sync,
+{
+    let walker = FileWalker::with_patterns(args.recursive, &args.glob, &args.exclude)?;
+
+    let stdout = std::io::stdout().lock();
+    let mut stdout = BufWriter::new(stdout);
+
+    // A single header for the rows of all files
+    if let (Some(table), None) = (table_output(args), summary(args)) {
+        writeln!(stdout, "{}", table.header())?;
+    }
+
+    let mut matches = 0;
+    let mut had_errors = false;
+    let mut paths = vec![];
+    let threads = thread_count(args.threads);
+
+    for path in walker.walk(&args.file) {
+        let path = match path {
+            Ok(path) => path,
+            Err(err) => {
+                eprintln!("bitgrep: {err}");
+                had_errors = true;
+                continue;
+            }
+        };
+
+        if threads > 1 {
+            paths.push(path);
+            continue;
+        }
+
+        let job = ScanJob { path, chunk: None };
+        match scan(&job, &mut stdout) {
+            Ok(found) => matches += found,
+            Err(err) => {
+                eprintln!("bitgrep: {}: {err}", job.path.display());
+                had_errors = true;
+            }
+        }
+    }
+
+    if threads > 1 {
+        let jobs = if split_files {
+            plan_jobs(paths)
+        } else {
+            paths
+                .into_iter()
+                .map(|path| ScanJob { path, chunk: None })
+                .collect()
+        };
+
+        // Every job prints to its own buffer, buffers are written in job order
+        run_ordered(
+            &jobs,
+            threads,
+            |job| {
+                let mut output = vec![];
+                let result =
+                    scan(job, &mut output).map_err(|err| format!("{}: {err}", job.path.display()));
+                return (output, result);
+            },
+            |(output, result)| {
+                if let Err(err) = stdout.write_all(&output) {
+                    eprintln!("bitgrep: {err}");
+                    had_errors = true;
+                }
+
+                match result {
+                    Ok(found) => matches += found,
+                    Err(err) => {
+                        eprintln!("bitgrep: {err}");
+                        had_errors = true;
+                    }
+                }
+            },
+        );
+    }
+    stdout.flush()?;
+
+    // A quiet match is a success even if other files failed, like grep -q
+    if had_errors && !(args.quiet && matches > 0) {
+        ::std::process::exit(2);
+    }
+
+    if matches == 0 {
+        ::std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Scans every timestamp encoding in a single pass with the regular scanner, files are
+/// not split since every offset is decoded with all encodings anyway.
+fn run_any_timestamp(args: &Args) -> Result<(), Box<dyn Error>> {
+    let date_minimum = parse_date(args.date_min.as_ref());
+    let date_maximum = parse_date(args.date_max.as_ref());
+
+    return scan_files(args, false, |job, writer| {
+        let file = open_file(job.path.clone())?;
+        let printer = printer(args, &file.path(), writer);
+        let processor = AnyTimestampProcessor::new(all_decoders(), date_minimum, date_maximum);
+
+        let entropy_producer = entropy_processor::<TimestampMatch>(args, None);
+        let scanner = Scanner::with_entropy_processor(
+            file,
+            processor,
+            IncludeAll,
+            printer,
+            entropy_producer.map(|rc| rc as Arc<Mutex<dyn EntropyConsumer<TimestampMatch>>>),
+        )
+        .with_context(parse_context(args).unwrap_or_default());
+        let matches = scanner.scan()?;
+
+        Ok(matches)
+    });
+}
+
+/// Scans a hex pattern with the regular scanner
+fn run_hex(args: &Args, pattern: &str) -> Result<(), Box<dyn Error>> {
+    let pattern = HexPattern::compile(pattern).unwrap_or_else(|err| {
+        let mut err =
+            Args::command().error(InvalidValue, format!("Failed parsing hex pattern: {err}"));
+        err.insert(
+            ContextKind::InvalidValue,
+            ContextValue::String(pattern.to_string()),
+        );
+        err.exit();
+    });
+
+    return scan_files(args, may_split(args, false), |job, writer| {
+        let file = open_file(job.path.clone())?;
+        let printer = printer(args, &file.path(), writer);
+
+        let entropy_producer = entropy_processor::<HexMatch>(args, None);
+        let scanner = Scanner::with_entropy_processor(
+            file,
+            HexProcessor::new(pattern.clone()),
+            IncludeAll,
+            printer,
+            entropy_producer.map(|rc| rc as Arc<Mutex<dyn EntropyConsumer<HexMatch>>>),
+        )
+        .with_prefilter(Prefilter::with_prefix(&pattern.prefix()))
+        .with_context(parse_context(args).unwrap_or_default());
+        let matches = scanner.scan()?;
+
+        Ok(matches)
+    });
+}
+
+/// Scans a string in every encoding with the regular scanner
+fn run_string(args: &Args, literal: &str) -> Result<(), Box<dyn Error>> {
+    // Ignoring case checks every char for the first bytes, so it's done once for all files
+    let first_bytes = StringProcessor::new(literal, args.ignore_case).first_bytes();
+    let prefilter = Prefilter::with_first_bytes(&first_bytes);
+
+    return scan_files(args, may_split(args, false), |job, writer| {
+        let file = open_file(job.path.clone())?;
+        let printer = printer(args, &file.path(), writer);
+        let processor = StringProcessor::new(literal, args.ignore_case);
+
+        let entropy_producer = entropy_processor::<StringMatch>(args, None);
+        let scanner = Scanner::with_entropy_processor(
+            file,
+            processor,
+            IncludeAll,
+            printer,
+            entropy_producer.map(|rc| rc as Arc<Mutex<dyn EntropyConsumer<StringMatch>>>),
+        )
+        .with_prefilter(prefilter.clone())
+        .with_context(parse_context(args).unwrap_or_default());
+        let matches = scanner.scan()?;
+
+        Ok(matches)
+    });
+}
+
+/// Scans a regex with the regular scanner
+fn run_regex(args: &Args, pattern: &str) -> Result<(), Box<dyn Error>> {
+    let encoding = args.regex_encoding.unwrap_or(StringEncoding::Utf8);
+    let regex = RegexBuilder::new(pattern)
+        .unicode(encoding != StringEncoding::Utf8)
+        .build()
+        .unwrap_or_else(|err| {
+            let mut err =
+                Args::command().error(InvalidValue, format!("Failed parsing regex: {err}"));
+            err.insert(
+                ContextKind::InvalidValue,
+                ContextValue::String(pattern.to_string()),
+            );
+            err.exit();
+        });
+
+    return scan_files(args, may_split(args, false), |job, writer| {
+        let file = open_file(job.path.clone())?;
+        let printer = printer(args, &file.path(), writer);
+        let entropy_producer = entropy_processor::<RegexMatch>(args, args.max_entropy);
+
+        let filter: Box<dyn Filter<RegexMatch>> = match (args.max_entropy, &entropy_producer) {
+            (Some(max_entropy), Some(entropy_producer)) => Box::new(
+                EntropyConfig {
+                    max_entropy,
+                    entropy_producer: entropy_producer.clone(),
+                }
+                .create_value_filter(),
+            ),
+            _ => Box::new(IncludeAll),
+        };
+
+        let scanner = Scanner::with_search(
+            file,
+            RegexSearch::new(regex.clone(), encoding),
+            filter,
+            printer,
+            entropy_producer.map(|rc| rc as Arc<Mutex<dyn EntropyConsumer<RegexMatch>>>),
+        )
+        .with_context(parse_context(args).unwrap_or_default());
+        let matches = scanner.scan()?;
+
+        Ok(matches)
     });
+}
+
+/// Scans records of a struct layout with the regular scanner, the fields are filtered
+/// by the processor. Files are not split.
+fn run_struct(args: &Args, layout: &str) -> Result<(), Box<dyn Error>> {
+    let processor = StructLayout::parse(layout)
+        .and_then(|parsed| StructProcessor::new(&parsed, args.endianness, parse_tolerance(args)))
+        .unwrap_or_else(|err| {
+            let mut err =
+                Args::command().error(InvalidValue, format!("Failed parsing struct layout: {err}"));
+            err.insert(
+                ContextKind::InvalidValue,
+                ContextValue::String(layout.to_string()),
+            );
+            err.exit();
+        });
+
+    return scan_files(args, false, |job, writer| {
+        let file = open_file(job.path.clone())?;
+        let printer = printer(args, &file.path(), writer);
+
+        let entropy_producer = entropy_processor::<StructMatch>(args, None);
+        let scanner = Scanner::with_entropy_processor(
+            file,
+            processor.clone(),
+            IncludeAll,
+            printer,
+            entropy_producer.map(|rc| rc as Arc<Mutex<dyn EntropyConsumer<StructMatch>>>),
+        )
+        .with_context(parse_context(args).unwrap_or_default());
+        let matches = scanner.scan()?;
+
+        Ok(matches)
+    });
+}
+
+/// Scans every rule of a rules file with the regular scanner, every file gets its own
+/// processor since the magic is checked at the start of the file. Files are not split.
+fn run_rules(args: &Args, path: &Path) -> Result<(), Box<dyn Error>> {
+    let rules = Rules::load(path).unwrap_or_else(|err| {
+        let mut err = Args::command().error(InvalidValue, err.to_string());
+        err.insert(
+            ContextKind::InvalidValue,
+            ContextValue::String(path.display().to_string()),
+        );
+        err.exit();
+    });
+
+    return scan_files(args, false, |job, writer| {
+        let file = open_file(job.path.clone())?;
+        let printer = printer(args, &file.path(), writer);
+        let processor = rules.create_processor(args.endianness, parse_tolerance(args))?;
+        let entropy_producer = entropy_processor::<RuleMatch>(args, rules.max_entropy());
+
+        let filter: Box<dyn Filter<RuleMatch>> = match (rules.max_entropy(), &entropy_producer) {
+            (Some(max_entropy), Some(entropy_producer)) => Box::new(
+                EntropyConfig {
+                    max_entropy,
+                    entropy_producer: entropy_producer.clone(),
+                }
+                .create_value_filter(),
+            ),
+            _ => Box::new(IncludeAll),
+        };
+
+        let scanner = Scanner::with_entropy_processor(
+            file,
+            processor,
+            filter,
+            printer,
+            entropy_producer.map(|rc| rc as Arc<Mutex<dyn EntropyConsumer<RuleMatch>>>),
+        )
+        .with_context(parse_context(args).unwrap_or_default());
+        let matches = scanner.scan()?;
+
+        Ok(matches)
+    });
+}
+
+/// Scans a single file or chunk, every scan gets its own filters so entropy
+/// is not carried over between files.
+fn scan_job<T, R>(
+    job: &ScanJob,
+    config: &Configuration<T>,
+    args: &Args,
+    writer: &mut dyn Write,
+    processor: R,
+) -> Result<usize, Box<dyn Error>>
+where
+    T: Compare + 'static,
+    R: Processor<T>,
+{
+    let value_size = processor.chunk_size().unwrap();
+
+    let entropy_producer = entropy_processor::<T>(args, args.max_entropy);
 
     let entropy_config = args.max_entropy.map(|max| EntropyConfig {
         max_entropy: max,
@@ -151,38 +1150,99 @@ where
     });
 
     let config = Configuration {
-        literal,
-        minimum: min,
-        maximum: max,
         entropy: entropy_config,
-        exclude_zero: args.exclude_zero,
-        exclude_literal,
+        ..config.clone()
     };
 
-    let filter = config.create_filter().ok_or("Failed creating filters")?;
+    let filter = match config.create_filter() {
+        Some(filter) => filter,
+        // The GUID processor only returns plausible GUIDs
+        None if args.plausible_guids => FilterTree::include_all(),
+        None => return Err("Failed creating filters".into()),
+    };
+    let prefilter = Prefilter::new(&config, args.endianness);
 
     // Unwrap option to coerce type, hell on earth
-    let entropy_processor = entropy_producer.map(|rc| rc as Rc<RefCell<dyn Processor<T>>>);
+    let entropy_processor = entropy_producer.map(|rc| rc as Arc<Mutex<dyn EntropyConsumer<T>>>);
 
-    let stdout = std::io::stdout().lock();
-    let stdout = BufWriter::new(stdout);
-    let printer = SimplePrinter::new(SimpleOutput::new(), stdout);
+    let Some(chunk) = &job.chunk else {
+        let file = open_file(job.path.clone())?;
+        let printer = printer(args, &file.path(), writer);
+
+        let scanner =
+            Scanner::with_entropy_processor(file, processor, filter, printer, entropy_processor)
+                .with_prefilter(prefilter)
+                .with_endianness(args.endianness)
+                .with_context(parse_context(args).unwrap_or_default());
+        let matches = scanner.scan()?;
+
+        return Ok(matches);
+    };
 
-    let file = open_file(args.file.clone())?;
+    // Read the preceding entropy window so entropy at the chunk start is the
+    // same as in a full scan, and enough bytes after the chunk for the value
+    // starting at its last offset.
+    let warmup = chunk.start.min(DEFAULT_BUFFER_SIZE);
+    let read_range = (chunk.start - warmup)..(chunk.end + value_size - 1);
 
-    let mut scanner = Scanner::with_entropy_processor(
+    let file = SourceFile::open_range(job.path.clone(), read_range.clone())?;
+    let printer = printer(args, &file.path(), writer);
+
+    let scanner =
+        Scanner::with_entropy_processor(file, processor, filter, printer, entropy_processor)
+            .with_prefilter(prefilter)
+            .with_endianness(args.endianness)
+            .with_offset(read_range.start, warmup);
+    let matches = scanner.scan()?;
+
+    Ok(matches)
+}
+
+/// Scans a whole file for numbers written as text, files are not split since
+/// the processor must see the bytes before a number.
+fn scan_text_job<T>(
+    job: &ScanJob,
+    config: &Configuration<T>,
+    args: &Args,
+    writer: &mut dyn Write,
+    processor: TextNumberProcessor<T>,
+) -> Result<usize, Box<dyn Error>>
+where
+    T: Compare + 'static,
+{
+    let entropy_producer = entropy_processor::<TextNumber<T>>(args, args.max_entropy);
+
+    let entropy_config = args.max_entropy.map(|max| EntropyConfig {
+        max_entropy: max,
+        entropy_producer: entropy_producer.as_ref().unwrap().clone(),
+    });
+
+    let config = Configuration {
+        entropy: entropy_config,
+        ..config.clone()
+    };
+
+    let filter = config.create_filter().ok_or("Failed creating filters")?;
+    let entropy_processor =
+        entropy_producer.map(|rc| rc as Arc<Mutex<dyn EntropyConsumer<TextNumber<T>>>>);
+
+    let file = open_file(job.path.clone())?;
+    let printer = printer(args, &file.path(), writer);
+
+    let scanner = Scanner::with_entropy_processor(
         file,
-        Box::new(processor),
-        filter,
+        processor,
+        TextNumberFilter::new(filter),
         printer,
         entropy_processor,
-    );
-    scanner.scan()?;
+    )
+    .with_context(parse_context(args).unwrap_or_default());
+    let matches = scanner.scan()?;
 
-    Ok(())
+    Ok(matches)
 }
 
-fn run_type(data_type: DataType, args: &Args) -> Result<(), Box<dyn Error>> {
+fn run_type(data_type: &DataType, args: &Args) -> Result<(), Box<dyn Error>> {
     match data_type {
         DataType::I8 => run::<i8>(args),
         DataType::I16 => run::<i16>(args),
@@ -196,13 +1256,111 @@ fn run_type(data_type: DataType, args: &Args) -> Result<(), Box<dyn Error>> {
         DataType::U128 => run::<u128>(args),
         DataType::F32 => run::<f32>(args),
         DataType::F64 => run::<f64>(args),
+        DataType::Unix32 => run::<Timestamp<Unix32>>(args),
+        DataType::Unix64 => run::<Timestamp<Unix64>>(args),
+        DataType::Unix64Ms => run::<Timestamp<Unix64Ms>>(args),
+        DataType::Unix64Us => run::<Timestamp<Unix64Us>>(args),
+        DataType::Unix64Ns => run::<Timestamp<Unix64Ns>>(args),
+        DataType::Filetime => run::<Timestamp<Filetime>>(args),
+        DataType::Systemtime => run::<Timestamp<SystemTime>>(args),
+        DataType::OleDate => run::<Timestamp<OleDate>>(args),
+        DataType::DotnetTicks => run::<Timestamp<DotnetTicks>>(args),
+        DataType::Cocoa => run::<Timestamp<Cocoa>>(args),
+        DataType::HfsPlus => run::<Timestamp<HfsPlus>>(args),
+        DataType::Gps => run::<Timestamp<Gps>>(args),
+        DataType::Dos => run::<Timestamp<Dos>>(args),
+        DataType::Guid => run_guid(args),
+        DataType::Ipv4 => run_ip::<Ipv4>(args),
+        DataType::Ipv6 => run_ip::<Ipv6>(args),
     }
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn main() {
     let args = Args::parse();
 
-    return run_type(args.data_type.clone(), &args);
+    // Errors exit with 2 like grep, 1 is for searches without matches
+    if let Err(err) = search(&args) {
+        eprintln!("bitgrep: {err}");
+        ::std::process::exit(2);
+    }
+}
+
+/// Runs the search of the arguments
+fn search(args: &Args) -> Result<(), Box<dyn Error>> {
+    if args.ignore_case && args.string.is_none() {
+        Args::command()
+            .error(ArgumentConflict, "--ignore-case requires --string")
+            .exit();
+    }
+
+    if args.regex_encoding.is_some() && args.regex.is_none() {
+        Args::command()
+            .error(ArgumentConflict, "--regex-encoding requires --regex")
+            .exit();
+    }
+
+    if args.any_timestamp {
+        return run_any_timestamp(args);
+    }
+
+    if let Some(pattern) = &args.hex {
+        return run_hex(args, pattern);
+    }
+
+    if let Some(literal) = &args.string {
+        return run_string(args, literal);
+    }
+
+    if let Some(pattern) = &args.regex {
+        return run_regex(args, pattern);
+    }
+
+    if let Some(layout) = &args.struct_layout {
+        return run_struct(args, layout);
+    }
+
+    if let Some(path) = &args.rules {
+        return run_rules(args, path);
+    }
+
+    let data_type = args
+        .data_type
+        .clone()
+        .expect("required without --any-timestamp");
+    if args.as_string && !data_type.is_number() && data_type != DataType::Ipv4 {
+        Args::command()
+            .error(
+                ArgumentConflict,
+                "--as-string requires a numeric data type or ipv4",
+            )
+            .exit();
+    }
+
+    if !args.cidr.is_empty() && !data_type.is_ip() {
+        Args::command()
+            .error(ArgumentConflict, "--cidr requires --data-type ipv4 or ipv6")
+            .exit();
+    }
+
+    if args.plausible_guids && data_type != DataType::Guid {
+        Args::command()
+            .error(
+                ArgumentConflict,
+                "--plausible-guids requires --data-type guid",
+            )
+            .exit();
+    }
+
+    if (args.date_min.is_some() || args.date_max.is_some()) && !data_type.is_timestamp() {
+        Args::command()
+            .error(
+                ArgumentConflict,
+                "--date-min and --date-max require a timestamp data type",
+            )
+            .exit();
+    }
+
+    return run_type(&data_type, args);
 }
 
 #[test]
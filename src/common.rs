@@ -1,5 +1,9 @@
+use std::fs::File;
+use std::ops::Range;
 use std::{io, path::PathBuf};
 
+use memmap2::{Mmap, MmapOptions};
+
 pub const DEFAULT_BUFFER_SIZE: usize = 4096;
 
 #[derive(PartialEq, Debug, Clone, Copy, clap::ValueEnum)]
@@ -8,6 +12,16 @@ pub enum Endianness {
     Big,
 }
 
+impl Endianness {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        return match self {
+            Endianness::Little => "little",
+            Endianness::Big => "big",
+        };
+    }
+}
+
 #[derive(PartialEq, Debug, Clone, clap::ValueEnum)]
 pub enum DataType {
     I8,
@@ -24,30 +38,245 @@ pub enum DataType {
 
     F32,
     F64,
+
+    /// 32-bit Unix time in seconds
+    Unix32,
+    /// 64-bit Unix time in seconds
+    Unix64,
+    /// 64-bit Unix time in milliseconds
+    Unix64Ms,
+    /// 64-bit Unix time in microseconds
+    Unix64Us,
+    /// 64-bit Unix time in nanoseconds
+    Unix64Ns,
+    /// Windows FILETIME, 100 nanoseconds since 1601
+    Filetime,
+    /// Windows SYSTEMTIME struct (16 bytes)
+    Systemtime,
+    /// OLE automation date, days since 1899-12-30 as a double
+    OleDate,
+    /// .NET `DateTime` ticks, 100 nanoseconds since 0001 with the kind in the top bits
+    DotnetTicks,
+    /// Cocoa / Core Data date, seconds since 2001-01-01 as a double
+    Cocoa,
+    /// HFS+ date, 32-bit seconds since 1904-01-01
+    HfsPlus,
+    /// GPS 32-bit week followed by 32-bit seconds of the week
+    Gps,
+    /// MS-DOS packed 16-bit time followed by 16-bit date
+    Dos,
+
+    /// GUID (UUID), in the Microsoft and RFC 4122 layouts
+    Guid,
+
+    /// IPv4 address, big endian is network byte order
+    Ipv4,
+    /// IPv6 address, big endian is network byte order
+    Ipv6,
+}
+
+impl DataType {
+    /// Whether values are dates, which can be filtered by a date range
+    #[must_use]
+    pub fn is_timestamp(&self) -> bool {
+        return !self.is_number()
+            && !matches!(self, DataType::Guid | DataType::Ipv4 | DataType::Ipv6);
+    }
+
+    /// Whether values are IP addresses, which can be filtered by CIDR ranges
+    #[must_use]
+    pub fn is_ip(&self) -> bool {
+        return matches!(self, DataType::Ipv4 | DataType::Ipv6);
+    }
+
+    /// Whether values are numbers, which can also be matched as text
+    #[must_use]
+    pub fn is_number(&self) -> bool {
+        return matches!(
+            self,
+            DataType::I8
+                | DataType::I16
+                | DataType::I32
+                | DataType::I64
+                | DataType::I128
+                | DataType::U8
+                | DataType::U16
+                | DataType::U32
+                | DataType::U64
+                | DataType::U128
+                | DataType::F32
+                | DataType::F64
+        );
+    }
+}
+
+/// How the data of a [`SourceFile`] is accessed
+pub(crate) enum SourceData<'a> {
+    /// Streams (stdin, pipes) read through a buffer
+    Reader(Box<dyn io::Read + Send + 'a>),
+    /// Regular files mapped to memory and scanned as a single slice
+    Mapped(Mmap),
 }
 
 pub struct SourceFile<'a> {
     path: PathBuf,
-    file: Box<dyn io::Read + 'a>,
+    data: SourceData<'a>,
 }
 
 impl<'a> SourceFile<'a> {
     #[must_use]
-    pub fn new(path: PathBuf, file: impl io::Read + 'a) -> Self {
+    pub fn new(path: PathBuf, file: impl io::Read + Send + 'a) -> Self {
         return SourceFile {
             path,
-            file: Box::new(file),
+            data: SourceData::Reader(Box::new(file)),
+        };
+    }
+
+    /// Opens a file for scanning, regular files are memory mapped.
+    /// Anything that cannot be mapped (e.g. pipes, devices or empty files) is read instead.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be opened
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        let file = File::open(&path)?;
+        let metadata = file.metadata()?;
+
+        if !metadata.is_file() || metadata.len() == 0 {
+            return Ok(Self::new(path, file));
+        }
+
+        // SAFETY: The file is only read, modifying it while scanning (e.g. truncating it)
+        // is undefined behaviour, just like with any other tool that maps files.
+        return match unsafe { Mmap::map(&file) } {
+            Ok(map) => Ok(SourceFile {
+                path,
+                data: SourceData::Mapped(map),
+            }),
+            Err(_) => Ok(Self::new(path, file)),
         };
     }
 
+    /// Opens a range of a regular file for scanning, the range is clamped to the file size.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be opened or mapped
+    pub fn open_range(path: PathBuf, range: Range<usize>) -> io::Result<Self> {
+        let file = File::open(&path)?;
+        let length = usize::try_from(file.metadata()?.len()).unwrap_or(usize::MAX);
+
+        let start = range.start.min(length);
+        let end = range.end.min(length);
+        if start == end {
+            return Ok(Self::new(path, io::empty()));
+        }
+
+        // SAFETY: See open()
+        let map = unsafe {
+            MmapOptions::new()
+                .offset(start as u64)
+                .len(end - start)
+                .map(&file)?
+        };
+
+        return Ok(SourceFile {
+            path,
+            data: SourceData::Mapped(map),
+        });
+    }
+
     #[must_use]
     pub fn path(&self) -> PathBuf {
         return self.path.clone();
     }
 
+    /// Whether the file is scanned from memory instead of being read
+    #[must_use]
+    pub fn is_mapped(&self) -> bool {
+        return matches!(self.data, SourceData::Mapped(_));
+    }
+
     // TODO(danilan): This unwraps the struct, maybe move to Rc
     #[must_use]
-    pub fn file(self) -> Box<dyn io::Read + 'a> {
-        return self.file;
+    pub fn file(self) -> Box<dyn io::Read + Send + 'a> {
+        return match self.data {
+            SourceData::Reader(reader) => reader,
+            SourceData::Mapped(map) => Box::new(io::Cursor::new(map)),
+        };
+    }
+
+    pub(crate) fn data(self) -> SourceData<'a> {
+        return self.data;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+
+    use super::*;
+
+    fn create_file(data: &[u8]) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().expect("temp file to be created");
+        file.write_all(data).unwrap();
+        return file;
+    }
+
+    fn read_all(source: SourceFile) -> Vec<u8> {
+        let mut result = vec![];
+        source.file().read_to_end(&mut result).unwrap();
+        return result;
+    }
+
+    #[test]
+    fn open_regular_file_is_mapped() {
+        let file = create_file(&[1u8, 2u8, 3u8]);
+
+        let source = SourceFile::open(file.path().to_owned()).unwrap();
+
+        assert!(source.is_mapped());
+        assert_eq!(read_all(source), [1u8, 2u8, 3u8]);
+    }
+
+    #[test]
+    fn open_empty_file_is_read() {
+        let file = create_file(&[]);
+
+        let source = SourceFile::open(file.path().to_owned()).unwrap();
+
+        assert!(!source.is_mapped());
+        assert!(read_all(source).is_empty());
+    }
+
+    #[test]
+    fn open_missing_file_returns_error() {
+        assert!(SourceFile::open("/does/not/exist".into()).is_err());
+    }
+
+    #[test]
+    fn open_range_maps_range() {
+        let file = create_file(&[1u8, 2u8, 3u8, 4u8, 5u8]);
+
+        let source = SourceFile::open_range(file.path().to_owned(), 1..3).unwrap();
+
+        assert!(source.is_mapped());
+        assert_eq!(read_all(source), [2u8, 3u8]);
+    }
+
+    #[test]
+    fn open_range_clamps_to_file_size() {
+        let file = create_file(&[1u8, 2u8, 3u8, 4u8, 5u8]);
+
+        let source = SourceFile::open_range(file.path().to_owned(), 3..10).unwrap();
+        assert_eq!(read_all(source), [4u8, 5u8]);
+
+        let source = SourceFile::open_range(file.path().to_owned(), 7..10).unwrap();
+        assert!(read_all(source).is_empty());
+    }
+
+    #[test]
+    fn new_reader_is_not_mapped() {
+        let source = SourceFile::new("ok".into(), [1u8].as_slice());
+
+        assert!(!source.is_mapped());
     }
 }
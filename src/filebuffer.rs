@@ -5,39 +5,37 @@ use std::io::{self, Read};
 
 pub(crate) struct FileBuffer<'a> {
     // File like implementation thorugh io::Read, make sure lifetime at least as parent
-    file: Box<dyn io::Read + 'a>,
+    file: Box<dyn io::Read + Send + 'a>,
 
     ring_buf: VecDeque<u8>,
+    // Reused for every read, so a refill doesn't allocate
+    read_buf: Vec<u8>,
     reached_eof: bool,
-    buffer_size: usize,
     position: usize,
 }
 
 impl<'a> FileBuffer<'a> {
-    pub fn new(reader: impl io::Read + 'a) -> Self {
+    pub fn new(reader: impl io::Read + Send + 'a) -> Self {
         return Self::with_buffer_size(reader, DEFAULT_BUFFER_SIZE);
     }
 
-    fn with_buffer_size(reader: impl io::Read + 'a, buffer_size: usize) -> Self {
+    fn with_buffer_size(reader: impl io::Read + Send + 'a, buffer_size: usize) -> Self {
         // with_capacity usually actually doubles requested capacity (round(smallest 2^n-1))
         // This means that there'll be enough space for both the file read and leftovers
         let buffer = VecDeque::with_capacity(buffer_size);
         FileBuffer {
             file: Box::new(reader),
             ring_buf: buffer,
+            read_buf: vec![0u8; buffer_size],
             reached_eof: false,
-            buffer_size,
             position: 0,
         }
     }
 
     fn fill_buffer(&mut self) -> Result<(), io::Error> {
-        let mut buffer = vec![0u8; self.buffer_size];
+        let bytes_read = self.file.read(&mut self.read_buf)?;
 
-        let bytes_read = self.file.read(&mut buffer)?;
-        buffer.truncate(bytes_read);
-
-        self.ring_buf.extend(buffer);
+        self.ring_buf.extend(&self.read_buf[..bytes_read]);
         // This is used so we can get a single slice when we need to peek
         self.ring_buf.make_contiguous();
 
@@ -0,0 +1,265 @@
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use thiserror::Error;
+use walkdir::WalkDir;
+
+/// Path used by the cli to represent stdin, passed through as is
+const STDIN_PATH: &str = "-";
+
+#[derive(Debug, Error)]
+pub enum WalkError {
+    #[error("{0}: Is a directory")]
+    IsDirectory(PathBuf),
+    #[error(transparent)]
+    Walk(#[from] walkdir::Error),
+    #[error("Invalid glob pattern: {0}")]
+    InvalidPattern(#[from] globset::Error),
+}
+
+/// Expands a list of paths to the files that should be scanned.
+///
+/// Directories are walked (in file name order) only when recursive,
+/// include patterns select the files found in directories and exclude patterns
+/// drop both files and whole directories. Files named explicitly are always
+/// scanned unless excluded.
+///
+/// Patterns are matched against both the full path and the file name,
+/// so `*.dat` and `dumps/**/*.dat` both work as expected.
+pub struct FileWalker {
+    recursive: bool,
+    include: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+}
+
+impl FileWalker {
+    #[must_use]
+    pub fn new(recursive: bool) -> Self {
+        return FileWalker {
+            recursive,
+            include: None,
+            exclude: None,
+        };
+    }
+
+    /// # Errors
+    /// Returns an error if one of the glob patterns is invalid
+    pub fn with_patterns<S: AsRef<str>>(
+        recursive: bool,
+        include: &[S],
+        exclude: &[S],
+    ) -> Result<Self, WalkError> {
+        return Ok(FileWalker {
+            recursive,
+            include: build_glob_set(include)?,
+            exclude: build_glob_set(exclude)?,
+        });
+    }
+
+    /// Lazily walks all paths in order, errors are returned per path
+    /// so a single unreadable directory does not stop the walk.
+    pub fn walk<'a>(
+        &'a self,
+        paths: &'a [PathBuf],
+    ) -> impl Iterator<Item = Result<PathBuf, WalkError>> + 'a {
+        return paths.iter().flat_map(|path| self.walk_path(path));
+    }
+
+    fn walk_path<'a>(
+        &'a self,
+        path: &Path,
+    ) -> Box<dyn Iterator<Item = Result<PathBuf, WalkError>> + 'a> {
+        if path == Path::new(STDIN_PATH) {
+            return Box::new(std::iter::once(Ok(path.to_owned())));
+        }
+
+        if !path.is_dir() {
+            // Let non existing files fail when opened, just like regular files
+            let included = !self.is_excluded(path);
+            return Box::new(included.then(|| Ok(path.to_owned())).into_iter());
+        }
+
+        if !self.recursive {
+            return Box::new(std::iter::once(Err(WalkError::IsDirectory(
+                path.to_owned(),
+            ))));
+        }
+
+        let entries = WalkDir::new(path)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !self.is_excluded(entry.path()))
+            .filter_map(|entry| match entry {
+                Ok(entry) if entry.file_type().is_file() && self.is_included(entry.path()) => {
+                    Some(Ok(entry.into_path()))
+                }
+                Ok(_) => None,
+                Err(err) => Some(Err(WalkError::from(err))),
+            });
+
+        return Box::new(entries);
+    }
+
+    fn is_included(&self, path: &Path) -> bool {
+        if self.is_excluded(path) {
+            return false;
+        }
+
+        return self
+            .include
+            .as_ref()
+            .map_or(true, |include| matches(include, path));
+    }
+
+    fn is_excluded(&self, path: &Path) -> bool {
+        return self
+            .exclude
+            .as_ref()
+            .is_some_and(|exclude| matches(exclude, path));
+    }
+}
+
+fn matches(set: &GlobSet, path: &Path) -> bool {
+    return set.is_match(path) || path.file_name().is_some_and(|name| set.is_match(name));
+}
+
+fn build_glob_set<S: AsRef<str>>(patterns: &[S]) -> Result<Option<GlobSet>, WalkError> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern.as_ref())?);
+    }
+
+    return Ok(Some(builder.build()?));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use assertor::{assert_that, VecAssertion};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn create_tree() -> TempDir {
+        let dir = tempfile::tempdir().expect("temp dir to be created");
+        fs::create_dir_all(dir.path().join("sub/deep")).unwrap();
+        fs::create_dir_all(dir.path().join("skip")).unwrap();
+
+        for file in [
+            "a.dat",
+            "b.bin",
+            "sub/c.dat",
+            "sub/deep/d.dat",
+            "skip/e.dat",
+        ] {
+            fs::write(dir.path().join(file), [0u8]).unwrap();
+        }
+
+        return dir;
+    }
+
+    fn walk_ok(walker: &FileWalker, root: &Path) -> Vec<PathBuf> {
+        return walker
+            .walk(&[root.to_owned()])
+            .map(|path| path.expect("walk to succeed"))
+            .map(|path| path.strip_prefix(root).unwrap().to_owned())
+            .collect();
+    }
+
+    #[test]
+    fn walk_file_returns_file() {
+        let dir = create_tree();
+        let file = dir.path().join("a.dat");
+
+        let result: Vec<_> = FileWalker::new(false)
+            .walk(std::slice::from_ref(&file))
+            .map(Result::unwrap)
+            .collect();
+
+        assert_that!(result).contains_exactly_in_order(vec![file]);
+    }
+
+    #[test]
+    fn walk_file_ignores_include_glob() {
+        let dir = create_tree();
+        let file = dir.path().join("a.dat");
+        let walker = FileWalker::with_patterns(false, &["*.bin"], &[]).unwrap();
+
+        let result: Vec<_> = walker
+            .walk(std::slice::from_ref(&file))
+            .map(Result::unwrap)
+            .collect();
+
+        assert_that!(result).contains_exactly_in_order(vec![file]);
+    }
+
+    #[test]
+    fn walk_stdin_passes_through() {
+        let result: Vec<_> = FileWalker::new(true)
+            .walk(&["-".into()])
+            .map(Result::unwrap)
+            .collect();
+
+        assert_that!(result).contains_exactly_in_order(vec![PathBuf::from("-")]);
+    }
+
+    #[test]
+    fn walk_directory_not_recursive_returns_error() {
+        let dir = create_tree();
+
+        let result: Vec<_> = FileWalker::new(false)
+            .walk(&[dir.path().to_owned()])
+            .collect();
+
+        assert_eq!(result.len(), 1);
+        assert!(matches!(result[0], Err(WalkError::IsDirectory(_))));
+    }
+
+    #[test]
+    fn walk_recursive_returns_sorted_files() {
+        let dir = create_tree();
+
+        let result = walk_ok(&FileWalker::new(true), dir.path());
+
+        assert_that!(result).contains_exactly_in_order(vec![
+            PathBuf::from("a.dat"),
+            PathBuf::from("b.bin"),
+            PathBuf::from("skip/e.dat"),
+            PathBuf::from("sub/c.dat"),
+            PathBuf::from("sub/deep/d.dat"),
+        ]);
+    }
+
+    #[test]
+    fn walk_recursive_include_glob() {
+        let dir = create_tree();
+        let walker = FileWalker::with_patterns(true, &["*.bin"], &[]).unwrap();
+
+        let result = walk_ok(&walker, dir.path());
+
+        assert_that!(result).contains_exactly_in_order(vec![PathBuf::from("b.bin")]);
+    }
+
+    #[test]
+    fn walk_recursive_exclude_prunes_directories() {
+        let dir = create_tree();
+        let walker = FileWalker::with_patterns(true, &["*.dat"], &["skip", "d.dat"]).unwrap();
+
+        let result = walk_ok(&walker, dir.path());
+
+        assert_that!(result)
+            .contains_exactly_in_order(vec![PathBuf::from("a.dat"), PathBuf::from("sub/c.dat")]);
+    }
+
+    #[test]
+    fn with_patterns_invalid_glob_returns_error() {
+        let result = FileWalker::with_patterns(true, &["a[b"], &[]);
+
+        assert!(matches!(result, Err(WalkError::InvalidPattern(_))));
+    }
+}
@@ -1,23 +1,27 @@
 use super::filter::Filter;
 
-type BoxedFilter<T> = Box<dyn Filter<T>>;
-
 /// Boolean And filter
 /// Values must match all of the included filters
-pub(super) struct And<T> {
-    filters: Vec<BoxedFilter<T>>,
+///
+/// Generic over the type of the included filters, use a
+/// `Box<dyn Filter<T>>` for dynamic dispatch.
+pub(super) struct And<F> {
+    filters: Vec<F>,
 }
 
-impl<T> Filter<T> for And<T>
+impl<T, F> Filter<T> for And<F>
 where
     T: Copy,
+    F: Filter<T>,
 {
+    #[inline]
     fn include(&self, result: T) -> bool {
         return self.filters.iter().all(|x| x.include(result));
     }
 }
 
-impl<T> And<T> {
+#[allow(dead_code)]
+impl<F> And<F> {
     #[must_use]
     pub fn new() -> Self {
         return And {
@@ -26,16 +30,11 @@ impl<T> And<T> {
     }
 
     #[must_use]
-    pub fn with_filters(filters: Vec<BoxedFilter<T>>) -> Self {
+    pub fn with_filters(filters: Vec<F>) -> Self {
         return And { filters };
     }
 
-    pub fn with_box(filters: Vec<BoxedFilter<T>>) -> Box<Self> {
-        return Box::new(Self::with_filters(filters));
-    }
-
-    pub fn add(&mut self, filter: Box<dyn Filter<T>>) {
-        // TODO(danilan): Consider moving boxing to this function
+    pub fn add(&mut self, filter: F) {
         self.filters.push(filter);
     }
 }
@@ -56,7 +55,7 @@ mod tests {
 
     #[test]
     fn and_true_false_returns_false() {
-        let and = And::with_filters(vec![Box::new(EqualFilter(2)), Box::new(EqualFilter(3))]);
+        let and = And::with_filters(vec![EqualFilter(2), EqualFilter(3)]);
 
         assert!(!and.include(2));
         assert!(!and.include(3));
@@ -64,18 +63,27 @@ mod tests {
 
     #[test]
     fn and_true_true_returns_true() {
-        let and = And::with_filters(vec![Box::new(EqualFilter(3)), Box::new(EqualFilter(3))]);
+        let and = And::with_filters(vec![EqualFilter(3), EqualFilter(3)]);
 
         assert!(and.include(3));
     }
 
     #[test]
     fn and_false_false_returns_false() {
-        let and = And::with_filters(vec![Box::new(EqualFilter(2)), Box::new(EqualFilter(1))]);
+        let and = And::with_filters(vec![EqualFilter(2), EqualFilter(1)]);
 
         assert!(!and.include(3));
     }
 
+    #[test]
+    fn and_boxed_filters() {
+        let and: And<Box<dyn Filter<i32>>> =
+            And::with_filters(vec![Box::new(EqualFilter(3)), Box::new(EqualFilter(3))]);
+
+        assert!(and.include(3));
+        assert!(!and.include(2));
+    }
+
     #[test]
     fn and_add_method() {
         let mut and = And::new();
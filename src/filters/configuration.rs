@@ -1,100 +1,171 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, Mutex};
 
 use crate::{
-    types::{bit_type::BitType, compare::Compare},
+    types::compare::{Compare, Tolerance},
     workers::entropy_processor::EntropyProducer,
 };
 
 use super::{
-    and::And, entropy::Entropy, equal::Equal, filter::Filter, max::Max, min::Min,
-    notequal::NotEqual, notequal_exact::NotEqualExact,
+    and::And,
+    date_range::DateRange,
+    entropy::Entropy,
+    equal::Equal,
+    equal_any::EqualAny,
+    expression::Expression,
+    filter::Filter,
+    max::Max,
+    min::Min,
+    notequal::NotEqual,
+    notequal_exact::NotEqualExact,
+    tree::{Comparison, FilterTree, Node},
 };
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Configuration<T: Compare + 'static> {
     pub minimum: Option<T>,
     pub maximum: Option<T>,
-    pub literal: Option<T>,
+    /// Values must be equal to one of the literals
+    pub literals: Vec<T>,
     pub exclude_zero: bool,
     pub exclude_literal: Option<T>,
     pub entropy: Option<EntropyConfig>,
+    /// Boolean expression, combined with the other filters using And
+    pub expression: Option<Expression<T>>,
+    /// How close floats must be to a literal to be equal
+    pub tolerance: Tolerance,
+    /// Earliest date in nanoseconds since the Unix epoch, only matches timestamp types
+    pub date_minimum: Option<i128>,
+    /// Latest date in nanoseconds since the Unix epoch, only matches timestamp types
+    pub date_maximum: Option<i128>,
 }
 
-type BoxedFilter<T> = Box<dyn Filter<T>>;
-
 impl<T: Compare> Configuration<T> {
+    pub fn create_filter(&self) -> Option<FilterTree<T>> {
+        let mut filters = self.create_nodes();
+
+        if filters.len() <= 1 {
+            return filters.pop().map(FilterTree::new);
+        }
+
+        return Some(FilterTree::new(Node::and(filters)));
+    }
+
+    /// Same filters as [`Configuration::create_filter`] in an And of trait objects,
+    /// dispatching every filter dynamically. Used to benchmark the filter tree.
+    #[must_use]
+    pub fn create_dyn_filter(&self) -> Option<Box<dyn Filter<T>>> {
+        let filters: Vec<Box<dyn Filter<T>>> = self
+            .create_nodes()
+            .into_iter()
+            .map(|node| Box::new(node) as Box<dyn Filter<T>>)
+            .collect();
+
+        if filters.is_empty() {
+            return None;
+        }
+
+        return Some(Box::new(And::with_filters(filters)));
+    }
+
     #[allow(clippy::option_map_unit_fn)]
-    pub fn create_filter(&self) -> Option<BoxedFilter<T>> {
-        let mut filters: Vec<BoxedFilter<T>> = Vec::with_capacity(5);
+    fn create_nodes(&self) -> Vec<Node<T>> {
+        let mut filters: Vec<Node<T>> = Vec::with_capacity(7);
 
         self.create_equal_filter().map(|f| filters.push(f));
         self.create_max_filter().map(|f| filters.push(f));
         self.create_min_filter().map(|f| filters.push(f));
+        self.create_date_range_filter().map(|f| filters.push(f));
         self.create_exclude_zero_filter().map(|f| filters.push(f));
         self.create_exclude_literal_filter()
             .map(|f| filters.push(f));
+        self.expression
+            .as_ref()
+            .map(|expression| filters.push(expression.create_filter(&self.tolerance)));
 
         self.entropy
             .as_ref()
-            .and_then(EntropyConfig::create_filter)
+            .map(EntropyConfig::create_filter)
             .map(|f| filters.push(f));
 
-        if filters.is_empty() {
-            return None;
-        }
-
-        return Some(And::with_box(filters));
+        return filters;
     }
 
-    fn create_max_filter(&self) -> Option<BoxedFilter<T>> {
+    fn create_max_filter(&self) -> Option<Node<T>> {
         if let Some(max) = self.maximum {
-            return Some(Max::with_box(max));
+            return Some(Node::Compare(Comparison::Max(Max::new(max))));
         }
 
         return None;
     }
 
-    fn create_min_filter(&self) -> Option<BoxedFilter<T>> {
+    fn create_min_filter(&self) -> Option<Node<T>> {
         if let Some(min) = self.minimum {
-            return Some(Min::with_box(min));
+            return Some(Node::Compare(Comparison::Min(Min::new(min))));
         }
 
         return None;
     }
 
-    fn create_equal_filter(&self) -> Option<BoxedFilter<T>> {
-        if let Some(literal) = self.literal {
-            return Some(Equal::with_box(literal));
+    fn create_date_range_filter(&self) -> Option<Node<T>> {
+        if self.date_minimum.is_none() && self.date_maximum.is_none() {
+            return None;
         }
 
-        return None;
+        return Some(Node::Compare(Comparison::DateRange(DateRange::new(
+            self.date_minimum,
+            self.date_maximum,
+        ))));
+    }
+
+    fn create_equal_filter(&self) -> Option<Node<T>> {
+        return match self.literals.as_slice() {
+            [] => None,
+            [literal] => Some(Node::Compare(Comparison::Equal(Equal::with_tolerance(
+                *literal,
+                self.tolerance,
+            )))),
+            literals => Some(Node::EqualAny(EqualAny::new(literals, self.tolerance))),
+        };
     }
 
-    fn create_exclude_zero_filter(&self) -> Option<BoxedFilter<T>> {
+    fn create_exclude_zero_filter(&self) -> Option<Node<T>> {
         if self.exclude_zero {
-            return Some(NotEqualExact::with_box(T::zero()));
+            return Some(Node::Compare(Comparison::NotEqualExact(
+                NotEqualExact::new(T::zero()),
+            )));
         }
 
         return None;
     }
 
-    fn create_exclude_literal_filter(&self) -> Option<BoxedFilter<T>> {
+    fn create_exclude_literal_filter(&self) -> Option<Node<T>> {
         if let Some(literal) = self.exclude_literal {
-            return Some(NotEqual::with_box(literal));
+            return Some(Node::Compare(Comparison::NotEqual(
+                NotEqual::with_tolerance(literal, self.tolerance),
+            )));
         }
 
         return None;
     }
 }
 
+#[derive(Clone)]
 pub struct EntropyConfig {
     pub max_entropy: f64,
-    pub entropy_producer: Rc<RefCell<dyn EntropyProducer>>,
+    pub entropy_producer: Arc<Mutex<dyn EntropyProducer>>,
 }
 
 impl EntropyConfig {
-    fn create_filter<T: BitType>(&self) -> Option<BoxedFilter<T>> {
-        let boxed_filter = Entropy::with_box(self.max_entropy, self.entropy_producer.clone());
-        return Some(boxed_filter as BoxedFilter<T>);
+    fn create_filter<T: Compare>(&self) -> Node<T> {
+        return Node::Entropy(Entropy::new(
+            self.max_entropy,
+            self.entropy_producer.clone(),
+        ));
+    }
+
+    /// Creates an entropy filter for values that are not numbers, e.g. regex matches
+    #[must_use]
+    pub fn create_value_filter<T>(&self) -> impl Filter<T> {
+        return Entropy::new(self.max_entropy, self.entropy_producer.clone());
     }
 }
@@ -0,0 +1,62 @@
+use super::filter::Filter;
+use crate::types::compare::Compare;
+
+/// Implements an inclusive range of dates, in nanoseconds since the Unix epoch.
+/// Works the same for every timestamp type, values that are not dates are excluded.
+pub(super) struct DateRange {
+    min: Option<i128>,
+    max: Option<i128>,
+}
+
+impl<T: Compare> Filter<T> for DateRange {
+    #[inline]
+    fn include(&self, result: T) -> bool {
+        let Some(nanos) = result.nanos() else {
+            return false;
+        };
+
+        return self.min.map_or(true, |min| min <= nanos)
+            && self.max.map_or(true, |max| nanos <= max);
+    }
+}
+
+impl DateRange {
+    #[must_use]
+    pub fn new(min: Option<i128>, max: Option<i128>) -> Self {
+        return DateRange { min, max };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::timestamp::{Timestamp, Unix32, Unix64Ms};
+
+    const SECOND: i128 = 1_000_000_000;
+
+    #[test]
+    fn date_range_inclusive() {
+        let filter = DateRange::new(Some(10 * SECOND), Some(20 * SECOND));
+
+        assert!(filter.include(Timestamp::<Unix32>::from_raw(10)));
+        assert!(filter.include(Timestamp::<Unix32>::from_raw(20)));
+        assert!(!filter.include(Timestamp::<Unix32>::from_raw(9)));
+        assert!(!filter.include(Timestamp::<Unix32>::from_raw(21)));
+    }
+
+    #[test]
+    fn date_range_compares_nanos() {
+        let filter = DateRange::new(Some(10 * SECOND + 1), None);
+
+        assert!(!filter.include(Timestamp::<Unix64Ms>::from_raw(10_000)));
+        assert!(filter.include(Timestamp::<Unix64Ms>::from_raw(10_001)));
+    }
+
+    #[test]
+    fn date_range_excludes_numbers() {
+        let filter = DateRange::new(None, None);
+
+        assert!(!filter.include(5u32));
+        assert!(!filter.include(5.0f64));
+    }
+}
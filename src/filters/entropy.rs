@@ -1,9 +1,9 @@
 use super::filter::Filter;
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, Mutex};
 
-use crate::{types::bit_type::BitType, workers::entropy_processor::EntropyProducer};
+use crate::workers::entropy_processor::EntropyProducer;
 
-type EntropyProducerRef = Rc<RefCell<dyn EntropyProducer>>;
+type EntropyProducerRef = Arc<Mutex<dyn EntropyProducer>>;
 
 /// Implements a max entropy filter
 /// Used to ignore noise from compression/encryption
@@ -20,9 +20,13 @@ pub(super) struct Entropy {
     producer: EntropyProducerRef,
 }
 
-impl<T: BitType> Filter<T> for Entropy {
+impl<T> Filter<T> for Entropy {
     fn include(&self, _: T) -> bool {
-        let nearby_entropy = self.producer.borrow().entropy();
+        let nearby_entropy = self
+            .producer
+            .lock()
+            .expect("entropy producer lock should not be poisoned")
+            .entropy();
 
         return nearby_entropy.is_some_and(|en| en <= self.max_entropy);
     }
@@ -36,11 +40,6 @@ impl Entropy {
             producer: entropy_producer,
         };
     }
-
-    #[must_use]
-    pub fn with_box(max_entropy: f64, entropy_producer: EntropyProducerRef) -> Box<Self> {
-        return Box::new(Self::new(max_entropy, entropy_producer));
-    }
 }
 
 #[cfg(test)]
@@ -57,7 +56,7 @@ mod tests {
 
     #[test]
     fn include_none_returns_false() {
-        let none_producer = Rc::new(RefCell::new(FakeEntropyProducer(None)));
+        let none_producer = Arc::new(Mutex::new(FakeEntropyProducer(None)));
         let dont_care = 0;
 
         assert!(!Entropy::new(5.0, none_producer).include(dont_care));
@@ -65,7 +64,7 @@ mod tests {
 
     #[test]
     fn include_entropy_greater_than_max_returns_false() {
-        let entropy_producer = Rc::new(RefCell::new(FakeEntropyProducer(Some(3.345678))));
+        let entropy_producer = Arc::new(Mutex::new(FakeEntropyProducer(Some(3.345_678))));
         let dont_care = 0;
 
         assert!(!Entropy::new(1.0, entropy_producer.clone()).include(dont_care));
@@ -76,10 +75,10 @@ mod tests {
 
     #[test]
     fn include_entropy_less_equal_than_max_returns_true() {
-        let entropy_producer = Rc::new(RefCell::new(FakeEntropyProducer(Some(3.345678))));
+        let entropy_producer = Arc::new(Mutex::new(FakeEntropyProducer(Some(3.345_678))));
         let dont_care = 0;
 
-        assert!(Entropy::new(3.345678, entropy_producer.clone()).include(dont_care));
+        assert!(Entropy::new(3.345_678, entropy_producer.clone()).include(dont_care));
         assert!(Entropy::new(3.5, entropy_producer.clone()).include(dont_care));
         assert!(Entropy::new(4.0, entropy_producer.clone()).include(dont_care));
         assert!(Entropy::new(5.0, entropy_producer.clone()).include(dont_care));
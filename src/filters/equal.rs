@@ -1,30 +1,32 @@
 use super::filter::Filter;
-use crate::types::compare::Compare;
+use crate::types::compare::{Compare, Tolerance};
 
 /// Implements an equal filter.
 /// Includes elements that are equal to a literal.
 ///
-/// In floats uses ULPS of 4 by default.
+/// In floats uses ULPS of 4 by default, see [`Tolerance`].
 /// See implementation in types/compare.rs
 pub(super) struct Equal<T> {
     literal: T,
+    tolerance: Tolerance,
 }
 
 impl<T: Compare> Filter<T> for Equal<T> {
     fn include(&self, result: T) -> bool {
-        return result.equal(&self.literal);
+        return result.equal(&self.literal, &self.tolerance);
     }
 }
 
 impl<T> Equal<T> {
+    #[allow(dead_code)]
     #[must_use]
     pub fn new(literal: T) -> Self {
-        return Equal { literal };
+        return Self::with_tolerance(literal, Tolerance::default());
     }
 
     #[must_use]
-    pub fn with_box(literal: T) -> Box<Self> {
-        return Box::new(Self::new(literal));
+    pub fn with_tolerance(literal: T, tolerance: Tolerance) -> Self {
+        return Equal { literal, tolerance };
     }
 }
 
@@ -85,4 +87,16 @@ mod tests {
         assert!(Equal::<f64>::new(f64::INFINITY).include(f64::INFINITY));
         assert!(Equal::<f32>::new(f32::NEG_INFINITY).include(f32::NEG_INFINITY));
     }
+
+    #[test]
+    fn filter_float_equal_with_tolerance() {
+        let tolerance = Tolerance {
+            abs_epsilon: Some(0.01),
+            ..Default::default()
+        };
+
+        assert!(Equal::<f64>::with_tolerance(32.445, tolerance).include(32.45));
+        assert!(!Equal::<f64>::with_tolerance(32.445, tolerance).include(32.46));
+        assert!(Equal::<i32>::with_tolerance(31, tolerance).include(31));
+    }
 }
@@ -0,0 +1,56 @@
+use super::filter::Filter;
+use crate::types::{
+    compare::{Compare, Tolerance},
+    literal_set::LiteralSet,
+};
+
+/// Implements an equal filter for a set of literals.
+/// Includes elements that are equal to any of the literals.
+///
+/// Integers are looked up in a hash set and floats in a sorted set,
+/// see [`Compare::Set`].
+pub(super) struct EqualAny<T: Compare> {
+    literals: T::Set,
+}
+
+impl<T: Compare> Filter<T> for EqualAny<T> {
+    #[inline]
+    fn include(&self, result: T) -> bool {
+        return self.literals.contains(&result);
+    }
+}
+
+impl<T: Compare> EqualAny<T> {
+    #[must_use]
+    pub fn new(literals: &[T], tolerance: Tolerance) -> Self {
+        return EqualAny {
+            literals: T::Set::new(literals, tolerance),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_equal_any_integers() {
+        let filter = EqualAny::new(&[31i32, -4, 1 << 20], Tolerance::default());
+
+        assert!(filter.include(31));
+        assert!(filter.include(-4));
+        assert!(filter.include(1 << 20));
+        assert!(!filter.include(4));
+    }
+
+    #[test]
+    fn filter_equal_any_floats() {
+        let filter = EqualAny::new(&[32.445f32, -0.0, f32::NAN], Tolerance::default());
+
+        assert!(filter.include(32.445));
+        assert!(filter.include(32.445 + f32::EPSILON));
+        assert!(filter.include(0.0));
+        assert!(filter.include(f32::NAN));
+        assert!(!filter.include(33.445));
+    }
+}
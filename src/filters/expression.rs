@@ -0,0 +1,364 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::{
+    equal::Equal,
+    max::Max,
+    min::Min,
+    not::Not,
+    notequal::NotEqual,
+    notequal_exact::NotEqualExact,
+    or::Or,
+    tree::{Comparison, Node},
+};
+use crate::types::compare::{Compare, Tolerance};
+
+/// Characters that end a value token
+const VALUE_DELIMITERS: &str = "()&|";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ExpressionError {
+    #[error("Unexpected end of expression")]
+    UnexpectedEnd,
+    #[error("Unexpected '{1}' at position {0}")]
+    UnexpectedToken(usize, String),
+    #[error("Invalid value '{1}' at position {0}")]
+    InvalidValue(usize, String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operator {
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Operator {
+    /// Longer tokens come first so `>=` is not parsed as `>`
+    const TOKENS: [(&'static str, Operator); 6] = [
+        (">=", Operator::GreaterEqual),
+        ("<=", Operator::LessEqual),
+        ("==", Operator::Equal),
+        ("!=", Operator::NotEqual),
+        (">", Operator::Greater),
+        ("<", Operator::Less),
+    ];
+}
+
+/// A boolean filter expression, e.g. `(>=10 && <=20) || ==99`.
+///
+/// Grammar, from lowest to highest precedence:
+/// ```text
+/// or         := and ('||' and)*
+/// and        := unary ('&&' unary)*
+/// unary      := '!' unary | '(' or ')' | comparison
+/// comparison := ('>=' | '<=' | '==' | '!=' | '>' | '<')? value
+/// ```
+/// A value without an operator is compared with `==`.
+/// Equality uses the same approximate float comparison as `--literal`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression<T> {
+    Compare(Operator, T),
+    And(Vec<Expression<T>>),
+    Or(Vec<Expression<T>>),
+    Not(Box<Expression<T>>),
+}
+
+impl<T: FromStr> Expression<T> {
+    /// # Errors
+    /// Returns an error if the expression is malformed or a value can't be parsed as `T`
+    pub fn parse(input: &str) -> Result<Self, ExpressionError> {
+        let mut parser = Parser { input, position: 0 };
+
+        let expression = parser.parse_or()?;
+
+        parser.skip_whitespace();
+        if !parser.rest().is_empty() {
+            return Err(parser.unexpected());
+        }
+
+        return Ok(expression);
+    }
+}
+
+impl<T: Compare> Expression<T> {
+    pub(super) fn create_filter(&self, tolerance: &Tolerance) -> Node<T> {
+        let create_filter = |expression: &Expression<T>| expression.create_filter(tolerance);
+
+        return match self {
+            Expression::Compare(operator, value) => {
+                create_compare_filter(*operator, *value, *tolerance)
+            }
+            Expression::And(expressions) => {
+                Node::and(expressions.iter().map(create_filter).collect())
+            }
+            Expression::Or(expressions) => Node::Or(Or::with_filters(
+                expressions.iter().map(create_filter).collect(),
+            )),
+            Expression::Not(expression) => Node::Not(Not::new(Box::new(create_filter(expression)))),
+        };
+    }
+}
+
+fn create_compare_filter<T: Compare>(
+    operator: Operator,
+    value: T,
+    tolerance: Tolerance,
+) -> Node<T> {
+    return match operator {
+        Operator::GreaterEqual => Node::Compare(Comparison::Min(Min::new(value))),
+        Operator::LessEqual => Node::Compare(Comparison::Max(Max::new(value))),
+        Operator::Equal => {
+            Node::Compare(Comparison::Equal(Equal::with_tolerance(value, tolerance)))
+        }
+        Operator::NotEqual => Node::Compare(Comparison::NotEqual(NotEqual::with_tolerance(
+            value, tolerance,
+        ))),
+        // Not using Not(Max) so NaN is excluded, like in the inclusive filters
+        Operator::Greater => Node::and(vec![
+            Node::Compare(Comparison::Min(Min::new(value))),
+            Node::Compare(Comparison::NotEqualExact(NotEqualExact::new(value))),
+        ]),
+        Operator::Less => Node::and(vec![
+            Node::Compare(Comparison::Max(Max::new(value))),
+            Node::Compare(Comparison::NotEqualExact(NotEqualExact::new(value))),
+        ]),
+    };
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn parse_or<T: FromStr>(&mut self) -> Result<Expression<T>, ExpressionError> {
+        let mut expressions = vec![self.parse_and()?];
+        while self.eat("||") {
+            expressions.push(self.parse_and()?);
+        }
+
+        if expressions.len() == 1 {
+            return Ok(expressions.remove(0));
+        }
+
+        return Ok(Expression::Or(expressions));
+    }
+
+    fn parse_and<T: FromStr>(&mut self) -> Result<Expression<T>, ExpressionError> {
+        let mut expressions = vec![self.parse_unary()?];
+        while self.eat("&&") {
+            expressions.push(self.parse_unary()?);
+        }
+
+        if expressions.len() == 1 {
+            return Ok(expressions.remove(0));
+        }
+
+        return Ok(Expression::And(expressions));
+    }
+
+    fn parse_unary<T: FromStr>(&mut self) -> Result<Expression<T>, ExpressionError> {
+        self.skip_whitespace();
+
+        if !self.rest().starts_with("!=") && self.eat("!") {
+            return Ok(Expression::Not(Box::new(self.parse_unary()?)));
+        }
+
+        if self.eat("(") {
+            let expression = self.parse_or()?;
+            if !self.eat(")") {
+                return Err(self.unexpected());
+            }
+
+            return Ok(expression);
+        }
+
+        return self.parse_comparison();
+    }
+
+    fn parse_comparison<T: FromStr>(&mut self) -> Result<Expression<T>, ExpressionError> {
+        let operator = Operator::TOKENS
+            .iter()
+            .find(|(token, _)| self.eat(token))
+            .map_or(Operator::Equal, |(_, operator)| *operator);
+
+        self.skip_whitespace();
+        let start = self.position;
+        let length = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || VALUE_DELIMITERS.contains(c))
+            .unwrap_or(self.rest().len());
+
+        if length == 0 {
+            return Err(self.unexpected());
+        }
+
+        let token = &self.input[start..start + length];
+        self.position += length;
+
+        let value = T::from_str(token)
+            .map_err(|_| ExpressionError::InvalidValue(start, token.to_string()))?;
+
+        return Ok(Expression::Compare(operator, value));
+    }
+
+    fn rest(&self) -> &str {
+        return &self.input[self.position..];
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes `token` if it's next, ignoring whitespace
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if !self.rest().starts_with(token) {
+            return false;
+        }
+
+        self.position += token.len();
+        return true;
+    }
+
+    fn unexpected(&self) -> ExpressionError {
+        return match self.rest().chars().next() {
+            None => ExpressionError::UnexpectedEnd,
+            Some(c) => ExpressionError::UnexpectedToken(self.position, c.to_string()),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::filter::Filter;
+
+    fn compare<T>(operator: Operator, value: T) -> Expression<T> {
+        return Expression::Compare(operator, value);
+    }
+
+    #[test]
+    fn parse_comparison() {
+        assert_eq!(
+            Expression::parse(">=10"),
+            Ok(compare(Operator::GreaterEqual, 10i32))
+        );
+        assert_eq!(
+            Expression::parse(" != -3.5 "),
+            Ok(compare(Operator::NotEqual, -3.5f64))
+        );
+        assert_eq!(Expression::parse("< 7"), Ok(compare(Operator::Less, 7u8)));
+    }
+
+    #[test]
+    fn parse_bare_value_is_equal() {
+        assert_eq!(Expression::parse("99"), Ok(compare(Operator::Equal, 99i64)));
+    }
+
+    #[test]
+    fn parse_and_binds_tighter_than_or() {
+        assert_eq!(
+            Expression::parse(">=10 && <=20 || ==99"),
+            Ok(Expression::Or(vec![
+                Expression::And(vec![
+                    compare(Operator::GreaterEqual, 10i32),
+                    compare(Operator::LessEqual, 20),
+                ]),
+                compare(Operator::Equal, 99),
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_grouping_and_not() {
+        assert_eq!(
+            Expression::parse("!(>1||<-1)&&!=0"),
+            Ok(Expression::And(vec![
+                Expression::Not(Box::new(Expression::Or(vec![
+                    compare(Operator::Greater, 1i32),
+                    compare(Operator::Less, -1),
+                ]))),
+                compare(Operator::NotEqual, 0),
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Expression::<i32>::parse(">=10 &&"),
+            Err(ExpressionError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Expression::<i32>::parse("(>=10"),
+            Err(ExpressionError::UnexpectedEnd)
+        );
+        assert_eq!(
+            Expression::<i32>::parse(">=10 )"),
+            Err(ExpressionError::UnexpectedToken(5, ")".to_string()))
+        );
+        assert_eq!(
+            Expression::<u8>::parse("<= 300"),
+            Err(ExpressionError::InvalidValue(3, "300".to_string()))
+        );
+        assert_eq!(
+            Expression::<i32>::parse("10 20"),
+            Err(ExpressionError::UnexpectedToken(3, "2".to_string()))
+        );
+    }
+
+    #[test]
+    fn create_filter_ranges() {
+        let filter = Expression::parse("(>=10 && <=20) || (>80 && <90)")
+            .unwrap()
+            .create_filter(&Tolerance::default());
+
+        assert!(filter.include(10i32));
+        assert!(filter.include(20));
+        assert!(filter.include(81));
+        assert!(!filter.include(80));
+        assert!(!filter.include(90));
+        assert!(!filter.include(50));
+    }
+
+    #[test]
+    fn create_filter_float_literals() {
+        let filter = Expression::parse("3.14 || 2.71")
+            .unwrap()
+            .create_filter(&Tolerance::default());
+
+        assert!(filter.include(3.14f64));
+        assert!(filter.include(2.71f64));
+        assert!(!filter.include(3.15f64));
+    }
+
+    #[test]
+    fn create_filter_strict_excludes_nan() {
+        let filter = Expression::parse(">0")
+            .unwrap()
+            .create_filter(&Tolerance::default());
+
+        assert!(filter.include(1.0f32));
+        assert!(!filter.include(0.0f32));
+        assert!(!filter.include(-0.0f32));
+        assert!(!filter.include(f32::NAN));
+    }
+
+    #[test]
+    fn create_filter_not() {
+        let filter = Expression::parse("!(>=0 && <=9)")
+            .unwrap()
+            .create_filter(&Tolerance::default());
+
+        assert!(filter.include(-1i16));
+        assert!(filter.include(10i16));
+        assert!(!filter.include(5i16));
+    }
+}
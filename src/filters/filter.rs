@@ -1,9 +1,11 @@
 /// Filters a result according to configuration
-pub trait Filter<T> {
+/// Filters are [`Send`] so a scan can be moved to another thread.
+pub trait Filter<T>: Send {
     /// Should output the result
     /// true to include, false to exclude.
     fn include(&self, result: T) -> bool;
 
+    #[inline]
     fn include_unwrap(&self, result: Option<T>) -> bool {
         if result.is_none() {
             return false;
@@ -12,3 +14,23 @@ pub trait Filter<T> {
         return self.include(result.unwrap());
     }
 }
+
+impl<T, F> Filter<T> for Box<F>
+where
+    F: Filter<T> + ?Sized,
+{
+    #[inline]
+    fn include(&self, result: T) -> bool {
+        return (**self).include(result);
+    }
+}
+
+/// Includes every result, for processors that only return matches
+pub struct IncludeAll;
+
+impl<T> Filter<T> for IncludeAll {
+    #[inline]
+    fn include(&self, _: T) -> bool {
+        return true;
+    }
+}
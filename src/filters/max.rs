@@ -6,7 +6,7 @@ pub(super) struct Max<T> {
     max: T,
 }
 
-impl<T: std::cmp::PartialOrd> Filter<T> for Max<T> {
+impl<T: std::cmp::PartialOrd + Send> Filter<T> for Max<T> {
     fn include(&self, result: T) -> bool {
         return result <= self.max;
     }
@@ -16,10 +16,6 @@ impl<T> Max<T> {
     pub fn new(max: T) -> Max<T> {
         return Max { max };
     }
-
-    pub fn with_box(max: T) -> Box<Max<T>> {
-        return Box::new(Self::new(max));
-    }
 }
 
 #[cfg(test)]
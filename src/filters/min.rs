@@ -6,7 +6,7 @@ pub(super) struct Min<T> {
     min: T,
 }
 
-impl<T: std::cmp::PartialOrd> Filter<T> for Min<T> {
+impl<T: std::cmp::PartialOrd + Send> Filter<T> for Min<T> {
     fn include(&self, result: T) -> bool {
         return self.min <= result;
     }
@@ -16,10 +16,6 @@ impl<T> Min<T> {
     pub fn new(min: T) -> Min<T> {
         return Min { min };
     }
-
-    pub fn with_box(min: T) -> Box<Min<T>> {
-        return Box::new(Self::new(min));
-    }
 }
 
 #[cfg(test)]
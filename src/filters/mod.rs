@@ -1,10 +1,17 @@
 pub mod configuration;
+pub mod expression;
 pub mod filter;
+pub mod text_number;
+pub mod tree;
 
 mod and;
+mod date_range;
 mod entropy;
 mod equal;
+mod equal_any;
 mod max;
 mod min;
+mod not;
 mod notequal;
 mod notequal_exact;
+mod or;
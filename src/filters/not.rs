@@ -0,0 +1,53 @@
+use super::filter::Filter;
+
+/// Boolean Not filter
+/// Includes values that are excluded by the inner filter
+pub(super) struct Not<F> {
+    filter: F,
+}
+
+impl<T, F> Filter<T> for Not<F>
+where
+    F: Filter<T>,
+{
+    #[inline]
+    fn include(&self, result: T) -> bool {
+        return !self.filter.include(result);
+    }
+}
+
+impl<F> Not<F> {
+    #[must_use]
+    pub fn new(filter: F) -> Self {
+        return Not { filter };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EqualFilter(i32);
+
+    impl Filter<i32> for EqualFilter {
+        fn include(&self, result: i32) -> bool {
+            return self.0 == result;
+        }
+    }
+
+    #[test]
+    fn not_inverts_filter() {
+        let not = Not::new(EqualFilter(2));
+
+        assert!(!not.include(2));
+        assert!(not.include(3));
+    }
+
+    #[test]
+    fn not_not_returns_filter() {
+        let not = Not::new(Not::new(EqualFilter(2)));
+
+        assert!(not.include(2));
+        assert!(!not.include(3));
+    }
+}
@@ -1,30 +1,32 @@
 use super::filter::Filter;
-use crate::types::compare::Compare;
+use crate::types::compare::{Compare, Tolerance};
 
 /// Implements a non-exact non-equal filter.
 /// Includes elements that are not approximately equal to a literal.
 ///
-/// In floats uses ULPS of 4 by default.
+/// In floats uses ULPS of 4 by default, see [`Tolerance`].
 /// See implementation in types/compare.rs
 pub(super) struct NotEqual<T> {
     literal: T,
+    tolerance: Tolerance,
 }
 
 impl<T: Compare> Filter<T> for NotEqual<T> {
     fn include(&self, result: T) -> bool {
-        return !result.equal(&self.literal);
+        return !result.equal(&self.literal, &self.tolerance);
     }
 }
 
 impl<T> NotEqual<T> {
+    #[allow(dead_code)]
     #[must_use]
     pub fn new(literal: T) -> Self {
-        return NotEqual { literal };
+        return Self::with_tolerance(literal, Tolerance::default());
     }
 
     #[must_use]
-    pub fn with_box(literal: T) -> Box<Self> {
-        return Box::new(Self::new(literal));
+    pub fn with_tolerance(literal: T, tolerance: Tolerance) -> Self {
+        return NotEqual { literal, tolerance };
     }
 }
 
@@ -84,4 +86,15 @@ mod tests {
         assert!(!NotEqual::<f64>::new(f64::INFINITY).include(f64::INFINITY));
         assert!(!NotEqual::<f32>::new(f32::NEG_INFINITY).include(f32::NEG_INFINITY));
     }
+
+    #[test]
+    fn filter_float_not_equal_with_tolerance() {
+        let tolerance = Tolerance {
+            rel_epsilon: Some(0.1),
+            ..Default::default()
+        };
+
+        assert!(!NotEqual::<f32>::with_tolerance(100.0, tolerance).include(91.0));
+        assert!(NotEqual::<f32>::with_tolerance(100.0, tolerance).include(89.0));
+    }
 }
@@ -21,11 +21,6 @@ impl<T> NotEqualExact<T> {
     pub fn new(literal: T) -> Self {
         return NotEqualExact { literal };
     }
-
-    #[must_use]
-    pub fn with_box(literal: T) -> Box<Self> {
-        return Box::new(Self::new(literal));
-    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,63 @@
+use super::filter::Filter;
+
+/// Boolean Or filter
+/// Values must match at least one of the included filters
+///
+/// Generic over the type of the included filters, use a
+/// `Box<dyn Filter<T>>` for dynamic dispatch.
+pub(super) struct Or<F> {
+    filters: Vec<F>,
+}
+
+impl<T, F> Filter<T> for Or<F>
+where
+    T: Copy,
+    F: Filter<T>,
+{
+    #[inline]
+    fn include(&self, result: T) -> bool {
+        return self.filters.iter().any(|x| x.include(result));
+    }
+}
+
+impl<F> Or<F> {
+    #[must_use]
+    pub fn with_filters(filters: Vec<F>) -> Self {
+        return Or { filters };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EqualFilter(i32);
+
+    impl Filter<i32> for EqualFilter {
+        fn include(&self, result: i32) -> bool {
+            return self.0 == result;
+        }
+    }
+
+    #[test]
+    fn or_true_false_returns_true() {
+        let or = Or::with_filters(vec![EqualFilter(2), EqualFilter(3)]);
+
+        assert!(or.include(2));
+        assert!(or.include(3));
+    }
+
+    #[test]
+    fn or_false_false_returns_false() {
+        let or = Or::with_filters(vec![EqualFilter(2), EqualFilter(1)]);
+
+        assert!(!or.include(3));
+    }
+
+    #[test]
+    fn or_empty_returns_false() {
+        let or: Or<EqualFilter> = Or::with_filters(vec![]);
+
+        assert!(!or.include(3));
+    }
+}
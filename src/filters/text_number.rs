@@ -0,0 +1,46 @@
+use super::filter::Filter;
+use crate::types::bit_type::BitType;
+use crate::types::text_number::TextNumber;
+
+/// Applies a filter of numbers to the values of numbers found in text
+pub struct TextNumberFilter<F> {
+    filter: F,
+}
+
+impl<F> TextNumberFilter<F> {
+    #[must_use]
+    pub fn new(filter: F) -> Self {
+        return TextNumberFilter { filter };
+    }
+}
+
+impl<T, F> Filter<TextNumber<T>> for TextNumberFilter<F>
+where
+    T: BitType,
+    F: Filter<T>,
+{
+    #[inline]
+    fn include(&self, result: TextNumber<T>) -> bool {
+        return self.filter.include(result.value());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::configuration::Configuration;
+    use crate::types::string_match::StringEncoding;
+
+    #[test]
+    fn filters_value() {
+        let config = Configuration::<i32> {
+            minimum: Some(10),
+            maximum: Some(20),
+            ..Default::default()
+        };
+        let filter = TextNumberFilter::new(config.create_filter().unwrap());
+
+        assert!(filter.include(TextNumber::new(15, StringEncoding::Utf8, 2)));
+        assert!(!filter.include(TextNumber::new(21, StringEncoding::Utf16Be, 4)));
+    }
+}
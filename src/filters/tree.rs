@@ -0,0 +1,172 @@
+use super::{
+    date_range::DateRange, entropy::Entropy, equal::Equal, equal_any::EqualAny, filter::Filter,
+    max::Max, min::Min, not::Not, notequal::NotEqual, notequal_exact::NotEqualExact, or::Or,
+};
+use crate::types::compare::Compare;
+
+/// A statically dispatched filter tree, created by a [`super::configuration::Configuration`].
+///
+/// Every node is matched instead of going through a vtable, leaves are inlined
+/// into their parent so only nested And, Or and Not nodes are called.
+pub struct FilterTree<T: Compare>(Node<T>);
+
+pub(super) enum Node<T: Compare> {
+    Compare(Comparison<T>),
+    EqualAny(EqualAny<T>),
+    Entropy(Entropy),
+    And(AllOf<T>),
+    Or(Or<Node<T>>),
+    Not(Not<Box<Node<T>>>),
+}
+
+/// Children of an And node.
+///
+/// Comparisons are cheap and evaluated first, the other children (entropy,
+/// nested nodes) are only evaluated when all comparisons match.
+pub(super) struct AllOf<T: Compare> {
+    comparisons: Vec<Comparison<T>>,
+    others: Vec<Node<T>>,
+}
+
+/// Leaves that only compare the value, matched without any nested arm
+pub(super) enum Comparison<T: Compare> {
+    Min(Min<T>),
+    Max(Max<T>),
+    Equal(Equal<T>),
+    NotEqual(NotEqual<T>),
+    NotEqualExact(NotEqualExact<T>),
+    DateRange(DateRange),
+}
+
+impl<T: Compare> Filter<T> for Comparison<T> {
+    #[inline]
+    fn include(&self, result: T) -> bool {
+        return match self {
+            Comparison::Min(filter) => filter.include(result),
+            Comparison::Max(filter) => filter.include(result),
+            Comparison::Equal(filter) => filter.include(result),
+            Comparison::NotEqual(filter) => filter.include(result),
+            Comparison::NotEqualExact(filter) => filter.include(result),
+            Comparison::DateRange(filter) => filter.include(result),
+        };
+    }
+}
+
+impl<T: Compare> Filter<T> for AllOf<T> {
+    #[inline]
+    fn include(&self, result: T) -> bool {
+        return self.comparisons.iter().all(|node| node.include(result))
+            && self.others.iter().all(|node| node.include(result));
+    }
+}
+
+impl<T: Compare> Node<T> {
+    /// An And node of `filters`, keeping their order within comparisons and other filters
+    pub(super) fn and(filters: Vec<Node<T>>) -> Self {
+        let mut comparisons = Vec::new();
+        let mut others = Vec::new();
+        for filter in filters {
+            match filter {
+                Node::Compare(comparison) => comparisons.push(comparison),
+                other => others.push(other),
+            }
+        }
+        return Node::And(AllOf {
+            comparisons,
+            others,
+        });
+    }
+
+    /// Nested nodes are kept out of line so their parents can inline the leaves
+    #[inline(never)]
+    fn include_nested(&self, result: T) -> bool {
+        return match self {
+            Node::And(filter) => filter.include(result),
+            Node::Or(filter) => filter.include(result),
+            Node::Not(filter) => filter.include(result),
+            _ => self.include(result),
+        };
+    }
+}
+
+impl<T: Compare> Filter<T> for Node<T> {
+    #[inline]
+    fn include(&self, result: T) -> bool {
+        return match self {
+            Node::Compare(filter) => filter.include(result),
+            Node::EqualAny(filter) => filter.include(result),
+            Node::Entropy(filter) => filter.include(result),
+            Node::And(_) | Node::Or(_) | Node::Not(_) => self.include_nested(result),
+        };
+    }
+}
+
+impl<T: Compare> Filter<T> for FilterTree<T> {
+    #[inline]
+    fn include(&self, result: T) -> bool {
+        return self.0.include(result);
+    }
+}
+
+impl<T: Compare> FilterTree<T> {
+    pub(super) fn new(root: Node<T>) -> Self {
+        return FilterTree(root);
+    }
+
+    /// An empty tree that includes every result, for processors that only return matches
+    #[must_use]
+    pub fn include_all() -> Self {
+        return FilterTree(Node::and(vec![]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn and_tree_includes_range() {
+        let tree = FilterTree::new(Node::and(vec![
+            Node::Compare(Comparison::Min(Min::new(10))),
+            Node::Compare(Comparison::Max(Max::new(20))),
+            Node::Compare(Comparison::NotEqualExact(NotEqualExact::new(15))),
+        ]));
+
+        assert!(tree.include(10));
+        assert!(tree.include(20));
+        assert!(!tree.include(15));
+        assert!(!tree.include(9));
+        assert!(!tree.include(21));
+    }
+
+    #[test]
+    fn empty_tree_includes_all() {
+        let tree = FilterTree::<i32>::include_all();
+
+        assert!(tree.include(0));
+        assert!(tree.include(i32::MIN));
+    }
+
+    #[test]
+    fn or_not_tree() {
+        let tree = FilterTree::new(Node::Or(Or::with_filters(vec![
+            Node::Compare(Comparison::Equal(Equal::new(3))),
+            Node::Not(Not::new(Box::new(Node::Compare(Comparison::Min(
+                Min::new(0),
+            ))))),
+        ])));
+
+        assert!(tree.include(3));
+        assert!(tree.include(-1));
+        assert!(!tree.include(0));
+        assert!(!tree.include(4));
+    }
+
+    #[test]
+    fn equal_tree() {
+        let tree = FilterTree::new(Node::Compare(Comparison::Equal(Equal::new(3.5f64))));
+
+        assert!(tree.include(3.5));
+        assert!(!tree.include(3.6));
+    }
+}
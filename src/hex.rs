@@ -8,8 +8,14 @@ pub enum DecodeHexError {
     ParseIntError(#[from] std::num::ParseIntError),
     #[error("Invalid hex string format: {0}")]
     InvalidHexFormat(String),
+    #[error("Invalid hex pattern at position {0}: {1}")]
+    InvalidPattern(usize, String),
 }
 
+/// Decodes a plain hex string, see [`HexPattern`] for patterns
+///
+/// # Errors
+/// Returns an error if the string has an odd length or a character that isn't a hex digit
 pub fn decode(s: &str) -> Result<Vec<u8>, DecodeHexError> {
     if s.len() % 2 != 0 {
         return Err(DecodeHexError::InvalidHexFormat(s.to_string()));
@@ -35,8 +41,481 @@ where
 {
     let mut result = String::new();
     for byte in bytes.as_ref() {
-        write!(&mut result, "{byte:x}").expect("should write to string");
+        write!(&mut result, "{byte:02x}").expect("should write to string");
     }
 
     return result;
 }
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A byte whose bits in `mask` equal `value`, `?` nibbles are not in the mask
+    Byte { value: u8, mask: u8 },
+    /// An inclusive range of bytes
+    Range(u8, u8),
+    /// Between `min` and `max` bytes of anything
+    Gap { min: usize, max: usize },
+    /// Any of several sequences
+    Alternation(Vec<Vec<Token>>),
+}
+
+impl Token {
+    fn min_size(&self) -> usize {
+        return match self {
+            Token::Byte { .. } | Token::Range(..) => 1,
+            Token::Gap { min, .. } => *min,
+            Token::Alternation(alternatives) => alternatives
+                .iter()
+                .map(|tokens| sequence_size(tokens).0)
+                .min()
+                .unwrap_or(0),
+        };
+    }
+
+    fn max_size(&self) -> usize {
+        return match self {
+            Token::Byte { .. } | Token::Range(..) => 1,
+            Token::Gap { max, .. } => *max,
+            Token::Alternation(alternatives) => alternatives
+                .iter()
+                .map(|tokens| sequence_size(tokens).1)
+                .max()
+                .unwrap_or(0),
+        };
+    }
+}
+
+/// Minimum and maximum size of a sequence of tokens
+fn sequence_size(tokens: &[Token]) -> (usize, usize) {
+    return (
+        tokens.iter().map(Token::min_size).sum(),
+        tokens.iter().map(Token::max_size).sum(),
+    );
+}
+
+/// A compiled hex byte pattern, e.g. `0AAD[33-4A]DF`.
+///
+/// Syntax, whitespace is ignored:
+/// * `0A` - an exact byte
+/// * `?A`, `0?`, `??` - nibble wildcards
+/// * `[33-4A]` - an inclusive range of bytes
+/// * `{4}`, `{2,8}` - a gap of any bytes, with a bounded size
+/// * `(0A|0B0C)` - alternations, also allowed at the top level without parentheses
+///
+/// Alternatives are tried in order and gaps are as short as possible,
+/// the first match found at an offset is returned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HexPattern {
+    tokens: Vec<Token>,
+    min_size: usize,
+    max_size: usize,
+}
+
+impl HexPattern {
+    /// # Errors
+    /// Returns an error if the pattern is malformed or can match zero bytes
+    pub fn compile(pattern: &str) -> Result<Self, DecodeHexError> {
+        let mut parser = PatternParser {
+            chars: pattern.char_indices().collect(),
+            index: 0,
+        };
+
+        let tokens = parser.parse_alternation()?;
+        if let Some((position, c)) = parser.peek() {
+            return Err(DecodeHexError::InvalidPattern(
+                position,
+                format!("unexpected '{c}'"),
+            ));
+        }
+
+        let (min_size, max_size) = sequence_size(&tokens);
+        if min_size == 0 {
+            return Err(DecodeHexError::InvalidPattern(
+                0,
+                "pattern can match zero bytes".to_string(),
+            ));
+        }
+
+        return Ok(HexPattern {
+            tokens,
+            min_size,
+            max_size,
+        });
+    }
+
+    /// Size of the shortest possible match
+    #[must_use]
+    pub fn min_size(&self) -> usize {
+        return self.min_size;
+    }
+
+    /// Size of the longest possible match
+    #[must_use]
+    pub fn max_size(&self) -> usize {
+        return self.max_size;
+    }
+
+    /// Exact bytes every match starts with
+    #[must_use]
+    pub fn prefix(&self) -> Vec<u8> {
+        return self
+            .tokens
+            .iter()
+            .map_while(|token| match token {
+                Token::Byte { value, mask: 0xFF } => Some(*value),
+                _ => None,
+            })
+            .collect();
+    }
+
+    /// Returns the size of the match at the start of `data`, if any
+    #[must_use]
+    pub fn match_size(&self, data: &[u8]) -> Option<usize> {
+        return match_tokens(&self.tokens, None, data);
+    }
+}
+
+/// Tokens left to match after an alternation
+struct Rest<'a> {
+    tokens: &'a [Token],
+    next: Option<&'a Rest<'a>>,
+}
+
+fn match_tokens(tokens: &[Token], rest: Option<&Rest>, data: &[u8]) -> Option<usize> {
+    let Some((token, tokens)) = tokens.split_first() else {
+        return match rest {
+            None => Some(0),
+            Some(rest) => match_tokens(rest.tokens, rest.next, data),
+        };
+    };
+
+    return match token {
+        Token::Byte { value, mask } => {
+            let byte = *data.first()?;
+            if byte & mask != *value {
+                return None;
+            }
+
+            match_tokens(tokens, rest, &data[1..]).map(|size| size + 1)
+        }
+        Token::Range(low, high) => {
+            let byte = *data.first()?;
+            if !(*low..=*high).contains(&byte) {
+                return None;
+            }
+
+            match_tokens(tokens, rest, &data[1..]).map(|size| size + 1)
+        }
+        Token::Gap { min, max } => (*min..=*max).find_map(|gap| {
+            let data = data.get(gap..)?;
+            match_tokens(tokens, rest, data).map(|size| size + gap)
+        }),
+        Token::Alternation(alternatives) => {
+            let rest = Rest { tokens, next: rest };
+            alternatives
+                .iter()
+                .find_map(|alternative| match_tokens(alternative, Some(&rest), data))
+        }
+    };
+}
+
+struct PatternParser {
+    chars: Vec<(usize, char)>,
+    index: usize,
+}
+
+impl PatternParser {
+    /// alternation := sequence ('|' sequence)*
+    fn parse_alternation(&mut self) -> Result<Vec<Token>, DecodeHexError> {
+        let mut alternatives = vec![self.parse_sequence()?];
+        while self.eat('|') {
+            alternatives.push(self.parse_sequence()?);
+        }
+
+        if alternatives.len() == 1 {
+            return Ok(alternatives.remove(0));
+        }
+
+        return Ok(vec![Token::Alternation(alternatives)]);
+    }
+
+    /// sequence := (byte | range | gap | '(' alternation ')')*
+    fn parse_sequence(&mut self) -> Result<Vec<Token>, DecodeHexError> {
+        let mut tokens = vec![];
+
+        while let Some((position, c)) = self.peek() {
+            let token = match c {
+                '|' | ')' => break,
+                '(' => {
+                    self.index += 1;
+                    let alternation = self.parse_alternation()?;
+                    self.expect(')')?;
+                    Token::Alternation(vec![alternation])
+                }
+                '[' => {
+                    self.index += 1;
+                    let low = self.parse_exact_byte()?;
+                    self.expect('-')?;
+                    let high = self.parse_exact_byte()?;
+                    self.expect(']')?;
+
+                    if low > high {
+                        return Err(DecodeHexError::InvalidPattern(
+                            position,
+                            format!("range start {low:02X} is after its end {high:02X}"),
+                        ));
+                    }
+                    Token::Range(low, high)
+                }
+                '{' => {
+                    self.index += 1;
+                    let min = self.parse_number()?;
+                    let max = if self.eat(',') {
+                        self.parse_number()?
+                    } else {
+                        min
+                    };
+                    self.expect('}')?;
+
+                    if min > max {
+                        return Err(DecodeHexError::InvalidPattern(
+                            position,
+                            format!("gap minimum {min} is larger than its maximum {max}"),
+                        ));
+                    }
+                    Token::Gap { min, max }
+                }
+                _ => {
+                    let (value, mask) = self.parse_byte()?;
+                    Token::Byte { value, mask }
+                }
+            };
+
+            tokens.push(token);
+        }
+
+        return Ok(tokens);
+    }
+
+    /// Two hex digits or `?` wildcards, returns the value and mask
+    fn parse_byte(&mut self) -> Result<(u8, u8), DecodeHexError> {
+        let (high, high_mask) = self.parse_nibble()?;
+        let (low, low_mask) = self.parse_nibble()?;
+
+        return Ok((high << 4 | low, high_mask << 4 | low_mask));
+    }
+
+    fn parse_exact_byte(&mut self) -> Result<u8, DecodeHexError> {
+        let position = self.position();
+        let (value, mask) = self.parse_byte()?;
+        if mask != 0xFF {
+            return Err(DecodeHexError::InvalidPattern(
+                position,
+                "wildcards are not allowed in ranges".to_string(),
+            ));
+        }
+
+        return Ok(value);
+    }
+
+    /// Nibbles must not be separated by whitespace
+    fn parse_nibble(&mut self) -> Result<(u8, u8), DecodeHexError> {
+        let Some(&(position, c)) = self.chars.get(self.index) else {
+            return Err(self.unexpected_end());
+        };
+        self.index += 1;
+
+        if c == '?' {
+            return Ok((0, 0));
+        }
+
+        let digit = c.to_digit(16).ok_or_else(|| {
+            DecodeHexError::InvalidPattern(position, format!("'{c}' is not a hex digit"))
+        })?;
+
+        return Ok((u8::try_from(digit).expect("hex digits fit in a byte"), 0xF));
+    }
+
+    fn parse_number(&mut self) -> Result<usize, DecodeHexError> {
+        self.skip_whitespace();
+        let start = self.index;
+        while self
+            .chars
+            .get(self.index)
+            .is_some_and(|(_, c)| c.is_ascii_digit())
+        {
+            self.index += 1;
+        }
+
+        let digits: String = self.chars[start..self.index]
+            .iter()
+            .map(|(_, c)| c)
+            .collect();
+        return digits.parse().map_err(|_| {
+            DecodeHexError::InvalidPattern(self.position(), "expected a number".to_string())
+        });
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .chars
+            .get(self.index)
+            .is_some_and(|(_, c)| c.is_whitespace())
+        {
+            self.index += 1;
+        }
+    }
+
+    /// Next character that is not whitespace
+    fn peek(&mut self) -> Option<(usize, char)> {
+        self.skip_whitespace();
+        return self.chars.get(self.index).copied();
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek().is_some_and(|(_, c)| c == expected) {
+            self.index += 1;
+            return true;
+        }
+
+        return false;
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), DecodeHexError> {
+        if self.eat(expected) {
+            return Ok(());
+        }
+
+        return match self.peek() {
+            None => Err(self.unexpected_end()),
+            Some((position, c)) => Err(DecodeHexError::InvalidPattern(
+                position,
+                format!("expected '{expected}' but found '{c}'"),
+            )),
+        };
+    }
+
+    fn position(&self) -> usize {
+        return self.chars.get(self.index).map_or_else(
+            || self.chars.last().map_or(0, |(i, c)| i + c.len_utf8()),
+            |(i, _)| *i,
+        );
+    }
+
+    fn unexpected_end(&self) -> DecodeHexError {
+        return DecodeHexError::InvalidPattern(self.position(), "unexpected end".to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, data: &[u8]) -> Option<usize> {
+        return HexPattern::compile(pattern).unwrap().match_size(data);
+    }
+
+    #[test]
+    fn encode_pads_bytes() {
+        assert_eq!(encode([0x0Au8, 0xFF, 0x00]), "0aff00");
+    }
+
+    #[test]
+    fn decode_bytes() {
+        assert_eq!(decode("0aFF00"), Ok(vec![0x0A, 0xFF, 0x00]));
+        assert_eq!(
+            decode("0aF"),
+            Err(DecodeHexError::InvalidHexFormat("0aF".to_string()))
+        );
+    }
+
+    #[test]
+    fn pattern_exact_bytes() {
+        assert_eq!(matches("0AAD DF", &[0x0A, 0xAD, 0xDF, 0x00]), Some(3));
+        assert_eq!(matches("0aaddf", &[0x0A, 0xAD, 0xDF]), Some(3));
+        assert_eq!(matches("0AADDF", &[0x0A, 0xAD, 0xDE]), None);
+        assert_eq!(matches("0AADDF", &[0x0A, 0xAD]), None);
+    }
+
+    #[test]
+    fn pattern_nibble_wildcards() {
+        assert_eq!(matches("?A", &[0x3A]), Some(1));
+        assert_eq!(matches("?A", &[0x3B]), None);
+        assert_eq!(matches("A?", &[0xAF]), Some(1));
+        assert_eq!(matches("A?", &[0xBF]), None);
+        assert_eq!(matches("0A??DF", &[0x0A, 0x12, 0xDF]), Some(3));
+    }
+
+    #[test]
+    fn pattern_ranges() {
+        let pattern = HexPattern::compile("0AAD[33-4A]DF").unwrap();
+
+        assert_eq!(pattern.match_size(&[0x0A, 0xAD, 0x33, 0xDF]), Some(4));
+        assert_eq!(pattern.match_size(&[0x0A, 0xAD, 0x4A, 0xDF]), Some(4));
+        assert_eq!(pattern.match_size(&[0x0A, 0xAD, 0x32, 0xDF]), None);
+        assert_eq!(pattern.match_size(&[0x0A, 0xAD, 0x4B, 0xDF]), None);
+    }
+
+    #[test]
+    fn pattern_gaps() {
+        let pattern = HexPattern::compile("AA {1,3} BB").unwrap();
+
+        assert_eq!((pattern.min_size(), pattern.max_size()), (3, 5));
+        assert_eq!(pattern.match_size(&[0xAA, 0x00, 0xBB]), Some(3));
+        assert_eq!(pattern.match_size(&[0xAA, 0x00, 0x00, 0x00, 0xBB]), Some(5));
+        assert_eq!(pattern.match_size(&[0xAA, 0xBB, 0xBB, 0xBB]), Some(3));
+        assert_eq!(pattern.match_size(&[0xAA, 0xBB]), None);
+        assert_eq!(pattern.match_size(&[0xAA, 0, 0, 0, 0, 0xBB]), None);
+        assert_eq!(matches("AA{2}BB", &[0xAA, 0, 0, 0xBB]), Some(4));
+    }
+
+    #[test]
+    fn pattern_alternations() {
+        assert_eq!(matches("AA(BB|CCDD)EE", &[0xAA, 0xBB, 0xEE]), Some(3));
+        assert_eq!(matches("AA(BB|CCDD)EE", &[0xAA, 0xCC, 0xDD, 0xEE]), Some(4));
+        assert_eq!(matches("AA(BB|CCDD)EE", &[0xAA, 0xCC, 0xEE]), None);
+        assert_eq!(matches("AABB|CC", &[0xCC]), Some(1));
+        assert_eq!(matches("AABB|CC", &[0xAA, 0xBB]), Some(2));
+    }
+
+    #[test]
+    fn pattern_alternation_backtracks() {
+        // The first alternative matches but the rest of the pattern doesn't
+        assert_eq!(matches("(AA|AA{1}) BB", &[0xAA, 0x00, 0xBB]), Some(3));
+        assert_eq!(matches("(AA|AABB)CC", &[0xAA, 0xBB, 0xCC]), Some(3));
+    }
+
+    #[test]
+    fn pattern_sizes_and_prefix() {
+        let pattern = HexPattern::compile("0AAD?F(01|0203){2,4}").unwrap();
+
+        assert_eq!(pattern.min_size(), 6);
+        assert_eq!(pattern.max_size(), 9);
+        assert_eq!(pattern.prefix(), vec![0x0A, 0xAD]);
+    }
+
+    #[test]
+    fn pattern_errors() {
+        for (pattern, position) in [
+            ("0", 1),
+            ("0G", 1),
+            ("0A B", 4),
+            ("[33-4]", 5),
+            ("[4A-33]", 0),
+            ("[3?-4A]", 1),
+            ("{3,1}", 0),
+            ("{a}", 1),
+            ("(AA", 3),
+            ("AA)", 2),
+            ("{0,3}", 0),
+            ("", 0),
+            ("A A", 1),
+        ] {
+            let result = HexPattern::compile(pattern);
+            assert!(
+                matches!(result, Err(DecodeHexError::InvalidPattern(p, _)) if p == position),
+                "pattern {pattern} returned {result:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use clap::ValueEnum;
+use thiserror::Error;
+
+use crate::common::{DataType, Endianness};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum LayoutError {
+    #[error("Struct layout has no fields")]
+    Empty,
+    #[error("Invalid field '{0}', expected 'name: type [le|be] [filter]'")]
+    InvalidField(String),
+    #[error("Unknown type '{1}' of field '{0}'")]
+    UnknownType(String, String),
+    #[error("Duplicate field '{0}'")]
+    DuplicateField(String),
+    #[error("Invalid filter of field '{0}': {1}")]
+    InvalidFilter(String, String),
+}
+
+/// A field of a [`StructLayout`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSpec {
+    pub name: String,
+    pub data_type: DataType,
+    /// Overrides the endianness of the search
+    pub endianness: Option<Endianness>,
+    /// Filter expression of the field, see [`crate::filters::expression::Expression`]
+    pub filter: Option<String>,
+}
+
+/// A record of consecutive fields without padding, e.g. `{ id: u32 ==7, lat: f64 be >=40 && <=41, lon: f64 }`.
+///
+/// Syntax, the braces are optional:
+/// ```text
+/// layout := '{' field (',' field)* '}'
+/// field  := name ':' type ('le' | 'be')? expression?
+/// ```
+/// Types are the `--data-type` names, the expression is the `--expr` syntax for the field's type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructLayout {
+    fields: Vec<FieldSpec>,
+}
+
+impl StructLayout {
+    /// # Errors
+    /// Returns an error if the layout is malformed, a type is unknown or a name is repeated.
+    /// Filters are only parsed when the fields are created.
+    pub fn parse(layout: &str) -> Result<Self, LayoutError> {
+        let trimmed = layout.trim();
+        let inner = trimmed
+            .strip_prefix('{')
+            .and_then(|inner| inner.strip_suffix('}'))
+            .unwrap_or(trimmed);
+
+        let mut fields: Vec<FieldSpec> = vec![];
+        for text in inner.split(',').map(str::trim) {
+            if text.is_empty() {
+                continue;
+            }
+
+            let field = parse_field(text)?;
+            if fields.iter().any(|other| other.name == field.name) {
+                return Err(LayoutError::DuplicateField(field.name));
+            }
+
+            fields.push(field);
+        }
+
+        if fields.is_empty() {
+            return Err(LayoutError::Empty);
+        }
+
+        return Ok(StructLayout { fields });
+    }
+
+    #[must_use]
+    pub fn fields(&self) -> &[FieldSpec] {
+        return &self.fields;
+    }
+}
+
+fn parse_field(text: &str) -> Result<FieldSpec, LayoutError> {
+    let invalid = || LayoutError::InvalidField(text.to_string());
+
+    let (name, rest) = text.split_once(':').ok_or_else(invalid)?;
+    let name = name.trim();
+    let is_identifier = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_identifier {
+        return Err(invalid());
+    }
+
+    let mut words = rest.trim_start().splitn(2, char::is_whitespace);
+    let type_name = words
+        .next()
+        .filter(|word| !word.is_empty())
+        .ok_or_else(invalid)?;
+    let data_type = DataType::from_str(type_name, true)
+        .map_err(|_| LayoutError::UnknownType(name.to_string(), type_name.to_string()))?;
+
+    let rest = words.next().unwrap_or_default().trim();
+    let (first, after) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let endianness = match first {
+        "le" => Some(Endianness::Little),
+        "be" => Some(Endianness::Big),
+        _ => None,
+    };
+    let filter = if endianness.is_some() {
+        after.trim()
+    } else {
+        rest
+    };
+
+    return Ok(FieldSpec {
+        name: name.to_string(),
+        data_type,
+        endianness,
+        filter: Some(filter.to_string()).filter(|filter| !filter.is_empty()),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(
+        name: &str,
+        data_type: DataType,
+        endianness: Option<Endianness>,
+        filter: Option<&str>,
+    ) -> FieldSpec {
+        return FieldSpec {
+            name: name.into(),
+            data_type,
+            endianness,
+            filter: filter.map(Into::into),
+        };
+    }
+
+    #[test]
+    fn parse_fields() {
+        let layout = StructLayout::parse(
+            "{ id: u32 ==7, lat: f64 be >=40 && <=41, lon: f64, at: unix64-ms le }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            layout.fields(),
+            [
+                field("id", DataType::U32, None, Some("==7")),
+                field(
+                    "lat",
+                    DataType::F64,
+                    Some(Endianness::Big),
+                    Some(">=40 && <=41")
+                ),
+                field("lon", DataType::F64, None, None),
+                field("at", DataType::Unix64Ms, Some(Endianness::Little), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_without_braces() {
+        let layout = StructLayout::parse("addr: ipv4 be, port: u16 be 443,").unwrap();
+
+        assert_eq!(
+            layout.fields(),
+            [
+                field("addr", DataType::Ipv4, Some(Endianness::Big), None),
+                field("port", DataType::U16, Some(Endianness::Big), Some("443")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(StructLayout::parse("{ }"), Err(LayoutError::Empty));
+        assert_eq!(
+            StructLayout::parse("{ id u32 }"),
+            Err(LayoutError::InvalidField("id u32".into()))
+        );
+        assert_eq!(
+            StructLayout::parse("{ 1d: u32 }"),
+            Err(LayoutError::InvalidField("1d: u32".into()))
+        );
+        assert_eq!(
+            StructLayout::parse("{ id: }"),
+            Err(LayoutError::InvalidField("id:".into()))
+        );
+        assert_eq!(
+            StructLayout::parse("{ id: u33 }"),
+            Err(LayoutError::UnknownType("id".into(), "u33".into()))
+        );
+        assert_eq!(
+            StructLayout::parse("{ id: u32, id: u8 }"),
+            Err(LayoutError::DuplicateField("id".into()))
+        );
+    }
+}
@@ -1,10 +1,14 @@
 pub mod common;
+pub mod files;
 pub mod filters;
+pub mod hex;
+pub mod layout;
+pub mod parallel;
 pub mod printers;
+pub mod rules;
 pub mod scanner;
 pub mod types;
 pub mod workers;
 
 mod filebuffer;
-mod hex;
 mod utils;
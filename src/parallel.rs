@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Splits a file of `file_size` bytes to ranges of scanned offsets, each
+/// at most `chunk_size` long.
+///
+/// The ranges only cover the offsets a value may start at, a reader of a
+/// chunk should read an extra `value_size - 1` bytes past the end of the range
+/// so values crossing the chunk boundary are not missed.
+///
+/// # Panics
+/// Panics if `chunk_size` is zero
+#[must_use]
+pub fn split_chunks(file_size: usize, chunk_size: usize) -> Vec<Range<usize>> {
+    assert!(chunk_size > 0, "chunk_size must be greater than zero");
+
+    return (0..file_size)
+        .step_by(chunk_size)
+        .map(|start| start..file_size.min(start + chunk_size))
+        .collect();
+}
+
+/// Runs `work` on every job using up to `threads` threads.
+///
+/// Results are passed to `sink` on the calling thread in the same order
+/// as the jobs, regardless of the order in which the work was done.
+/// Results that finished early are held until all preceding results are sinked.
+pub fn run_ordered<J, R, W, S>(jobs: &[J], threads: usize, work: W, mut sink: S)
+where
+    J: Sync,
+    R: Send,
+    W: Fn(&J) -> R + Sync,
+    S: FnMut(R),
+{
+    let next_job = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, jobs.len().max(1)) {
+            let sender = sender.clone();
+            let next_job = &next_job;
+            let work = &work;
+
+            scope.spawn(move || loop {
+                let index = next_job.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(index) else {
+                    break;
+                };
+
+                if sender.send((index, work(job))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // Only workers hold senders now, receiving ends when they're all done
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_result = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+
+            while let Some(result) = pending.remove(&next_result) {
+                sink(result);
+                next_result += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use assertor::{assert_that, VecAssertion};
+
+    use super::*;
+
+    #[test]
+    fn split_chunks_exact() {
+        assert_that!(split_chunks(9, 3)).contains_exactly_in_order(vec![0..3, 3..6, 6..9]);
+    }
+
+    #[test]
+    fn split_chunks_remainder() {
+        assert_that!(split_chunks(10, 4)).contains_exactly_in_order(vec![0..4, 4..8, 8..10]);
+    }
+
+    #[test]
+    fn split_chunks_smaller_than_chunk() {
+        let chunks = split_chunks(3, 8);
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0], 0..3);
+    }
+
+    #[test]
+    fn split_chunks_empty() {
+        assert!(split_chunks(0, 8).is_empty());
+    }
+
+    #[test]
+    fn run_ordered_keeps_job_order() {
+        let jobs: Vec<u64> = (0..20).collect();
+        let mut results = vec![];
+
+        run_ordered(
+            &jobs,
+            4,
+            |job| {
+                // Make early jobs finish last
+                thread::sleep(Duration::from_millis(20 - job));
+                return job * 2;
+            },
+            |result| results.push(result),
+        );
+
+        assert_that!(results).contains_exactly_in_order((0..20).map(|x| x * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn run_ordered_no_jobs() {
+        let jobs: Vec<u64> = vec![];
+        let mut called = false;
+
+        run_ordered(&jobs, 4, |job| *job, |_| called = true);
+
+        assert!(!called);
+    }
+}
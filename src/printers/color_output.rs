@@ -0,0 +1,104 @@
+use std::fmt::Display;
+
+use super::output::{hex_dump, paint, Content, Highlight, Output, Stringifier};
+use crate::hex;
+
+/// Colors of grep's defaults, see `GREP_COLORS` in `man grep`
+const PATH_COLOR: &str = "35";
+const OFFSET_COLOR: &str = "32";
+const TYPE_COLOR: &str = "36";
+const VALUE_COLOR: &str = "1";
+const MATCH_COLOR: &str = "01;31";
+
+/// Prints the same as [`super::output::SimpleOutput`] with every part of the line
+/// in its own ANSI color, matched bytes are highlighted in hex dumps instead of bracketed.
+#[derive(Default)]
+pub struct ColorOutput {
+    content: Content,
+}
+
+impl ColorOutput {
+    #[must_use]
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Prints a hex dump of the captured context after every value
+    #[must_use]
+    pub fn with_hex_dump() -> Self {
+        return ColorOutput {
+            content: Content::Context,
+        };
+    }
+}
+
+impl<T: Display> Stringifier<T> for ColorOutput {
+    fn stringify(&self, output: Output<T>) -> String {
+        let line = format!(
+            "{}: [{}] {}: {} [{}]",
+            paint(PATH_COLOR, output.file_path.display()),
+            paint(
+                OFFSET_COLOR,
+                format_args!("{:#01X}", output.data_context.offset)
+            ),
+            paint(TYPE_COLOR, output.type_label()),
+            paint(VALUE_COLOR, &output.value),
+            paint(
+                MATCH_COLOR,
+                hex::encode(output.data_context.value_as_slice())
+            ),
+        );
+
+        let highlight = Highlight::Color {
+            value: MATCH_COLOR,
+            offset: OFFSET_COLOR,
+        };
+
+        return match self.content {
+            Content::Simple => line,
+            Content::Context => line + &hex_dump(&output.data_context, highlight),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::printers::output::{ContextSize, DataContext};
+
+    fn output(data: &[u8], context: ContextSize) -> Output<u16> {
+        let data_context = DataContext::with_context(data, 2..4, 0x10, context);
+        return Output::new(Path::new("ok"), 1, "u16".into(), data_context);
+    }
+
+    #[test]
+    fn stringify_colors_parts() {
+        assert_eq!(
+            ColorOutput::new().stringify(output(b"abcdef", ContextSize::default())),
+            "\x1b[35mok\x1b[0m: [\x1b[32m0x12\x1b[0m] \x1b[36mu16\x1b[0m: \x1b[1m1\x1b[0m \
+             [\x1b[01;31m6364\x1b[0m]"
+        );
+    }
+
+    #[test]
+    fn stringify_hex_dump_highlights_value() {
+        let context = ContextSize {
+            before: 1,
+            after: 1,
+        };
+        let stringified = ColorOutput::with_hex_dump().stringify(output(b"abcdef", context));
+        let dump = stringified.split_once('\n').unwrap().1;
+
+        // 11 blank bytes after the context, the gap between halves and the end of the row
+        let blank = " ".repeat(11 * 3 + 2);
+        assert_eq!(
+            dump,
+            format!(
+                "\x1b[32m00000010\x1b[0m     62 \x1b[01;31m63\x1b[0m \x1b[01;31m64\x1b[0m 65{blank} \
+                 | b\x1b[01;31mc\x1b[0m\x1b[01;31md\x1b[0me|"
+            )
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::fmt::Display;
+
+use super::output::{Output, Stringifier};
+use crate::common::Endianness;
+use crate::hex;
+
+/// A column of [`CsvOutput`], the fields of an [`Output`] and its data context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Column {
+    Path,
+    Offset,
+    Type,
+    Endianness,
+    Value,
+    Hex,
+    Entropy,
+    Rule,
+    /// Captured bytes before the value, empty without a context
+    Before,
+    /// Captured bytes after the value, empty without a context
+    After,
+}
+
+impl Column {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        return match self {
+            Column::Path => "path",
+            Column::Offset => "offset",
+            Column::Type => "type",
+            Column::Endianness => "endianness",
+            Column::Value => "value",
+            Column::Hex => "hex",
+            Column::Entropy => "entropy",
+            Column::Rule => "rule",
+            Column::Before => "before",
+            Column::After => "after",
+        };
+    }
+}
+
+/// Prints the selected columns of every value as a row of delimiter separated values.
+///
+/// Fields with the delimiter, quotes or line breaks are quoted like RFC 4180,
+/// fields that aren't known for a value are empty. The header is printed by the caller
+/// once before all rows, see [`CsvOutput::header`].
+pub struct CsvOutput {
+    columns: Vec<Column>,
+    delimiter: char,
+}
+
+impl CsvOutput {
+    /// Comma separated values
+    #[must_use]
+    pub fn csv(columns: Vec<Column>) -> Self {
+        return CsvOutput {
+            columns,
+            delimiter: ',',
+        };
+    }
+
+    /// Tab separated values
+    #[must_use]
+    pub fn tsv(columns: Vec<Column>) -> Self {
+        return CsvOutput {
+            columns,
+            delimiter: '\t',
+        };
+    }
+
+    /// Names of the columns
+    #[must_use]
+    pub fn header(&self) -> String {
+        let names: Vec<&str> = self.columns.iter().map(|column| column.name()).collect();
+        return self.row(names);
+    }
+
+    fn row<S: AsRef<str>>(&self, fields: impl IntoIterator<Item = S>) -> String {
+        let fields: Vec<String> = fields
+            .into_iter()
+            .map(|field| self.quote(field.as_ref()))
+            .collect();
+
+        return fields.join(&self.delimiter.to_string());
+    }
+
+    fn quote(&self, field: &str) -> String {
+        let needs_quotes = field
+            .chars()
+            .any(|c| c == self.delimiter || c == '"' || c == '\n' || c == '\r');
+        if !needs_quotes {
+            return field.to_string();
+        }
+
+        return format!("\"{}\"", field.replace('"', "\"\""));
+    }
+}
+
+impl<T: Display> Stringifier<T> for CsvOutput {
+    fn stringify(&self, output: Output<T>) -> String {
+        let context = &output.data_context;
+        let fields = self.columns.iter().map(|column| match column {
+            Column::Path => output.file_path.to_string_lossy().into_owned(),
+            Column::Offset => context.offset.to_string(),
+            Column::Type => output.value_type.clone(),
+            Column::Endianness => output
+                .endianness
+                .map(Endianness::name)
+                .unwrap_or_default()
+                .to_string(),
+            Column::Value => output.value.to_string(),
+            Column::Hex => hex::encode(context.value_as_slice()),
+            Column::Entropy => output
+                .entropy
+                .map(|entropy| entropy.to_string())
+                .unwrap_or_default(),
+            Column::Rule => output.rule.clone().unwrap_or_default(),
+            Column::Before => hex::encode(context.before_value()),
+            Column::After => hex::encode(context.after_value()),
+        });
+
+        return self.row(fields);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::printers::output::{ContextSize, DataContext};
+
+    const COLUMNS: [Column; 6] = [
+        Column::Path,
+        Column::Offset,
+        Column::Type,
+        Column::Value,
+        Column::Hex,
+        Column::Entropy,
+    ];
+
+    fn output(path: &str, value: &str) -> Output<String> {
+        let context = ContextSize {
+            before: 1,
+            after: 1,
+        };
+        let data_context = DataContext::with_context(b"abcdef", 2..4, 0x10, context);
+        return Output::new(Path::new(path), value.into(), "regex".into(), data_context);
+    }
+
+    #[test]
+    fn header_names_columns() {
+        assert_eq!(
+            CsvOutput::csv(COLUMNS.to_vec()).header(),
+            "path,offset,type,value,hex,entropy"
+        );
+        assert_eq!(
+            CsvOutput::tsv(vec![Column::Rule, Column::Before]).header(),
+            "rule\tbefore"
+        );
+    }
+
+    #[test]
+    fn stringify_selected_columns() {
+        let output = output("dir/ok", "cd").with_entropy(Some(2.5));
+
+        assert_eq!(
+            CsvOutput::csv(COLUMNS.to_vec()).stringify(output),
+            "dir/ok,18,regex,cd,6364,2.5"
+        );
+    }
+
+    #[test]
+    fn stringify_unknown_fields_empty() {
+        let columns = vec![
+            Column::Endianness,
+            Column::Entropy,
+            Column::Rule,
+            Column::After,
+        ];
+
+        assert_eq!(
+            CsvOutput::csv(columns).stringify(output("ok", "cd")),
+            ",,,65"
+        );
+    }
+
+    #[test]
+    fn stringify_quotes_fields() {
+        let columns = vec![Column::Path, Column::Value];
+
+        assert_eq!(
+            CsvOutput::csv(columns.clone()).stringify(output("a,b \"c\".bin", "x\ty")),
+            "\"a,b \"\"c\"\".bin\",x\ty"
+        );
+        assert_eq!(
+            CsvOutput::tsv(columns).stringify(output("a,b.bin", "x\ty\nz")),
+            "a,b.bin\t\"x\ty\nz\""
+        );
+    }
+}
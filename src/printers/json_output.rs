@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use super::output::{Content, Output, Stringifier};
+use crate::common::Endianness;
+use crate::hex;
+
+/// A line of [`JsonOutput`], fields are serialized in order
+#[derive(Serialize)]
+struct JsonLine<'a> {
+    path: Cow<'a, str>,
+    offset: usize,
+    #[serde(rename = "type")]
+    value_type: &'a str,
+    endianness: Option<&'static str>,
+    value: String,
+    hex: String,
+    entropy: Option<f64>,
+    rule: Option<&'a str>,
+    /// Captured bytes before and after the value, only with a context
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<String>,
+}
+
+/// Prints every value as a JSON object in its own line (JSON Lines), for tools like `jq`.
+/// Fields that aren't known for a value are null, e.g. the entropy without an entropy processor.
+#[derive(Default)]
+pub struct JsonOutput {
+    content: Content,
+}
+
+impl JsonOutput {
+    #[must_use]
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Adds the captured context in hex as `before` and `after`
+    #[must_use]
+    pub fn with_context() -> Self {
+        return JsonOutput {
+            content: Content::Context,
+        };
+    }
+}
+
+impl<T: Display> Stringifier<T> for JsonOutput {
+    fn stringify(&self, output: Output<T>) -> String {
+        let context = &output.data_context;
+        let (before, after) = match self.content {
+            Content::Simple => (None, None),
+            Content::Context => (
+                Some(hex::encode(context.before_value())),
+                Some(hex::encode(context.after_value())),
+            ),
+        };
+
+        let line = JsonLine {
+            path: output.file_path.to_string_lossy(),
+            offset: context.offset,
+            value_type: &output.value_type,
+            endianness: output.endianness.map(Endianness::name),
+            value: output.value.to_string(),
+            hex: hex::encode(context.value_as_slice()),
+            entropy: output.entropy,
+            rule: output.rule.as_deref(),
+            before,
+            after,
+        };
+
+        return serde_json::to_string(&line).expect("output should serialize to JSON");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::printers::output::{ContextSize, DataContext};
+
+    fn output(context: ContextSize) -> Output<f64> {
+        let data_context = DataContext::with_context(b"abcdef", 2..4, 0x10, context);
+        return Output::new(Path::new("dir/ok"), 34.41, "f64".into(), data_context);
+    }
+
+    #[test]
+    fn stringify_unknown_fields_null() {
+        assert_eq!(
+            JsonOutput::new().stringify(output(ContextSize::default())),
+            r#"{"path":"dir/ok","offset":18,"type":"f64","endianness":null,"value":"34.41","hex":"6364","entropy":null,"rule":null}"#
+        );
+    }
+
+    #[test]
+    fn stringify_all_fields() {
+        let context = ContextSize {
+            before: 1,
+            after: 4,
+        };
+        let output = output(context)
+            .with_endianness(Some(Endianness::Big))
+            .with_entropy(Some(3.5))
+            .with_rule(Some("lat"));
+
+        assert_eq!(
+            JsonOutput::with_context().stringify(output),
+            r#"{"path":"dir/ok","offset":18,"type":"f64","endianness":"big","value":"34.41","hex":"6364","entropy":3.5,"rule":"lat","before":"62","after":"6566"}"#
+        );
+    }
+}
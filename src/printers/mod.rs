@@ -1,3 +1,7 @@
+pub mod color_output;
+pub mod csv_output;
+pub mod json_output;
 pub mod output;
-pub(super) mod printer;
+pub mod printer;
 pub mod simple_printer;
+pub mod summary_printer;
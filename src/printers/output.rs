@@ -1,23 +1,46 @@
+use std::fmt::Write;
+use std::ops::Range;
 use std::path::Path;
 use std::string::String;
 use std::{fmt::Display, path::PathBuf};
 
+use crate::common::Endianness;
 use crate::hex;
 
+/// Bytes in a row of a hex dump
+const DUMP_ROW_SIZE: usize = 16;
+
+#[derive(Default)]
 pub(crate) enum Content {
+    #[default]
     Simple,
+    /// A hex dump of the value and its context after the line of the value
     Context,
 }
 
+/// Amount of bytes to capture around a value, like the lines of grep's `-B` and `-A`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ContextSize {
+    pub before: usize,
+    pub after: usize,
+}
+
 #[derive(Debug, PartialEq, Default)]
 pub struct Output<T>
 where
     T: Display,
 {
-    file_path: PathBuf,
-    value_type: String, // Enum?
-    value: T,           // Remove T and convert on own?
-    data_context: DataContext,
+    pub(super) file_path: PathBuf,
+    pub(super) value_type: String, // Enum?
+    pub(super) value: T,           // Remove T and convert on own?
+    pub(super) data_context: DataContext,
+
+    /// Endianness the value was read in, None for values without one (e.g. text)
+    pub(super) endianness: Option<Endianness>,
+    /// Entropy of the data before the value, None if not calculated
+    pub(super) entropy: Option<f64>,
+    /// Name of the rule that matched the value, see [`crate::rules::Rules`]
+    pub(super) rule: Option<String>,
 }
 
 impl<T> Output<T>
@@ -30,6 +53,35 @@ where
             value,
             value_type,
             data_context,
+            endianness: None,
+            entropy: None,
+            rule: None,
+        };
+    }
+
+    #[must_use]
+    pub fn with_endianness(mut self, endianness: Option<Endianness>) -> Self {
+        self.endianness = endianness;
+        return self;
+    }
+
+    #[must_use]
+    pub fn with_entropy(mut self, entropy: Option<f64>) -> Self {
+        self.entropy = entropy;
+        return self;
+    }
+
+    #[must_use]
+    pub fn with_rule(mut self, rule: Option<&str>) -> Self {
+        self.rule = rule.map(str::to_string);
+        return self;
+    }
+
+    /// The type of the value, preceded by the name of the rule that matched it
+    pub(super) fn type_label(&self) -> String {
+        return match &self.rule {
+            Some(rule) => format!("{rule} {}", self.value_type),
+            None => self.value_type.clone(),
         };
     }
 }
@@ -37,7 +89,7 @@ where
 #[derive(Debug, PartialEq, Default)]
 pub struct DataContext {
     data: Vec<u8>,
-    offset: usize,
+    pub(super) offset: usize,
 
     /// zero index of value in data
     value_index: usize,
@@ -46,6 +98,7 @@ pub struct DataContext {
 }
 
 impl DataContext {
+    #[must_use]
     pub fn new(data: Vec<u8>, offset: usize) -> Self {
         let size = data.len();
         return DataContext {
@@ -56,38 +109,274 @@ impl DataContext {
         };
     }
 
-    // TODO(danilan): add with_context()
+    /// The value at `value` in `data` and up to `context` bytes around it,
+    /// where `position` is the offset of `data`. Context is cut at the bounds of `data`.
+    #[must_use]
+    pub fn with_context(
+        data: &[u8],
+        value: Range<usize>,
+        position: usize,
+        context: ContextSize,
+    ) -> Self {
+        let start = value.start.saturating_sub(context.before);
+        let end = data.len().min(value.end + context.after);
 
-    fn value_as_slice(&self) -> &[u8] {
+        return DataContext {
+            data: data[start..end].to_vec(),
+            offset: position + value.start,
+            value_index: value.start - start,
+            value_size: value.len(),
+        };
+    }
+
+    pub(super) fn value_as_slice(&self) -> &[u8] {
         let range = self.value_index..(self.value_index + self.value_size);
         return &self.data[range];
     }
+
+    /// Captured bytes before the value
+    pub(super) fn before_value(&self) -> &[u8] {
+        return &self.data[..self.value_index];
+    }
+
+    /// Captured bytes after the value
+    pub(super) fn after_value(&self) -> &[u8] {
+        return &self.data[self.value_index + self.value_size..];
+    }
 }
 
 // TODO(danilan): Consider moving to newtypes implementing display
 #[derive(Default)]
-pub struct SimpleOutput {}
+pub struct SimpleOutput {
+    content: Content,
+}
 
 impl SimpleOutput {
     #[must_use]
     pub fn new() -> Self {
         return Self::default();
     }
+
+    /// Prints a hex dump of the captured context after every value,
+    /// see [`DataContext::with_context`]
+    #[must_use]
+    pub fn with_hex_dump() -> Self {
+        return SimpleOutput {
+            content: Content::Context,
+        };
+    }
+}
+
+/// How a hex dump marks the bytes of the value
+#[derive(Clone, Copy)]
+pub(super) enum Highlight {
+    /// Brackets around the bytes, e.g. `00 01[02 03]04`
+    Brackets,
+    /// ANSI colors of the value's bytes in both columns, and of the offsets of rows
+    Color {
+        value: &'static str,
+        offset: &'static str,
+    },
+}
+
+/// `text` in an ANSI color, e.g. `01;31` for bold red
+pub(super) fn paint(color: &str, text: impl Display) -> String {
+    return format!("\x1b[{color}m{text}\x1b[0m");
+}
+
+/// Rows of 16 bytes aligned to their offset, like `hexdump -C`.
+/// Bytes outside the context are left blank.
+pub(super) fn hex_dump(context: &DataContext, highlight: Highlight) -> String {
+    let start = context.offset - context.value_index;
+    let end = start + context.data.len();
+    let value = context.offset..context.offset + context.value_size;
+
+    let mut dump = String::new();
+    let mut row = start - start % DUMP_ROW_SIZE;
+    while row < end {
+        match highlight {
+            Highlight::Brackets => write!(&mut dump, "\n{row:08x} "),
+            Highlight::Color { offset: color, .. } => {
+                write!(&mut dump, "\n{} ", paint(color, format_args!("{row:08x}")))
+            }
+        }
+        .expect("should write to string");
+
+        let mut ascii = String::with_capacity(DUMP_ROW_SIZE);
+        for offset in row..row + DUMP_ROW_SIZE {
+            // A value continued from the previous row is opened again
+            let opens = value.contains(&offset) && (offset == value.start || offset == row);
+            let closes = offset == value.end && offset != row;
+            let separator = match (highlight, opens, closes) {
+                (Highlight::Brackets, true, _) => '[',
+                (Highlight::Brackets, _, true) => ']',
+                _ => ' ',
+            };
+
+            // The halves of a row are separated by an extra space, brackets stay next to the value
+            match (separator, offset == row + DUMP_ROW_SIZE / 2) {
+                (']', true) => dump.push_str("] "),
+                (_, true) => {
+                    dump.push(' ');
+                    dump.push(separator);
+                }
+                _ => dump.push(separator),
+            }
+
+            if (start..end).contains(&offset) {
+                let byte = context.data[offset - start];
+                let character = if byte.is_ascii_graphic() || byte == b' ' {
+                    char::from(byte)
+                } else {
+                    '.'
+                };
+
+                match highlight {
+                    Highlight::Color { value: color, .. } if value.contains(&offset) => {
+                        dump.push_str(&paint(color, format_args!("{byte:02x}")));
+                        ascii.push_str(&paint(color, character));
+                    }
+                    _ => {
+                        write!(&mut dump, "{byte:02x}").expect("should write to string");
+                        ascii.push(character);
+                    }
+                }
+            } else {
+                dump.push_str("  ");
+                // Characters stay under their column, rows are only cut short at the end
+                if offset < start {
+                    ascii.push(' ');
+                }
+            }
+        }
+
+        let last = row + DUMP_ROW_SIZE;
+        let closes = matches!(highlight, Highlight::Brackets) && value.contains(&(last - 1));
+        dump.push(if closes { ']' } else { ' ' });
+        write!(&mut dump, " |{ascii}|").expect("should write to string");
+
+        row = last;
+    }
+
+    return dump;
 }
 
 pub trait Stringifier<T: Display> {
     fn stringify(&self, output: Output<T>) -> String;
 }
 
+impl<T, S> Stringifier<T> for Box<S>
+where
+    T: Display,
+    S: Stringifier<T> + ?Sized,
+{
+    fn stringify(&self, output: Output<T>) -> String {
+        return (**self).stringify(output);
+    }
+}
+
 impl<T: Display> Stringifier<T> for SimpleOutput {
     fn stringify(&self, output: Output<T>) -> String {
-        return format!(
+        let line = format!(
             "{}: [{:#01X}] {}: {} [{}]",
             output.file_path.display(),
             output.data_context.offset,
-            output.value_type,
+            output.type_label(),
             output.value,
             hex::encode(output.data_context.value_as_slice()),
         );
+
+        return match self.content {
+            Content::Simple => line,
+            Content::Context => line + &hex_dump(&output.data_context, Highlight::Brackets),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stringify(
+        output: &SimpleOutput,
+        data: &[u8],
+        value: Range<usize>,
+        context: ContextSize,
+    ) -> String {
+        let data_context = DataContext::with_context(data, value, 0x10, context);
+        return output.stringify(Output::new(Path::new("ok"), 1, "u16".into(), data_context));
+    }
+
+    #[test]
+    fn with_context_cut_at_bounds() {
+        let context = ContextSize {
+            before: 4,
+            after: 8,
+        };
+        let data_context = DataContext::with_context(b"abcdefgh", 2..4, 0x10, context);
+
+        assert_eq!(data_context.data, b"abcdefgh");
+        assert_eq!(data_context.offset, 0x12);
+        assert_eq!(data_context.value_index, 2);
+        assert_eq!(data_context.value_as_slice(), b"cd");
+    }
+
+    #[test]
+    fn stringify_simple_ignores_context() {
+        let context = ContextSize {
+            before: 2,
+            after: 2,
+        };
+
+        assert_eq!(
+            stringify(&SimpleOutput::new(), b"abcdefgh", 2..4, context),
+            "ok: [0x12] u16: 1 [6364]"
+        );
+    }
+
+    #[test]
+    fn stringify_rule_precedes_type() {
+        let data_context = DataContext::new(b"cd".to_vec(), 0x12);
+        let output =
+            Output::new(Path::new("ok"), 1, "u16".into(), data_context).with_rule(Some("port"));
+
+        assert_eq!(
+            SimpleOutput::new().stringify(output),
+            "ok: [0x12] port u16: 1 [6364]"
+        );
+    }
+
+    #[test]
+    fn stringify_hex_dump() {
+        let data: Vec<u8> = (0x30..0x50).collect();
+        let context = ContextSize {
+            before: 3,
+            after: 20,
+        };
+
+        assert_eq!(
+            stringify(&SimpleOutput::with_hex_dump(), &data, 6..8, context),
+            "ok: [0x16] u16: 1 [3637]\n\
+             00000010           33 34 35[36 37] 38 39 3a 3b 3c 3d 3e 3f  |   3456789:;<=>?|\n\
+             00000020  40 41 42 43 44 45 46 47  48 49 4a 4b              |@ABCDEFGHIJK|"
+        );
+    }
+
+    #[test]
+    fn stringify_hex_dump_value_across_rows() {
+        let data: Vec<u8> = (0x30..0x50).collect();
+        let value = 0x0E..0x1A;
+
+        assert_eq!(
+            stringify(
+                &SimpleOutput::with_hex_dump(),
+                &data,
+                value,
+                ContextSize::default()
+            ),
+            "ok: [0x1E] u16: 1 [3e3f40414243444546474849]\n\
+             00000010                                            [3e 3f] |              >?|\n\
+             00000020 [40 41 42 43 44 45 46 47  48 49]                   |@ABCDEFGHI|"
+        );
     }
 }
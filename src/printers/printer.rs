@@ -8,9 +8,29 @@ where
     T: Display,
 {
     /// Feed a result to print
+    ///
+    /// # Errors
+    /// Returns an error if writing the result fails.
     fn feed(&mut self, output: Output<T>) -> Result<(), Box<dyn Error>>;
 
     /// Call when ending all processing, this allows
     /// printers to print a footer or flush buffered output.
+    ///
+    /// # Errors
+    /// Returns an error if writing or flushing the output fails.
     fn end(&mut self) -> Result<(), Box<dyn Error>>;
 }
+
+impl<T, P> Printer<T> for Box<P>
+where
+    T: Display,
+    P: Printer<T> + ?Sized,
+{
+    fn feed(&mut self, output: Output<T>) -> Result<(), Box<dyn Error>> {
+        return (**self).feed(output);
+    }
+
+    fn end(&mut self) -> Result<(), Box<dyn Error>> {
+        return (**self).end();
+    }
+}
@@ -71,7 +71,7 @@ mod tests {
     use assertor::{assert_that, VecAssertion};
 
     use crate::printers::{
-        output::{self, Output, Stringifier},
+        output::{Output, Stringifier},
         printer::Printer,
     };
 
@@ -80,7 +80,7 @@ mod tests {
     struct FakeStringifier {}
 
     impl Stringifier<i32> for FakeStringifier {
-        fn stringify(&self, output: Output<i32>) -> String {
+        fn stringify(&self, _output: Output<i32>) -> String {
             return "OK!".into();
         }
     }
@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+use std::{error::Error, fmt::Display, io::Write};
+
+use super::{output::Output, printer::Printer};
+
+/// What a [`SummaryPrinter`] prints at the end of a file, like grep's `-c`, `-l`, `-L` and `-q`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Summary {
+    /// The path and the amount of matches of every file
+    Count,
+    /// Paths of files with matches
+    FilesWithMatches,
+    /// Paths of files without matches
+    FilesWithoutMatch,
+    /// Nothing, matches are only reported by the exit code
+    Quiet,
+}
+
+/// Counts the matches of a file instead of printing them, prints a [`Summary`] at the end
+// TODO(danilan): Stop scanning a file after its first match with -l, -L and -q
+pub struct SummaryPrinter<W>
+where
+    W: Write,
+{
+    summary: Summary,
+    file_path: PathBuf,
+    matches: usize,
+    finished: bool,
+    io_writer: W,
+}
+
+impl<W: Write> SummaryPrinter<W> {
+    #[must_use]
+    pub fn new(summary: Summary, file_path: &Path, io_writer: W) -> Self {
+        return SummaryPrinter {
+            summary,
+            file_path: file_path.to_owned(),
+            matches: 0,
+            finished: false,
+            io_writer,
+        };
+    }
+}
+
+impl<T, W> Printer<T> for SummaryPrinter<W>
+where
+    T: Display,
+    W: Write,
+{
+    fn feed(&mut self, _: Output<T>) -> Result<(), Box<dyn Error>> {
+        assert!(!self.finished, "Cannot called feed() after end()");
+        self.matches += 1;
+
+        Ok(())
+    }
+
+    fn end(&mut self) -> Result<(), Box<dyn Error>> {
+        let path = self.file_path.display();
+        match self.summary {
+            Summary::Count => writeln!(self.io_writer, "{path}:{}", self.matches)?,
+            Summary::FilesWithMatches if self.matches > 0 => writeln!(self.io_writer, "{path}")?,
+            Summary::FilesWithoutMatch if self.matches == 0 => {
+                writeln!(self.io_writer, "{path}")?;
+            }
+            _ => {}
+        }
+
+        self.io_writer.flush()?;
+        self.finished = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printers::output::DataContext;
+
+    /// Prints the summary of a file with `matches` matches
+    fn print(summary: Summary, matches: usize) -> String {
+        let mut written = vec![];
+        let mut printer = SummaryPrinter::new(summary, Path::new("ok"), &mut written);
+
+        for _ in 0..matches {
+            let output = Output::new(Path::new("ok"), 1, "u8".into(), DataContext::default());
+            printer.feed(output).unwrap();
+        }
+        Printer::<u8>::end(&mut printer).unwrap();
+
+        return String::from_utf8(written).unwrap();
+    }
+
+    #[test]
+    fn end_prints_count() {
+        assert_eq!(print(Summary::Count, 3), "ok:3\n");
+        assert_eq!(print(Summary::Count, 0), "ok:0\n");
+    }
+
+    #[test]
+    fn end_prints_files() {
+        assert_eq!(print(Summary::FilesWithMatches, 2), "ok\n");
+        assert_eq!(print(Summary::FilesWithMatches, 0), "");
+        assert_eq!(print(Summary::FilesWithoutMatch, 2), "");
+        assert_eq!(print(Summary::FilesWithoutMatch, 0), "ok\n");
+    }
+
+    #[test]
+    fn end_quiet_prints_nothing() {
+        assert_eq!(print(Summary::Quiet, 2), "");
+    }
+}
@@ -0,0 +1,810 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::common::{DataType, Endianness};
+use crate::filters::configuration::Configuration;
+use crate::filters::expression::{Expression, Operator};
+use crate::filters::filter::IncludeAll;
+use crate::filters::text_number::TextNumberFilter;
+use crate::filters::tree::FilterTree;
+use crate::hex::HexPattern;
+use crate::types::apple_time::{Cocoa, HfsPlus};
+use crate::types::compare::{Compare, Tolerance};
+use crate::types::dos_time::Dos;
+use crate::types::gps_time::Gps;
+use crate::types::guid::Guid;
+use crate::types::ip::{IpAddress, IpRange, Ipv4, Ipv6};
+use crate::types::timestamp::{Timestamp, Unix32, Unix64, Unix64Ms, Unix64Ns, Unix64Us};
+use crate::types::windows_time::{DotnetTicks, Filetime, OleDate, SystemTime};
+use crate::workers::guid_processor::GuidProcessor;
+use crate::workers::hex_processor::HexProcessor;
+use crate::workers::native_processor::NativeProcessor;
+use crate::workers::regex_processor::RegexProcessor;
+use crate::workers::rule_processor::RuleProcessor;
+use crate::workers::string_processor::StringProcessor;
+use crate::workers::text_number_processor::TextNumberProcessor;
+
+#[derive(Debug, Error)]
+pub enum RuleError {
+    #[error("Failed reading rules file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid rules file: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Rules file has no types")]
+    NoTypes,
+    #[error("Type {0} must have a single type name, e.g. {{ \"double\": {{ \"min\": 1 }} }}")]
+    InvalidEntry(usize),
+    #[error("Invalid magic '{0}': {1}")]
+    InvalidMagic(String, String),
+    #[error("Unknown type '{1}' of rule '{0}'")]
+    UnknownType(String, String),
+    #[error("Duplicate rule '{0}'")]
+    DuplicateRule(String),
+    #[error("Invalid rule '{0}': {1}")]
+    InvalidRule(String, String),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    filters: FiltersSection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FiltersSection {
+    file: FileSection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileSection {
+    magic: Option<String>,
+    types: Vec<BTreeMap<String, RuleOptions>>,
+    entropy: Option<EntropySection>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntropySection {
+    max: f64,
+}
+
+/// Options of a single type entry, which options are allowed depends on the type
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct RuleOptions {
+    name: Option<String>,
+    /// A number, or a string for types that aren't numbers in JSON
+    min: Option<Value>,
+    max: Option<Value>,
+    /// A single value or an array of values
+    literal: Option<Value>,
+    expr: Option<String>,
+    #[serde(default, alias = "exclude_zero")]
+    exclude_zero: bool,
+    #[serde(default, alias = "as_string")]
+    as_string: bool,
+    endian: Option<String>,
+    regex: Option<String>,
+    #[serde(default, alias = "ignore_case")]
+    ignore_case: bool,
+    #[serde(default)]
+    cidr: Vec<String>,
+}
+
+/// A type entry of a rules file
+#[derive(Debug, Clone)]
+struct Rule {
+    name: String,
+    type_name: String,
+    options: RuleOptions,
+}
+
+/// Searches of a rules file that run together in a single scan, e.g.
+/// ```json
+/// {
+///   "filters": {
+///     "file": {
+///       "magic": "0x7F454C46",
+///       "types": [
+///         { "double": { "min": 80.3432, "max": 82.221112, "exclude-zero": true } },
+///         { "string": { "literal": "AMAzING" } },
+///         { "integer": { "name": "small", "min": -10, "max": 12, "as-string": true } }
+///       ],
+///       "entropy": { "max": 6 }
+///     }
+///   }
+/// }
+/// ```
+///
+/// Every entry of `types` is a rule with a single type: `double`, `float`, `integer`,
+/// a `--data-type` name, `string` (a `literal` or a `regex`) or `bytes` (a hex pattern `literal`).
+/// Files that don't start with the `magic` hex pattern are skipped.
+/// Rules are named `{type}#{position}` unless they have a `name`.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    magic: Option<HexPattern>,
+    entries: Vec<Rule>,
+    max_entropy: Option<f64>,
+}
+
+impl Rules {
+    /// # Errors
+    /// Returns an error if the file can't be read or the rules are invalid
+    pub fn load(path: &Path) -> Result<Self, RuleError> {
+        return Rules::parse(&std::fs::read_to_string(path)?);
+    }
+
+    /// Parses and validates the rules, every rule is created once so bad values are found here
+    ///
+    /// # Errors
+    /// Returns an error if the JSON doesn't match the format, a type is unknown,
+    /// an option isn't supported by its type or a value can't be parsed
+    pub fn parse(json: &str) -> Result<Self, RuleError> {
+        let file = serde_json::from_str::<RulesFile>(json)?.filters.file;
+
+        let magic = file
+            .magic
+            .map(|magic| {
+                let digits = magic.trim();
+                let digits = digits.strip_prefix("0x").unwrap_or(digits);
+                return HexPattern::compile(digits)
+                    .map_err(|err| RuleError::InvalidMagic(magic.clone(), err.to_string()));
+            })
+            .transpose()?;
+
+        let mut rules: Vec<Rule> = Vec::with_capacity(file.types.len());
+        for (index, entry) in file.types.into_iter().enumerate() {
+            let mut entry = entry.into_iter();
+            let (Some((type_name, options)), None) = (entry.next(), entry.next()) else {
+                return Err(RuleError::InvalidEntry(index + 1));
+            };
+
+            let name = options
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{type_name}#{}", index + 1));
+            if rules.iter().any(|rule| rule.name == name) {
+                return Err(RuleError::DuplicateRule(name));
+            }
+
+            rules.push(Rule {
+                name,
+                type_name,
+                options,
+            });
+        }
+
+        if rules.is_empty() {
+            return Err(RuleError::NoTypes);
+        }
+
+        let parsed = Rules {
+            magic,
+            entries: rules,
+            max_entropy: file.entropy.map(|entropy| entropy.max),
+        };
+        parsed.create_processor(Endianness::Little, Tolerance::default())?;
+
+        return Ok(parsed);
+    }
+
+    /// Maximum entropy of matches, applies to every rule
+    #[must_use]
+    pub fn max_entropy(&self) -> Option<f64> {
+        return self.max_entropy;
+    }
+
+    /// Creates a processor of all the rules, a processor scans a single file.
+    /// Numbers are read in `endianness` unless the rule has its own.
+    ///
+    /// # Errors
+    /// Returns an error if a rule is invalid, rules are validated by [`Rules::parse`]
+    pub fn create_processor(
+        &self,
+        endianness: Endianness,
+        tolerance: Tolerance,
+    ) -> Result<RuleProcessor, RuleError> {
+        let mut processor = RuleProcessor::new(self.magic.clone());
+
+        for rule in &self.entries {
+            rule.add_to(&mut processor, endianness, tolerance)?;
+        }
+
+        return Ok(processor);
+    }
+}
+
+impl Rule {
+    fn invalid(&self, message: impl Display) -> RuleError {
+        return RuleError::InvalidRule(self.name.clone(), message.to_string());
+    }
+
+    /// Names of the options that are set, as written in the rules file
+    fn set_options(&self) -> Vec<&'static str> {
+        let options = &self.options;
+
+        return [
+            ("min", options.min.is_some()),
+            ("max", options.max.is_some()),
+            ("literal", options.literal.is_some()),
+            ("expr", options.expr.is_some()),
+            ("exclude-zero", options.exclude_zero),
+            ("as-string", options.as_string),
+            ("endian", options.endian.is_some()),
+            ("regex", options.regex.is_some()),
+            ("ignore-case", options.ignore_case),
+            ("cidr", !options.cidr.is_empty()),
+        ]
+        .into_iter()
+        .filter(|(_, set)| *set)
+        .map(|(option, _)| option)
+        .collect();
+    }
+
+    fn check_options(&self, allowed: &[&str]) -> Result<(), RuleError> {
+        if let Some(option) = self
+            .set_options()
+            .into_iter()
+            .find(|option| !allowed.contains(option))
+        {
+            return Err(self.invalid(format!(
+                "'{option}' is not supported by type '{}'",
+                self.type_name
+            )));
+        }
+
+        return Ok(());
+    }
+
+    fn parse<T>(&self, option: &str, value: &Value) -> Result<T, RuleError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let text = match value {
+            Value::Number(number) => number.to_string(),
+            Value::String(text) => text.clone(),
+            _ => return Err(self.invalid(format!("'{option}' must be a number or a string"))),
+        };
+
+        return T::from_str(&text)
+            .map_err(|err| self.invalid(format!("failed parsing '{option}' {text}: {err}")));
+    }
+
+    fn parse_option<T>(&self, option: &str, value: Option<&Value>) -> Result<Option<T>, RuleError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        return value.map(|value| self.parse(option, value)).transpose();
+    }
+
+    /// Values of `literal`, which is a single value or an array
+    fn literals(&self) -> Vec<&Value> {
+        return match &self.options.literal {
+            None => vec![],
+            Some(Value::Array(values)) => values.iter().collect(),
+            Some(value) => vec![value],
+        };
+    }
+
+    /// The `literal` of types that only take a single string
+    fn literal_text(&self) -> Result<Option<&str>, RuleError> {
+        return match &self.options.literal {
+            None => Ok(None),
+            Some(Value::String(text)) if !text.is_empty() => Ok(Some(text)),
+            Some(_) => Err(self.invalid("'literal' must be a non empty string")),
+        };
+    }
+
+    fn endianness(&self, default: Endianness) -> Result<Endianness, RuleError> {
+        let Some(endian) = &self.options.endian else {
+            return Ok(default);
+        };
+
+        return Endianness::from_str(endian, true).map_err(|_| {
+            self.invalid(format!("unknown endian '{endian}', expected little or big"))
+        });
+    }
+
+    fn config<T>(
+        &self,
+        minimum: Option<T>,
+        maximum: Option<T>,
+        tolerance: Tolerance,
+    ) -> Result<Configuration<T>, RuleError>
+    where
+        T: Compare + 'static,
+        T::Err: Display,
+    {
+        let literals = self
+            .literals()
+            .into_iter()
+            .map(|literal| self.parse("literal", literal))
+            .collect::<Result<Vec<T>, RuleError>>()?;
+        let expression = self
+            .options
+            .expr
+            .as_ref()
+            .map(|expression| Expression::parse(expression))
+            .transpose()
+            .map_err(|err| self.invalid(format!("failed parsing 'expr': {err}")))?;
+
+        if !literals.is_empty() && (minimum.is_some() || maximum.is_some()) {
+            return Err(self.invalid("'literal' can't be used with 'min' or 'max'"));
+        }
+
+        return Ok(Configuration {
+            minimum,
+            maximum,
+            literals,
+            exclude_zero: self.options.exclude_zero,
+            exclude_literal: None,
+            entropy: None,
+            expression,
+            tolerance,
+            date_minimum: None,
+            date_maximum: None,
+        });
+    }
+
+    /// The rule's filter, `exclude-zero` alone is not enough like on the command line
+    fn filter<T: Compare>(&self, config: &Configuration<T>) -> Result<FilterTree<T>, RuleError> {
+        let has_values = config.minimum.is_some()
+            || config.maximum.is_some()
+            || !config.literals.is_empty()
+            || config.expression.is_some();
+
+        return config
+            .create_filter()
+            .filter(|_| has_values)
+            .ok_or_else(|| self.invalid("needs 'min', 'max', 'literal' or 'expr'"));
+    }
+
+    fn add_to(
+        &self,
+        processor: &mut RuleProcessor,
+        endianness: Endianness,
+        tolerance: Tolerance,
+    ) -> Result<(), RuleError> {
+        match self.type_name.as_str() {
+            "string" => return self.add_string(processor),
+            "bytes" => return self.add_bytes(processor),
+            _ => {}
+        }
+
+        let data_type = match self.type_name.as_str() {
+            "double" => DataType::F64,
+            "float" => DataType::F32,
+            "integer" => DataType::I32,
+            type_name => DataType::from_str(type_name, true)
+                .map_err(|_| RuleError::UnknownType(self.name.clone(), type_name.to_string()))?,
+        };
+
+        if data_type.is_ip() {
+            self.check_options(&[
+                "min",
+                "max",
+                "literal",
+                "expr",
+                "as-string",
+                "endian",
+                "cidr",
+            ])?;
+        } else {
+            self.check_options(&[
+                "min",
+                "max",
+                "literal",
+                "expr",
+                "exclude-zero",
+                "as-string",
+                "endian",
+            ])?;
+        }
+
+        if self.options.as_string && !data_type.is_number() && data_type != DataType::Ipv4 {
+            return Err(self.invalid("'as-string' requires a numeric type or ipv4"));
+        }
+
+        let endianness = self.endianness(endianness)?;
+        return match data_type {
+            DataType::I8 => self.add_number::<i8>(processor, endianness, tolerance),
+            DataType::I16 => self.add_number::<i16>(processor, endianness, tolerance),
+            DataType::I32 => self.add_number::<i32>(processor, endianness, tolerance),
+            DataType::I64 => self.add_number::<i64>(processor, endianness, tolerance),
+            DataType::I128 => self.add_number::<i128>(processor, endianness, tolerance),
+            DataType::U8 => self.add_number::<u8>(processor, endianness, tolerance),
+            DataType::U16 => self.add_number::<u16>(processor, endianness, tolerance),
+            DataType::U32 => self.add_number::<u32>(processor, endianness, tolerance),
+            DataType::U64 => self.add_number::<u64>(processor, endianness, tolerance),
+            DataType::U128 => self.add_number::<u128>(processor, endianness, tolerance),
+            DataType::F32 => self.add_number::<f32>(processor, endianness, tolerance),
+            DataType::F64 => self.add_number::<f64>(processor, endianness, tolerance),
+            DataType::Unix32 => {
+                self.add_number::<Timestamp<Unix32>>(processor, endianness, tolerance)
+            }
+            DataType::Unix64 => {
+                self.add_number::<Timestamp<Unix64>>(processor, endianness, tolerance)
+            }
+            DataType::Unix64Ms => {
+                self.add_number::<Timestamp<Unix64Ms>>(processor, endianness, tolerance)
+            }
+            DataType::Unix64Us => {
+                self.add_number::<Timestamp<Unix64Us>>(processor, endianness, tolerance)
+            }
+            DataType::Unix64Ns => {
+                self.add_number::<Timestamp<Unix64Ns>>(processor, endianness, tolerance)
+            }
+            DataType::Filetime => {
+                self.add_number::<Timestamp<Filetime>>(processor, endianness, tolerance)
+            }
+            DataType::Systemtime => {
+                self.add_number::<Timestamp<SystemTime>>(processor, endianness, tolerance)
+            }
+            DataType::OleDate => {
+                self.add_number::<Timestamp<OleDate>>(processor, endianness, tolerance)
+            }
+            DataType::DotnetTicks => {
+                self.add_number::<Timestamp<DotnetTicks>>(processor, endianness, tolerance)
+            }
+            DataType::Cocoa => {
+                self.add_number::<Timestamp<Cocoa>>(processor, endianness, tolerance)
+            }
+            DataType::HfsPlus => {
+                self.add_number::<Timestamp<HfsPlus>>(processor, endianness, tolerance)
+            }
+            DataType::Gps => self.add_number::<Timestamp<Gps>>(processor, endianness, tolerance),
+            DataType::Dos => self.add_number::<Timestamp<Dos>>(processor, endianness, tolerance),
+            DataType::Guid => self.add_guid(processor, endianness, tolerance),
+            DataType::Ipv4 => self.add_ip::<Ipv4>(processor, endianness, tolerance),
+            DataType::Ipv6 => self.add_ip::<Ipv6>(processor, endianness, tolerance),
+        };
+    }
+
+    fn add_number<T>(
+        &self,
+        processor: &mut RuleProcessor,
+        endianness: Endianness,
+        tolerance: Tolerance,
+    ) -> Result<(), RuleError>
+    where
+        T: Compare + 'static,
+        T::Err: Display,
+    {
+        let config = self.config::<T>(
+            self.parse_option("min", self.options.min.as_ref())?,
+            self.parse_option("max", self.options.max.as_ref())?,
+            tolerance,
+        )?;
+        let filter = self.filter(&config)?;
+
+        if self.options.as_string {
+            processor.add(
+                &self.name,
+                TextNumberProcessor::<T>::new(),
+                TextNumberFilter::new(filter),
+                None,
+            );
+        } else {
+            processor.add(
+                &self.name,
+                NativeProcessor::<T>::new(endianness),
+                filter,
+                Some(endianness),
+            );
+        }
+
+        return Ok(());
+    }
+
+    /// Literals are searched in both layouts, like `--data-type guid`
+    fn add_guid(
+        &self,
+        processor: &mut RuleProcessor,
+        endianness: Endianness,
+        tolerance: Tolerance,
+    ) -> Result<(), RuleError> {
+        let config = self.config::<Guid>(
+            self.parse_option("min", self.options.min.as_ref())?,
+            self.parse_option("max", self.options.max.as_ref())?,
+            tolerance,
+        )?;
+        let filter = self.filter(&config)?;
+
+        processor.add(
+            &self.name,
+            GuidProcessor::new(&config.literals, false, endianness),
+            filter,
+            Some(endianness),
+        );
+
+        return Ok(());
+    }
+
+    /// `min` and `max` also take ranges and `cidr` ranges are matched with an expression,
+    /// like `--data-type ipv4` and `--data-type ipv6`
+    fn add_ip<A>(
+        &self,
+        processor: &mut RuleProcessor,
+        endianness: Endianness,
+        tolerance: Tolerance,
+    ) -> Result<(), RuleError>
+    where
+        A: IpAddress + Compare + 'static,
+        <A as FromStr>::Err: Display,
+    {
+        let minimum = self.parse_option::<IpRange<A>>("min", self.options.min.as_ref())?;
+        let maximum = self.parse_option::<IpRange<A>>("max", self.options.max.as_ref())?;
+        let mut config = self.config(
+            minimum.map(|range| range.first()),
+            maximum.map(|range| range.last()),
+            tolerance,
+        )?;
+
+        let ranges = self
+            .options
+            .cidr
+            .iter()
+            .map(|cidr| {
+                let range: IpRange<A> = self.parse("cidr", &Value::String(cidr.clone()))?;
+                return Ok(Expression::And(vec![
+                    Expression::Compare(Operator::GreaterEqual, range.first()),
+                    Expression::Compare(Operator::LessEqual, range.last()),
+                ]));
+            })
+            .collect::<Result<Vec<_>, RuleError>>()?;
+
+        if !ranges.is_empty() {
+            let ranges = Expression::Or(ranges);
+            config.expression = Some(match config.expression {
+                Some(expression) => Expression::And(vec![expression, ranges]),
+                None => ranges,
+            });
+        }
+
+        let filter = self.filter(&config)?;
+        if self.options.as_string {
+            processor.add(
+                &self.name,
+                TextNumberProcessor::<A>::dotted_quads(),
+                TextNumberFilter::new(filter),
+                None,
+            );
+        } else {
+            processor.add(
+                &self.name,
+                NativeProcessor::<A>::new(endianness),
+                filter,
+                Some(endianness),
+            );
+        }
+
+        return Ok(());
+    }
+
+    /// A `literal` in every encoding like `--string`, or a `regex` in the raw bytes
+    fn add_string(&self, processor: &mut RuleProcessor) -> Result<(), RuleError> {
+        self.check_options(&["literal", "regex", "ignore-case"])?;
+
+        let literal = self.literal_text()?;
+        match (literal, &self.options.regex) {
+            (Some(literal), None) => {
+                processor.add(
+                    &self.name,
+                    StringProcessor::new(literal, self.options.ignore_case),
+                    IncludeAll,
+                    None,
+                );
+            }
+            (None, Some(pattern)) if !self.options.ignore_case => {
+                let regex = RegexProcessor::new(pattern)
+                    .map_err(|err| self.invalid(format!("failed parsing 'regex': {err}")))?;
+                processor.add(&self.name, regex, IncludeAll, None);
+            }
+            (None, Some(_)) => return Err(self.invalid("'ignore-case' requires 'literal'")),
+            _ => return Err(self.invalid("needs either 'literal' or 'regex'")),
+        }
+
+        return Ok(());
+    }
+
+    /// A hex pattern `literal` like `--hex`, a leading 0x is ignored
+    fn add_bytes(&self, processor: &mut RuleProcessor) -> Result<(), RuleError> {
+        self.check_options(&["literal"])?;
+
+        let literal = self
+            .literal_text()?
+            .ok_or_else(|| self.invalid("needs a hex pattern 'literal'"))?
+            .trim();
+        let pattern = HexPattern::compile(literal.strip_prefix("0x").unwrap_or(literal))
+            .map_err(|err| self.invalid(format!("failed parsing 'literal': {err}")))?;
+
+        processor.add(&self.name, HexProcessor::new(pattern), IncludeAll, None);
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::scan_value::ScanValue;
+    use crate::workers::processors::Processor;
+
+    fn rules(types: &str) -> Result<Rules, RuleError> {
+        return Rules::parse(&format!(
+            r#"{{ "filters": {{ "file": {{ "types": {types} }} }} }}"#
+        ));
+    }
+
+    fn invalid(types: &str) -> String {
+        return rules(types).unwrap_err().to_string();
+    }
+
+    /// Consumes every offset like the scanner, returns the offsets and output of matches
+    fn scan(rules: &Rules, data: &[u8]) -> Vec<(usize, String)> {
+        let mut processor = rules
+            .create_processor(Endianness::Little, Tolerance::default())
+            .unwrap();
+        let chunk_size = processor.chunk_size().unwrap();
+
+        return (0..data.len())
+            .filter_map(|index| {
+                let chunk = &data[index..data.len().min(index + chunk_size)];
+                let result = processor.consume(chunk)?;
+                let rule = result.rule().unwrap();
+                Some((index, format!("{rule} {}: {result}", result.type_name())))
+            })
+            .collect();
+    }
+
+    #[test]
+    fn parse_readme_example() {
+        let rules = Rules::parse(
+            r#"{
+              "filters": {
+                "file": {
+                  "magic": "0xABCDEF",
+                  "types": [
+                    { "double": { "min": 80.3432, "max": 82.221112, "exclude-zero": true } },
+                    { "string": { "literal": "AMAzING" } },
+                    { "string": { "regex": "12334+" } },
+                    { "bytes": { "literal": "0xDEADBEEF" } },
+                    { "integer": { "min": -10, "max": 12, "as_string": true } }
+                  ],
+                  "entropy": { "max": 6 }
+                }
+              }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(rules.max_entropy(), Some(6.0));
+        assert_eq!(
+            rules
+                .entries
+                .iter()
+                .map(|rule| rule.name.as_str())
+                .collect::<Vec<_>>(),
+            ["double#1", "string#2", "string#3", "bytes#4", "integer#5"]
+        );
+    }
+
+    #[test]
+    fn scan_tags_rules() {
+        let rules = rules(
+            r#"[
+              { "u16": { "name": "port", "literal": [443, 8443], "endian": "big" } },
+              { "string": { "literal": "ok", "ignore-case": true } },
+              { "bytes": { "literal": "DEAD[00-0F]" } },
+              { "integer": { "min": -10, "max": 12, "as-string": true } }
+            ]"#,
+        )
+        .unwrap();
+
+        let mut data = vec![0x01, 0xBB];
+        data.extend(b" OK -7 13 ");
+        data.extend([0xDE, 0xAD, 0x0E]);
+
+        assert_eq!(
+            scan(&rules, &data),
+            vec![
+                (0, "port u16: 443".into()),
+                (3, "string#2 utf8: OK".into()),
+                (6, "integer#4 i32: -7 (utf8)".into()),
+                (12, "bytes#3 hex: ...".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_ip_ranges() {
+        let rules = rules(
+            r#"[
+              { "ipv4": { "cidr": ["10.0.0.0/8"], "endian": "big" } },
+              { "ipv4": { "name": "text", "min": "192.168.1.0/24", "as-string": true } }
+            ]"#,
+        )
+        .unwrap();
+
+        let mut data = vec![10, 1, 2, 3];
+        data.extend(b" 192.168.1.7 ");
+
+        assert_eq!(
+            scan(&rules, &data),
+            vec![
+                (0, "ipv4#1 ipv4: 10.1.2.3".into()),
+                (5, "text ipv4: 192.168.1.7 (utf8)".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_requires_magic() {
+        let rules = Rules::parse(
+            r#"{ "filters": { "file": { "magic": "0x7F454C46",
+                 "types": [{ "u8": { "literal": 69 } }] } } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(scan(&rules, b"\x7FELF"), vec![(1, "u8#1 u8: 69".into())]);
+        assert_eq!(scan(&rules, b"\x00ELF"), vec![]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(invalid("[]").contains("no types"));
+        assert!(invalid(r#"[{ "double": { "min": 1, "maximum": 2 } }]"#)
+            .starts_with("Invalid rules file: unknown field `maximum`"));
+        assert_eq!(
+            invalid(r#"[{ "double": { "min": 1 }, "float": { "min": 1 } }]"#),
+            "Type 1 must have a single type name, e.g. { \"double\": { \"min\": 1 } }"
+        );
+        assert_eq!(
+            invalid(r#"[{ "u33": { "min": 1 } }]"#),
+            "Unknown type 'u33' of rule 'u33#1'"
+        );
+        assert_eq!(
+            invalid(
+                r#"[{ "u8": { "name": "a", "min": 1 } }, { "u8": { "name": "a", "min": 1 } }]"#
+            ),
+            "Duplicate rule 'a'"
+        );
+        assert_eq!(
+            invalid(r#"[{ "u8": { "min": 300 } }]"#),
+            "Invalid rule 'u8#1': failed parsing 'min' 300: number too large to fit in target type"
+        );
+        assert_eq!(
+            invalid(r#"[{ "double": { "exclude-zero": true } }]"#),
+            "Invalid rule 'double#1': needs 'min', 'max', 'literal' or 'expr'"
+        );
+        assert_eq!(
+            invalid(r#"[{ "string": { "literal": "a", "min": 1 } }]"#),
+            "Invalid rule 'string#1': 'min' is not supported by type 'string'"
+        );
+        assert_eq!(
+            invalid(r#"[{ "unix32": { "min": 1, "as-string": true } }]"#),
+            "Invalid rule 'unix32#1': 'as-string' requires a numeric type or ipv4"
+        );
+        assert_eq!(
+            invalid(r#"[{ "bytes": { "literal": "0xABC" } }]"#)
+                .split(':')
+                .next(),
+            Some("Invalid rule 'bytes#1'")
+        );
+
+        let magic = Rules::parse(
+            r#"{ "filters": { "file": { "magic": "0xABDEF", "types": [{ "u8": { "min": 1 } }] } } }"#,
+        );
+        assert!(matches!(magic, Err(RuleError::InvalidMagic(magic, _)) if magic == "0xABDEF"));
+    }
+}
@@ -1,47 +1,69 @@
-use std::cell::RefCell;
 use std::error::Error;
-use std::fmt::Display;
 use std::path::PathBuf;
-use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
-use crate::common::SourceFile;
+use crate::common::{Endianness, SourceData, SourceFile, DEFAULT_BUFFER_SIZE};
 use crate::filebuffer::FileBuffer;
 use crate::filters::filter::Filter;
-use crate::printers::output::{DataContext, Output};
+use crate::printers::output::{ContextSize, DataContext, Output};
 use crate::printers::printer::Printer;
+use crate::types::scan_value::ScanValue;
+use crate::workers::entropy_processor::EntropyConsumer;
+use crate::workers::prefilter::Prefilter;
 use crate::workers::processors::Processor;
+use crate::workers::search::{ProcessorSearch, Search};
 
-type EntropyProcessorRef<T> = Option<Rc<RefCell<dyn Processor<T>>>>;
+type EntropyProcessorRef<T> = Option<Arc<Mutex<dyn EntropyConsumer<T>>>>;
+
+/// Scanned data, streams are read through a buffer while mapped files
+/// are scanned directly.
+enum Source<'a> {
+    Buffered(FileBuffer<'a>),
+    Mapped(memmap2::Mmap),
+}
 
 /// Scans a file for data types that match a filter
 /// T is the type to be scanned
-pub struct Scanner<'a, T, P>
+///
+/// Values are found by a [`Search`], usually a processor passed every offset.
+/// The filter (F) and search (S) are generic so they are statically dispatched
+/// in the per offset loop, boxed trait objects can be used as well.
+pub struct Scanner<'a, T, P, F, S>
 where
-    T: Display + Copy,
+    T: ScanValue,
     P: Printer<T>,
+    F: Filter<T>,
+    S: Search<T>,
 {
     file_path: PathBuf,
-    filebuffer: FileBuffer<'a>,
+    source: Option<Source<'a>>,
     printer: P,
 
-    // TODO(danilan): Move to static dispatch
-    filter: Box<dyn Filter<T>>,
-    processor: Box<dyn Processor<T>>,
+    filter: F,
+    search: S,
     entropy_processor: EntropyProcessorRef<T>,
+
+    /// Offset in the original file of the first byte read
+    offset: usize,
+    /// Leading bytes that only rebuild the entropy state and are not scanned
+    warmup: usize,
+    /// Bytes around every match that are printed with it
+    context: ContextSize,
+    /// Endianness the processor reads values in, printed with values that don't have their own
+    endianness: Option<Endianness>,
+    /// Amount of matches printed so far
+    matches: usize,
 }
 
-impl<'a, T, P> Scanner<'a, T, P>
+impl<'a, T, P, F, R> Scanner<'a, T, P, F, ProcessorSearch<R>>
 where
-    T: Display + Copy,
+    T: ScanValue,
     P: Printer<T>,
+    F: Filter<T>,
+    R: Processor<T>,
 {
     #[must_use]
-    pub fn new(
-        file: SourceFile<'a>,
-        processor: Box<dyn Processor<T>>,
-        filter: Box<dyn Filter<T>>,
-        printer: P,
-    ) -> Self {
+    pub fn new(file: SourceFile<'a>, processor: R, filter: F, printer: P) -> Self {
         return Self::with_entropy_processor(file, processor, filter, printer, None);
     }
 
@@ -49,84 +71,267 @@ where
     #[must_use]
     pub fn with_entropy_processor(
         file: SourceFile<'a>,
-        processor: Box<dyn Processor<T>>,
-        filter: Box<dyn Filter<T>>,
+        processor: R,
+        filter: F,
+        printer: P,
+        entropy_processor: EntropyProcessorRef<T>,
+    ) -> Self {
+        let search = ProcessorSearch::new(processor);
+        return Self::with_search(file, search, filter, printer, entropy_processor);
+    }
+
+    /// Skips offsets rejected by a [`Prefilter`] without converting them.
+    /// The prefilter must not reject values the filter would include.
+    #[must_use]
+    pub fn with_prefilter(mut self, prefilter: Prefilter) -> Self {
+        self.search = self.search.with_prefilter(prefilter);
+        return self;
+    }
+}
+
+impl<'a, T, P, F, S> Scanner<'a, T, P, F, S>
+where
+    T: ScanValue,
+    P: Printer<T>,
+    F: Filter<T>,
+    S: Search<T>,
+{
+    /// Scans values found by `search` instead of passing every offset to a processor
+    #[must_use]
+    pub fn with_search(
+        file: SourceFile<'a>,
+        search: S,
+        filter: F,
         printer: P,
         entropy_processor: EntropyProcessorRef<T>,
     ) -> Self {
+        let file_path = file.path();
+        let source = match file.data() {
+            SourceData::Reader(reader) => Source::Buffered(FileBuffer::new(reader)),
+            SourceData::Mapped(map) => Source::Mapped(map),
+        };
+
         return Self {
-            file_path: file.path(),
-            filebuffer: FileBuffer::new(file.file()),
+            file_path,
+            source: Some(source),
             printer,
             filter,
-            processor,
+            search,
             entropy_processor,
+            offset: 0,
+            warmup: 0,
+            context: ContextSize::default(),
+            endianness: None,
+            matches: 0,
         };
     }
 
+    /// Scans a part of a larger file, used to split a file between threads.
+    ///
+    /// `offset` is the position in the original file of the first byte read from
+    /// the source. The first `warmup` bytes are not matched, they're only fed
+    /// to the entropy processor so its state matches a scan of the whole file.
+    #[must_use]
+    pub fn with_offset(mut self, offset: usize, warmup: usize) -> Self {
+        self.offset = offset;
+        self.warmup = warmup;
+        return self;
+    }
+
+    /// Captures bytes around every match, context is cut at the bounds of the scanned data.
+    #[must_use]
+    pub fn with_context(mut self, context: ContextSize) -> Self {
+        self.context = context;
+        return self;
+    }
+
+    /// Prints values with the endianness the processor reads them in
+    #[must_use]
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = Some(endianness);
+        return self;
+    }
+
+    /// Scans the whole file, returns the amount of matches.
+    ///
+    /// # Errors
+    /// Returns an error if reading the file or printing a result fails.
     pub fn scan(mut self) -> Result<usize, Box<dyn Error>> {
-        return self.scan_file();
+        self.scan_file()?;
+        return Ok(self.matches);
     }
 
-    /// Wrapper method that calls printer.end(), also does not
+    /// Wrapper method that calls `printer.end()`, also does not
     /// consume self so can be used for testing.
     fn scan_file(&mut self) -> Result<usize, Box<dyn Error>> {
-        let position = self.scan_buffer()?;
+        // End printing even on failure, so matches found until the error are flushed
+        let position = self.scan_source();
         self.printer.end()?;
-        Ok(position)
+        position
     }
 
-    fn scan_buffer(&mut self) -> Result<usize, Box<dyn Error>> {
-        let type_name = std::any::type_name::<T>();
-        let chunk_size = self.processor.chunk_size().unwrap();
-        loop {
-            let cur_pos = self.filebuffer.position();
-            let data = self.filebuffer.peek(chunk_size)?;
+    fn scan_source(&mut self) -> Result<usize, Box<dyn Error>> {
+        let source = self.source.take().ok_or("file was already scanned")?;
 
-            let result = self.processor.consume(data);
+        let position = match source {
+            Source::Buffered(buffer) => self.scan_buffered(buffer)?,
+            Source::Mapped(map) => self.scan_mapped(&map)?,
+        };
 
-            // TODO(danilan): Need to improve here, cause it doesn't make sense to scan byte by byte
-            if let Some(entropy_processor) = &self.entropy_processor {
-                entropy_processor.borrow_mut().consume(&data[..1]);
-            }
+        Ok(self.offset + position)
+    }
+
+    fn scan_mapped(&mut self, data: &[u8]) -> Result<usize, Box<dyn Error>> {
+        let warmup = self.warmup.min(data.len());
+        self.feed_entropy(&data[..warmup]);
+
+        let scanned = self.scan_slice(data, warmup, 0, true)?;
+        Ok(warmup + scanned)
+    }
 
-            if result.is_none() {
+    fn scan_buffered(&mut self, mut buffer: FileBuffer) -> Result<usize, Box<dyn Error>> {
+        let warmup = buffer.pop(self.warmup)?;
+        self.feed_entropy(&warmup);
+
+        let read_size =
+            DEFAULT_BUFFER_SIZE + self.search.max_size() + self.context.before + self.context.after;
+        // Scanned bytes kept at the start of the data for the context of the next matches
+        let mut history = 0;
+        loop {
+            let position = buffer.position();
+            let data = buffer.peek(read_size)?;
+            // Only a read at the end of the file is short
+            let at_end = data.len() < read_size;
+
+            let scanned = self.scan_slice(data, history, position, at_end)?;
+            if scanned == 0 {
                 break; // EOF
             }
 
-            if self.filter.include_unwrap(result) {
+            // Keep the tail that wasn't scanned yet, values there cross the buffer's end
+            let kept = (history + scanned).min(self.context.before);
+            buffer.pop_drop(history + scanned - kept)?;
+            history = kept;
+        }
+
+        Ok(buffer.position() + history)
+    }
+
+    /// Scans all values that fit in `data` from `start`, where `position` is the position
+    /// of `data` in the source. Returns the amount of scanned positions.
+    ///
+    /// At the end of the data, searches that match shorter values are also
+    /// passed the last offsets where only their smallest match fits.
+    /// Before the end, values are only scanned where their context after them fits as well.
+    fn scan_slice(
+        &mut self,
+        data: &[u8],
+        start: usize,
+        position: usize,
+        at_end: bool,
+    ) -> Result<usize, Box<dyn Error>> {
+        let min_size = if at_end {
+            self.search.min_size()
+        } else {
+            self.search.max_size() + self.context.after
+        };
+
+        if data.len() < start + min_size {
+            return Ok(0);
+        }
+
+        let positions = data.len() - min_size + 1;
+        let mut next = start;
+        while let Some((range, result)) = self.search.find(data, next, positions, position) {
+            // Entropy of a match includes all the bytes up to its first byte
+            self.feed_entropy(&data[next..=range.start]);
+            next = range.start + 1;
+
+            if self.filter.include(result.clone()) {
+                let value_type = result.type_name().to_string();
+                let endianness = result.endianness().or(self.endianness);
+                let rule = result.rule().map(str::to_string);
+
                 let output = Output::new(
                     &self.file_path,
-                    result.unwrap(),
-                    type_name.into(),
-                    DataContext::new(data.to_vec(), cur_pos),
-                );
+                    result,
+                    value_type,
+                    DataContext::with_context(data, range, self.offset + position, self.context),
+                )
+                .with_endianness(endianness)
+                .with_entropy(self.entropy())
+                .with_rule(rule.as_deref());
+                self.matches += 1;
                 self.printer.feed(output)?;
             }
+        }
+
+        self.feed_entropy(&data[next..positions]);
+
+        Ok(positions - start)
+    }
+
+    /// Entropy of the bytes fed so far, None without an entropy processor
+    fn entropy(&self) -> Option<f64> {
+        return self
+            .entropy_processor
+            .as_ref()?
+            .lock()
+            .expect("entropy processor lock should not be poisoned")
+            .entropy();
+    }
 
-            // move carret to the next byte
-            self.filebuffer.pop_drop(1)?;
+    fn feed_entropy(&self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
         }
 
-        Ok(self.filebuffer.position())
+        if let Some(entropy_processor) = &self.entropy_processor {
+            entropy_processor
+                .lock()
+                .expect("entropy processor lock should not be poisoned")
+                .consume(bytes);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{error::Error, fmt::Display, path::Path, vec};
+    use std::{
+        error::Error,
+        fmt::Display,
+        io::Write,
+        path::Path,
+        sync::{Arc, Mutex},
+        vec,
+    };
 
     use assertor::{assert_that, BooleanAssertion, VecAssertion};
 
+    use regex::bytes::RegexBuilder;
+
     use super::Scanner;
     use crate::{
-        common::{Endianness, SourceFile},
-        filters::filter::Filter,
+        common::{Endianness, SourceFile, DEFAULT_BUFFER_SIZE},
+        filters::{
+            configuration::{Configuration, EntropyConfig},
+            filter::Filter,
+        },
         printers::{
-            output::{DataContext, Output},
+            output::{ContextSize, DataContext, Output},
             printer::Printer,
         },
-        workers::native_processor::NativeProcessor,
+        types::{
+            regex_match::RegexMatch,
+            string_match::{StringEncoding, StringMatch},
+        },
+        workers::{
+            entropy_processor::{EntropyConsumer, EntropyProcessor, EntropyProducer},
+            native_processor::NativeProcessor,
+            processors::Processor,
+            regex_search::RegexSearch,
+            string_processor::StringProcessor,
+        },
     };
 
     struct TrueFilter;
@@ -161,18 +366,196 @@ mod tests {
     where
         T: Display + Clone,
     {
-        fn feed(&mut self, output: Output<T>) -> Result<(), Box<(dyn Error)>> {
+        fn feed(&mut self, output: Output<T>) -> Result<(), Box<dyn Error>> {
             self.outputs.push(output);
             Ok(())
         }
 
-        fn end(&mut self) -> Result<(), Box<(dyn Error)>> {
+        fn end(&mut self) -> Result<(), Box<dyn Error>> {
             self.finished = true;
             Ok(())
         }
     }
 
-    // TODO(danilan): Test entropy_processor
+    /// Scans `data[start - warmup..end]` with an entropy filter
+    fn scan_entropy_chunk(data: &[u8], start: usize, end: usize, warmup: usize) -> Vec<Output<u8>> {
+        let entropy_processor = Arc::new(Mutex::new(EntropyProcessor::<u8>::new(4096)));
+        let config = Configuration::<u8> {
+            entropy: Some(EntropyConfig {
+                max_entropy: 5.0,
+                entropy_producer: entropy_processor.clone(),
+            }),
+            ..Default::default()
+        };
+
+        let file = SourceFile::new("ok".into(), &data[start - warmup..end]);
+        let mut scanner = Scanner::with_entropy_processor(
+            file,
+            Box::new(NativeProcessor::<u8>::new(Endianness::Little)),
+            config.create_filter().unwrap(),
+            FakePrinter::<u8>::new(),
+            Some(entropy_processor as Arc<Mutex<dyn EntropyConsumer<u8>>>),
+        )
+        .with_offset(start - warmup, warmup);
+
+        scanner.scan_file().expect("scan to complete successfuly");
+        return scanner.printer.outputs;
+    }
+
+    struct EqualFilter(u32);
+
+    impl Filter<u32> for EqualFilter {
+        fn include(&self, result: u32) -> bool {
+            return result == self.0;
+        }
+    }
+
+    fn scan_u32(
+        file: SourceFile,
+        filter: impl Filter<u32>,
+        context: ContextSize,
+    ) -> Vec<Output<u32>> {
+        let mut scanner = Scanner::new(
+            file,
+            Box::new(NativeProcessor::<u32>::new(Endianness::Little)),
+            filter,
+            FakePrinter::<u32>::new(),
+        )
+        .with_context(context);
+
+        scanner.scan_file().expect("scan to complete successfuly");
+        return scanner.printer.outputs;
+    }
+
+    fn scan_regex(
+        file: SourceFile,
+        pattern: &str,
+        encoding: StringEncoding,
+        context: ContextSize,
+    ) -> Vec<Output<RegexMatch>> {
+        let regex = RegexBuilder::new(pattern).unicode(false).build().unwrap();
+        let mut scanner = Scanner::with_search(
+            file,
+            RegexSearch::new(regex, encoding),
+            TrueFilter,
+            FakePrinter::<RegexMatch>::new(),
+            None,
+        )
+        .with_context(context);
+
+        scanner.scan_file().expect("scan to complete successfuly");
+        return scanner.printer.outputs;
+    }
+
+    #[test]
+    fn scan_mapped_same_as_buffered() {
+        // Values cross the read buffer boundaries
+        let data: Vec<u8> = (0..10000u32).map(|i| (i % 251) as u8).collect();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&data).unwrap();
+
+        let mapped_file = SourceFile::open(file.path().to_owned()).unwrap();
+        assert!(mapped_file.is_mapped());
+        let buffered_file = SourceFile::new(file.path().to_owned(), data.as_slice());
+
+        let mapped = scan_u32(mapped_file, TrueFilter, ContextSize::default());
+        let buffered = scan_u32(buffered_file, TrueFilter, ContextSize::default());
+
+        assert_eq!(mapped.len(), data.len() - 3);
+        assert_that!(mapped).contains_exactly_in_order(buffered);
+    }
+
+    #[test]
+    fn scan_context_mapped_same_as_buffered() {
+        // Context before a match is longer than the read buffer
+        let data: Vec<u8> = (0..20000u32).map(|i| (i % 251) as u8).collect();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&data).unwrap();
+
+        let mapped_file = SourceFile::open(file.path().to_owned()).unwrap();
+        let buffered_file = SourceFile::new(file.path().to_owned(), data.as_slice());
+        let context = ContextSize {
+            before: 6000,
+            after: 300,
+        };
+
+        let value = u32::from_le_bytes([10, 11, 12, 13]);
+        let mapped = scan_u32(mapped_file, EqualFilter(value), context);
+        let buffered = scan_u32(buffered_file, EqualFilter(value), context);
+
+        assert_eq!(mapped.len(), 80);
+        assert_that!(mapped).contains_exactly_in_order(buffered);
+
+        let at = |offset: usize| DataContext::with_context(&data, offset..offset + 4, 0, context);
+        assert_eq!(
+            mapped.first().unwrap(),
+            &Output::new(file.path(), value, "u32".into(), at(10))
+        );
+        assert_eq!(
+            mapped[40],
+            Output::new(file.path(), value, "u32".into(), at(10 + 40 * 251))
+        );
+        assert_eq!(
+            mapped.last().unwrap(),
+            &Output::new(file.path(), value, "u32".into(), at(10 + 79 * 251))
+        );
+    }
+
+    #[test]
+    fn scan_returns_matches() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+        let scanner = Scanner::new(
+            SourceFile::new("ok".into(), data.as_slice()),
+            NativeProcessor::<u32>::new(Endianness::Little),
+            EqualFilter(u32::from_le_bytes([10, 11, 12, 13])),
+            FakePrinter::<u32>::new(),
+        );
+
+        assert_eq!(scanner.scan().expect("scan to complete successfuly"), 4);
+    }
+
+    #[test]
+    fn scan_with_endianness_and_entropy() {
+        let mut data = vec![0u8; 600];
+        data.extend(7u32.to_be_bytes());
+
+        let entropy_processor = Arc::new(Mutex::new(EntropyProcessor::<u32>::new(4096)));
+        let mut scanner = Scanner::with_entropy_processor(
+            SourceFile::new("ok".into(), data.as_slice()),
+            NativeProcessor::<u32>::new(Endianness::Big),
+            EqualFilter(7),
+            FakePrinter::<u32>::new(),
+            Some(entropy_processor as Arc<Mutex<dyn EntropyConsumer<u32>>>),
+        )
+        .with_endianness(Endianness::Big);
+        scanner.scan_file().expect("scan to complete successfuly");
+
+        let data_context = DataContext::new(7u32.to_be_bytes().to_vec(), 600);
+        assert_eq!(
+            scanner.printer.outputs,
+            vec![Output::new(Path::new("ok"), 7, "u32".into(), data_context)
+                .with_endianness(Some(Endianness::Big))
+                .with_entropy(Some(0.0))]
+        );
+    }
+
+    #[test]
+    fn scan_with_offset_rebuilds_entropy() {
+        // Low entropy at the start, gradually becomes noisier
+        let data: Vec<u8> = (0..12000usize)
+            .map(|i| u8::try_from((i * i / 7919) % (1 + i / 100) % 256).unwrap())
+            .collect();
+
+        let full_scan = scan_entropy_chunk(&data, 0, data.len(), 0);
+        assert!(!full_scan.is_empty());
+        assert!(full_scan.len() < data.len() - 512);
+
+        let mut chunked_scan = scan_entropy_chunk(&data, 0, 3000, 0);
+        chunked_scan.extend(scan_entropy_chunk(&data, 3000, 7000, 3000));
+        chunked_scan.extend(scan_entropy_chunk(&data, 7000, data.len(), 4096));
+
+        assert_that!(chunked_scan).contains_exactly_in_order(full_scan);
+    }
 
     #[test]
     fn scan_buffer_f64() {
@@ -213,6 +596,39 @@ mod tests {
         assert_that!(scanner.printer.finished).is_true();
     }
 
+    #[test]
+    fn scan_variable_size_values() {
+        // The UTF-8 match at the end is shorter than the processor's chunk size
+        let buf = b"A\x00B\x00xAB".to_vec();
+        let file = SourceFile::new("ok".into(), buf.as_slice());
+
+        let mut scanner = Scanner::new(
+            file,
+            StringProcessor::new("AB", false),
+            TrueFilter {},
+            FakePrinter::<StringMatch>::new(),
+        );
+
+        let bytes_scanned = scanner.scan_file().expect("scan to complete successfuly");
+        assert_eq!(bytes_scanned, 6);
+
+        let expected = vec![
+            Output::new(
+                Path::new("ok"),
+                StringMatch::new("AB".into(), StringEncoding::Utf16Le, 4),
+                "utf16le".into(),
+                DataContext::new(buf[..4].to_vec(), 0),
+            ),
+            Output::new(
+                Path::new("ok"),
+                StringMatch::new("AB".into(), StringEncoding::Utf8, 2),
+                "utf8".into(),
+                DataContext::new(buf[5..].to_vec(), 5),
+            ),
+        ];
+        assert_that!(scanner.printer.outputs).contains_exactly_in_order(expected);
+    }
+
     #[test]
     fn scan_buffer_big() {
         let buf = vec![1u8, 2u8, 3u8, 4u8, 5u8, 6u8];
@@ -252,4 +668,85 @@ mod tests {
         assert_that!(scanner.printer.outputs).contains_exactly_in_order(expected);
         assert_that!(scanner.printer.finished).is_true();
     }
+
+    #[test]
+    fn scan_regex_mapped_same_as_buffered() {
+        // Matches cross the read buffer boundaries and are at the end of the file
+        let mut data: Vec<u8> = (0..20000u32).map(|i| (i % 251) as u8).collect();
+        for offset in [4090, 8190, 12000, 19990] {
+            data[offset..offset + 10].copy_from_slice(b"key=value;");
+        }
+        let utf16 = StringEncoding::Utf16Be.encode("key=value;");
+        data[15001..15001 + utf16.len()].copy_from_slice(&utf16);
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&data).unwrap();
+
+        // Context before a match is longer than the read buffer
+        let context = ContextSize {
+            before: 5000,
+            after: 100,
+        };
+
+        for (encoding, count, context) in [
+            (StringEncoding::Utf8, 4, ContextSize::default()),
+            (StringEncoding::Utf16Be, 1, ContextSize::default()),
+            (StringEncoding::Utf8, 4, context),
+            (StringEncoding::Utf16Be, 1, context),
+        ] {
+            let mapped_file = SourceFile::open(file.path().to_owned()).unwrap();
+            assert!(mapped_file.is_mapped());
+            let buffered_file = SourceFile::new(file.path().to_owned(), data.as_slice());
+
+            let mapped = scan_regex(mapped_file, "key=[a-z]+;", encoding, context);
+            let buffered = scan_regex(buffered_file, "key=[a-z]+;", encoding, context);
+
+            assert_eq!(mapped.len(), count);
+            assert_that!(mapped).contains_exactly_in_order(buffered);
+        }
+    }
+
+    #[test]
+    fn scan_regex_drops_high_entropy() {
+        // Noisy data followed by low entropy data
+        let mut data: Vec<u8> = (0..8192u32)
+            .map(|i| i.wrapping_mul(2_654_435_761).to_le_bytes()[2])
+            .collect();
+        data.extend(std::iter::repeat(b'a').take(8192));
+        data[4000..4006].copy_from_slice(b"secret");
+        data[12000..12006].copy_from_slice(b"secret");
+
+        let entropy_processor = Arc::new(Mutex::new(EntropyProcessor::<RegexMatch>::new(
+            DEFAULT_BUFFER_SIZE,
+        )));
+        let filter = EntropyConfig {
+            max_entropy: 5.0,
+            entropy_producer: entropy_processor.clone(),
+        }
+        .create_value_filter();
+
+        let regex = RegexBuilder::new("secret").unicode(false).build().unwrap();
+        let mut scanner = Scanner::with_search(
+            SourceFile::new("ok".into(), data.as_slice()),
+            RegexSearch::new(regex, StringEncoding::Utf8),
+            filter,
+            FakePrinter::<RegexMatch>::new(),
+            Some(entropy_processor as Arc<Mutex<dyn EntropyConsumer<RegexMatch>>>),
+        );
+        scanner.scan_file().expect("scan to complete successfuly");
+
+        // Entropy of a match includes all the bytes up to its first byte
+        let mut expected = EntropyProcessor::<RegexMatch>::new(DEFAULT_BUFFER_SIZE);
+        expected.consume(&data[..=12000]);
+
+        let found = RegexMatch::from_bytes(b"secret");
+        let data_context = DataContext::new(b"secret".to_vec(), 12000);
+        assert_eq!(
+            scanner.printer.outputs,
+            vec![
+                Output::new(Path::new("ok"), found, "regex".into(), data_context)
+                    .with_entropy(expected.entropy())
+            ]
+        );
+    }
 }
@@ -0,0 +1,134 @@
+use super::bit_type::ByteOrdering;
+use super::datetime::{NANOS_PER_MICRO, NANOS_PER_SECOND};
+use super::timestamp::{nanos_to_ticks, ticks_to_nanos, Epoch};
+
+/// 2001-01-01
+const COCOA_EPOCH_NANOS: i128 = 978_307_200 * NANOS_PER_SECOND;
+/// 1904-01-01
+const HFS_PLUS_EPOCH_NANOS: i128 = -2_082_844_800 * NANOS_PER_SECOND;
+
+/// Cocoa dates are limited to 0001-01-01 up to 9999-12-31, anything else is noise
+const COCOA_MIN_SECONDS: f64 = -63_113_904_000.0;
+const COCOA_MAX_SECONDS: f64 = 252_423_993_600.0;
+const COCOA_MICROS_PER_SECOND: f64 = 1_000_000.0;
+
+/// Cocoa / Core Data `NSDate`, a double of seconds since 2001-01-01.
+///
+/// Times are rounded to microseconds.
+#[derive(Clone, Copy)]
+pub struct Cocoa;
+
+impl Epoch for Cocoa {
+    type Raw = f64;
+    const NAME: &'static str = "cocoa";
+    const FRACTION_DIGITS: usize = 6;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn to_nanos(raw: f64) -> Option<i128> {
+        if !(COCOA_MIN_SECONDS..COCOA_MAX_SECONDS).contains(&raw) {
+            return None;
+        }
+
+        let micros = (raw * COCOA_MICROS_PER_SECOND).round() as i128;
+        return Some(COCOA_EPOCH_NANOS + ticks_to_nanos(micros, NANOS_PER_MICRO));
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn from_nanos(nanos: i128) -> Option<f64> {
+        let micros = nanos_to_ticks(nanos - COCOA_EPOCH_NANOS, NANOS_PER_MICRO);
+        let raw = micros as f64 / COCOA_MICROS_PER_SECOND;
+
+        return (COCOA_MIN_SECONDS..COCOA_MAX_SECONDS)
+            .contains(&raw)
+            .then_some(raw);
+    }
+}
+
+/// HFS+ dates, 32-bit unsigned seconds since 1904-01-01.
+///
+/// Most HFS+ dates are UTC, but the volume creation date is local time.
+#[derive(Clone, Copy)]
+pub struct HfsPlus;
+
+impl Epoch for HfsPlus {
+    type Raw = u32;
+    const NAME: &'static str = "hfs-plus";
+    const FRACTION_DIGITS: usize = 0;
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Unsigned;
+
+    fn to_nanos(raw: u32) -> Option<i128> {
+        return Some(ticks_to_nanos(raw.into(), NANOS_PER_SECOND) + HFS_PLUS_EPOCH_NANOS);
+    }
+
+    fn from_nanos(nanos: i128) -> Option<u32> {
+        return nanos_to_ticks(nanos - HFS_PLUS_EPOCH_NANOS, NANOS_PER_SECOND)
+            .try_into()
+            .ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::datetime::parse_iso8601;
+    use crate::types::timestamp::Timestamp;
+
+    fn seconds(date: &str) -> f64 {
+        let nanos = parse_iso8601(date).unwrap() - COCOA_EPOCH_NANOS;
+        return f64::from(i32::try_from(nanos / NANOS_PER_SECOND / 86_400).unwrap()) * 86_400.0;
+    }
+
+    #[test]
+    fn epochs() {
+        assert_eq!(COCOA_EPOCH_NANOS, parse_iso8601("2001-01-01").unwrap());
+        assert_eq!(HFS_PLUS_EPOCH_NANOS, parse_iso8601("1904-01-01").unwrap());
+        assert_eq!(COCOA_MIN_SECONDS.to_bits(), seconds("0001-01-01").to_bits());
+        assert_eq!(
+            COCOA_MAX_SECONDS.to_bits(),
+            (seconds("9999-12-31") + 86_400.0).to_bits()
+        );
+    }
+
+    #[test]
+    fn cocoa() {
+        assert_eq!(
+            Timestamp::<Cocoa>::from_raw(0.0).to_string(),
+            "2001-01-01T00:00:00.000000Z"
+        );
+        assert_eq!(
+            Timestamp::<Cocoa>::from_raw(636_249_600.25).to_string(),
+            "2021-03-01T00:00:00.250000Z"
+        );
+        assert_eq!(
+            "2021-03-01T00:00:00.25Z"
+                .parse::<Timestamp<Cocoa>>()
+                .unwrap()
+                .raw()
+                .to_bits(),
+            636_249_600.25f64.to_bits()
+        );
+    }
+
+    #[test]
+    fn cocoa_invalid() {
+        assert!(Timestamp::<Cocoa>::from_raw(f64::NAN).nanos().is_none());
+        assert!(Timestamp::<Cocoa>::from_raw(f64::NEG_INFINITY)
+            .nanos()
+            .is_none());
+        assert!(Timestamp::<Cocoa>::from_raw(1e20).nanos().is_none());
+        assert!(Timestamp::<Cocoa>::from_raw(-1e20).nanos().is_none());
+    }
+
+    #[test]
+    fn hfs_plus() {
+        let timestamp: Timestamp<HfsPlus> = "2021-03-01".parse().unwrap();
+
+        assert_eq!(timestamp.raw(), 3_697_401_600);
+        assert_eq!(timestamp.to_string(), "2021-03-01T00:00:00Z");
+        assert_eq!(
+            Timestamp::<HfsPlus>::from_raw(u32::MAX).to_string(),
+            "2040-02-06T06:28:15Z"
+        );
+        assert!("1903-12-31".parse::<Timestamp<HfsPlus>>().is_err());
+    }
+}
@@ -1,39 +1,106 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
-use super::endian::{FromBigEndian, FromLittleEndian};
+use super::endian::{FromBigEndian, FromLittleEndian, ToLittleEndian};
 
 // TODO(danilan): Migrate from own implementations to num crate float/num/int types
 
+/// How the most significant byte of a type's representation relates to its value
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum ByteOrdering {
+    /// The most significant byte orders like a `u8` (unsigned integers)
+    Unsigned,
+    /// The most significant byte orders like an `i8` (two's complement integers)
+    Signed,
+    /// Cannot derive anything from the bytes without converting (e.g. floats)
+    Unordered,
+}
+
 /// A general marker trait that represents a type that bitgrep supports.
 /// Used as part of the generics black magic
 pub trait BitType:
-    num::Num + FromStr + Copy + PartialOrd + Display + FromLittleEndian + FromBigEndian
+    num::Zero
+    + FromStr
+    + Copy
+    + PartialOrd
+    + Display
+    + FromLittleEndian
+    + FromBigEndian
+    + ToLittleEndian
+    + Send
+    + Sync
 {
+    /// Ordering of the most significant byte, integers that have an ordering
+    /// are also compared exactly, so can be searched as raw bytes.
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Unordered;
+
+    /// Name of the type in the output
+    const NAME: &'static str;
 }
 
-impl BitType for f32 {}
-impl BitType for f64 {}
+impl BitType for f32 {
+    const NAME: &'static str = "f32";
+}
+impl BitType for f64 {
+    const NAME: &'static str = "f64";
+}
 
-impl BitType for i8 {}
-impl BitType for i16 {}
-impl BitType for i32 {}
-impl BitType for i64 {}
-impl BitType for i128 {}
+impl BitType for i8 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Signed;
+    const NAME: &'static str = "i8";
+}
+impl BitType for i16 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Signed;
+    const NAME: &'static str = "i16";
+}
+impl BitType for i32 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Signed;
+    const NAME: &'static str = "i32";
+}
+impl BitType for i64 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Signed;
+    const NAME: &'static str = "i64";
+}
+impl BitType for i128 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Signed;
+    const NAME: &'static str = "i128";
+}
 
-impl BitType for u8 {}
-impl BitType for u16 {}
-impl BitType for u32 {}
-impl BitType for u64 {}
-impl BitType for u128 {}
+impl BitType for u8 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Unsigned;
+    const NAME: &'static str = "u8";
+}
+impl BitType for u16 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Unsigned;
+    const NAME: &'static str = "u16";
+}
+impl BitType for u32 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Unsigned;
+    const NAME: &'static str = "u32";
+}
+impl BitType for u64 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Unsigned;
+    const NAME: &'static str = "u64";
+}
+impl BitType for u128 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Unsigned;
+    const NAME: &'static str = "u128";
+}
 
-pub trait Float: BitType + approx::UlpsEq {
+pub trait Float: BitType + approx::UlpsEq<Epsilon = Self> + approx::RelativeEq {
+    /// Converts a tolerance, may lose precision
+    fn from_f64(value: f64) -> Self;
     fn is_nan(self) -> bool;
     fn is_pos_infinity(self) -> bool;
     fn is_neg_infinity(self) -> bool;
 }
 
 impl Float for f32 {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_f64(value: f64) -> Self {
+        return value as f32;
+    }
+
     fn is_nan(self) -> bool {
         return self.is_nan();
     }
@@ -47,6 +114,10 @@ impl Float for f32 {
     }
 }
 impl Float for f64 {
+    fn from_f64(value: f64) -> Self {
+        return value;
+    }
+
     fn is_nan(self) -> bool {
         return self.is_nan();
     }
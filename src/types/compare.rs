@@ -1,9 +1,41 @@
-use approx::Ulps;
-
 use super::bit_type::{BitType, Float};
+use super::literal_set::{HashLiteralSet, LiteralSet, SortedLiteralSet};
+
+/// How close floats must be to be considered equal, integers are always compared exactly.
+///
+/// Floats are equal if any of the checks pass:
+/// * Absolute difference is at most `abs_epsilon` (the type's machine epsilon by default)
+/// * Same sign and at most `max_ulps` representable values apart
+/// * Relative difference (to the larger value) is at most `rel_epsilon`, when set
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub max_ulps: u32,
+    pub abs_epsilon: Option<f64>,
+    pub rel_epsilon: Option<f64>,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        return Tolerance {
+            max_ulps: 4,
+            abs_epsilon: None,
+            rel_epsilon: None,
+        };
+    }
+}
 
 pub trait Compare: BitType {
-    fn equal(&self, other: &Self) -> bool;
+    /// Set of literals matched with [`Compare::equal`]
+    type Set: LiteralSet<Self>;
+
+    fn equal(&self, other: &Self, tolerance: &Tolerance) -> bool;
+
+    /// Nanoseconds since the Unix epoch of timestamp types, None for other types
+    /// and invalid timestamps
+    #[inline]
+    fn nanos(&self) -> Option<i128> {
+        return None;
+    }
 }
 
 #[inline]
@@ -11,9 +43,8 @@ fn numeric_equal<T: BitType>(first: &T, second: &T) -> bool {
     return first == second;
 }
 
-// TODO(danilan): Somehow get max_ulps as configuration
 #[inline]
-fn float_equal<T>(first: &T, second: &T) -> bool
+fn float_equal<T>(first: &T, second: &T, tolerance: &Tolerance) -> bool
 where
     T: Float,
 {
@@ -25,89 +56,123 @@ where
         return true;
     }
 
-    return Ulps::default().max_ulps(4).eq(&first, &second);
+    let abs_epsilon = tolerance
+        .abs_epsilon
+        .map_or(T::default_epsilon(), T::from_f64);
+
+    if first.ulps_eq(second, abs_epsilon, tolerance.max_ulps) {
+        return true;
+    }
+
+    return tolerance.rel_epsilon.is_some_and(|rel_epsilon| {
+        first.relative_eq(second, abs_epsilon, T::from_f64(rel_epsilon))
+    });
 }
 
 impl Compare for f32 {
+    type Set = SortedLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
-        return float_equal(self, other);
+    fn equal(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        return float_equal(self, other, tolerance);
     }
 }
 
 impl Compare for f64 {
+    type Set = SortedLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
-        return float_equal(self, other);
+    fn equal(&self, other: &Self, tolerance: &Tolerance) -> bool {
+        return float_equal(self, other, tolerance);
     }
 }
 
 impl Compare for i8 {
+    type Set = HashLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
         return numeric_equal(self, other);
     }
 }
 
 impl Compare for i16 {
+    type Set = HashLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
         return numeric_equal(self, other);
     }
 }
 
 impl Compare for i32 {
+    type Set = HashLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
         return numeric_equal(self, other);
     }
 }
 
 impl Compare for i64 {
+    type Set = HashLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
         return numeric_equal(self, other);
     }
 }
 
 impl Compare for i128 {
+    type Set = HashLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
         return numeric_equal(self, other);
     }
 }
 
 impl Compare for u8 {
+    type Set = HashLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
         return numeric_equal(self, other);
     }
 }
 
 impl Compare for u16 {
+    type Set = HashLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
         return numeric_equal(self, other);
     }
 }
 
 impl Compare for u32 {
+    type Set = HashLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
         return numeric_equal(self, other);
     }
 }
 
 impl Compare for u64 {
+    type Set = HashLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
         return numeric_equal(self, other);
     }
 }
 
 impl Compare for u128 {
+    type Set = HashLiteralSet<Self>;
+
     #[inline]
-    fn equal(&self, other: &Self) -> bool {
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
         return numeric_equal(self, other);
     }
 }
@@ -116,100 +181,104 @@ impl Compare for u128 {
 mod tests {
     use super::*;
 
+    fn default_equal<T: Float>(first: &T, second: &T) -> bool {
+        return float_equal(first, second, &Tolerance::default());
+    }
+
     #[test]
     fn float_equal_f32_not_equal_returns_false() {
-        assert!(!float_equal::<f32>(&32.445, &32.0));
-        assert!(!float_equal::<f32>(&32.445, &f32::NAN));
-        assert!(!float_equal::<f32>(&32.0, &34.0));
-        assert!(!float_equal::<f32>(&0.0, &0.0001));
+        assert!(!default_equal::<f32>(&32.445, &32.0));
+        assert!(!default_equal::<f32>(&32.445, &f32::NAN));
+        assert!(!default_equal::<f32>(&32.0, &34.0));
+        assert!(!default_equal::<f32>(&0.0, &0.0001));
 
-        assert!(!float_equal::<f32>(&f32::NAN, &f32::INFINITY));
-        assert!(!float_equal::<f32>(&f32::NAN, &f32::NEG_INFINITY));
+        assert!(!default_equal::<f32>(&f32::NAN, &f32::INFINITY));
+        assert!(!default_equal::<f32>(&f32::NAN, &f32::NEG_INFINITY));
 
-        assert!(!float_equal::<f32>(&f32::INFINITY, &f32::NEG_INFINITY));
-        assert!(!float_equal::<f32>(&f32::NEG_INFINITY, &f32::INFINITY));
+        assert!(!default_equal::<f32>(&f32::INFINITY, &f32::NEG_INFINITY));
+        assert!(!default_equal::<f32>(&f32::NEG_INFINITY, &f32::INFINITY));
 
-        assert!(!float_equal::<f32>(&0.0, &(f32::EPSILON * 2.0)));
+        assert!(!default_equal::<f32>(&0.0, &(f32::EPSILON * 2.0)));
     }
 
     #[test]
     fn float_equal_f64_not_equal_returns_false() {
-        assert!(!float_equal::<f64>(&32.445, &32.0));
-        assert!(!float_equal::<f64>(&32.445, &f64::NAN));
-        assert!(!float_equal::<f64>(&32.0, &34.0));
-        assert!(!float_equal::<f64>(&0.0, &0.0001));
+        assert!(!default_equal::<f64>(&32.445, &32.0));
+        assert!(!default_equal::<f64>(&32.445, &f64::NAN));
+        assert!(!default_equal::<f64>(&32.0, &34.0));
+        assert!(!default_equal::<f64>(&0.0, &0.0001));
 
-        assert!(!float_equal::<f64>(&f64::NAN, &f64::INFINITY));
-        assert!(!float_equal::<f64>(&f64::NAN, &f64::NEG_INFINITY));
+        assert!(!default_equal::<f64>(&f64::NAN, &f64::INFINITY));
+        assert!(!default_equal::<f64>(&f64::NAN, &f64::NEG_INFINITY));
 
-        assert!(!float_equal::<f64>(&f64::INFINITY, &f64::NEG_INFINITY));
-        assert!(!float_equal::<f64>(&f64::NEG_INFINITY, &f64::INFINITY));
+        assert!(!default_equal::<f64>(&f64::INFINITY, &f64::NEG_INFINITY));
+        assert!(!default_equal::<f64>(&f64::NEG_INFINITY, &f64::INFINITY));
 
-        assert!(!float_equal::<f64>(&0.0, &(f64::EPSILON * 2.0)));
+        assert!(!default_equal::<f64>(&0.0, &(f64::EPSILON * 2.0)));
     }
 
     #[test]
     fn float_equal_f32_equal_returns_true() {
-        assert!(float_equal::<f32>(&32.445, &32.445));
-        assert!(float_equal::<f32>(&(32.445 + f32::EPSILON), &32.445));
-        assert!(float_equal::<f32>(&(32.445 - f32::EPSILON), &32.445));
+        assert!(default_equal::<f32>(&32.445, &32.445));
+        assert!(default_equal::<f32>(&(32.445 + f32::EPSILON), &32.445));
+        assert!(default_equal::<f32>(&(32.445 - f32::EPSILON), &32.445));
 
-        assert!(float_equal::<f32>(&-0.0, &0.0));
-        assert!(float_equal::<f32>(&0.0, &0.0));
+        assert!(default_equal::<f32>(&-0.0, &0.0));
+        assert!(default_equal::<f32>(&0.0, &0.0));
 
-        assert!(float_equal::<f32>(&f32::NAN, &f32::NAN));
-        assert!(float_equal::<f32>(&f32::NAN, &-f32::NAN));
-        assert!(float_equal::<f32>(&-f32::NAN, &-f32::NAN));
-        assert!(float_equal::<f32>(&f32::NAN, &f32::NAN));
+        assert!(default_equal::<f32>(&f32::NAN, &f32::NAN));
+        assert!(default_equal::<f32>(&f32::NAN, &-f32::NAN));
+        assert!(default_equal::<f32>(&-f32::NAN, &-f32::NAN));
+        assert!(default_equal::<f32>(&f32::NAN, &f32::NAN));
     }
 
     #[test]
     fn float_equal_f32_equal_zeroes_returns_true() {
-        assert!(float_equal::<f32>(&-0.0, &0.0));
-        assert!(float_equal::<f32>(&0.0, &0.0));
+        assert!(default_equal::<f32>(&-0.0, &0.0));
+        assert!(default_equal::<f32>(&0.0, &0.0));
     }
 
     #[test]
     fn float_equal_f32_equal_special_returns_true() {
-        assert!(float_equal::<f32>(&f32::NAN, &f32::NAN));
-        assert!(float_equal::<f32>(&f32::NAN, &-f32::NAN));
-        assert!(float_equal::<f32>(&-f32::NAN, &-f32::NAN));
-        assert!(float_equal::<f32>(&f32::NAN, &-f32::NAN));
+        assert!(default_equal::<f32>(&f32::NAN, &f32::NAN));
+        assert!(default_equal::<f32>(&f32::NAN, &-f32::NAN));
+        assert!(default_equal::<f32>(&-f32::NAN, &-f32::NAN));
+        assert!(default_equal::<f32>(&f32::NAN, &-f32::NAN));
 
-        assert!(float_equal::<f32>(&f32::INFINITY, &f32::INFINITY));
-        assert!(float_equal::<f32>(&f32::NEG_INFINITY, &f32::NEG_INFINITY));
+        assert!(default_equal::<f32>(&f32::INFINITY, &f32::INFINITY));
+        assert!(default_equal::<f32>(&f32::NEG_INFINITY, &f32::NEG_INFINITY));
     }
 
     #[test]
     fn float_equal_f64_equal_returns_true() {
-        assert!(float_equal::<f64>(&32.445, &32.445));
-        assert!(float_equal::<f64>(&(32.445 + f64::EPSILON), &32.445));
-        assert!(float_equal::<f64>(&(32.445 - f64::EPSILON), &32.445));
+        assert!(default_equal::<f64>(&32.445, &32.445));
+        assert!(default_equal::<f64>(&(32.445 + f64::EPSILON), &32.445));
+        assert!(default_equal::<f64>(&(32.445 - f64::EPSILON), &32.445));
 
-        assert!(float_equal::<f64>(&-0.0, &0.0));
-        assert!(float_equal::<f64>(&0.0, &0.0));
+        assert!(default_equal::<f64>(&-0.0, &0.0));
+        assert!(default_equal::<f64>(&0.0, &0.0));
 
-        assert!(float_equal::<f64>(&f64::NAN, &f64::NAN));
-        assert!(float_equal::<f64>(&f64::NAN, &-f64::NAN));
-        assert!(float_equal::<f64>(&-f64::NAN, &-f64::NAN));
-        assert!(float_equal::<f64>(&f64::NAN, &f64::NAN));
+        assert!(default_equal::<f64>(&f64::NAN, &f64::NAN));
+        assert!(default_equal::<f64>(&f64::NAN, &-f64::NAN));
+        assert!(default_equal::<f64>(&-f64::NAN, &-f64::NAN));
+        assert!(default_equal::<f64>(&f64::NAN, &f64::NAN));
     }
 
     #[test]
     fn float_equal_f64_equal_zeroes_returns_true() {
-        assert!(float_equal::<f64>(&-0.0, &0.0));
-        assert!(float_equal::<f64>(&0.0, &0.0));
+        assert!(default_equal::<f64>(&-0.0, &0.0));
+        assert!(default_equal::<f64>(&0.0, &0.0));
     }
 
     #[test]
     fn float_equal_f64_equal_special_returns_true() {
-        assert!(float_equal::<f64>(&f64::NAN, &f64::NAN));
-        assert!(float_equal::<f64>(&f64::NAN, &-f64::NAN));
-        assert!(float_equal::<f64>(&-f64::NAN, &-f64::NAN));
-        assert!(float_equal::<f64>(&f64::NAN, &-f64::NAN));
+        assert!(default_equal::<f64>(&f64::NAN, &f64::NAN));
+        assert!(default_equal::<f64>(&f64::NAN, &-f64::NAN));
+        assert!(default_equal::<f64>(&-f64::NAN, &-f64::NAN));
+        assert!(default_equal::<f64>(&f64::NAN, &-f64::NAN));
 
-        assert!(float_equal::<f64>(&f64::INFINITY, &f64::INFINITY));
-        assert!(float_equal::<f64>(&f64::NEG_INFINITY, &f64::NEG_INFINITY));
+        assert!(default_equal::<f64>(&f64::INFINITY, &f64::INFINITY));
+        assert!(default_equal::<f64>(&f64::NEG_INFINITY, &f64::NEG_INFINITY));
     }
 
     #[test]
@@ -225,4 +294,72 @@ mod tests {
         assert!(numeric_equal::<u8>(&40, &40));
         assert!(numeric_equal::<i64>(&0, &0));
     }
+
+    #[test]
+    fn float_equal_max_ulps() {
+        // Large enough so the default absolute epsilon doesn't apply
+        let one_ulp_apart = f64::from_bits(1000.0f64.to_bits() + 1);
+        let five_ulps_apart = f64::from_bits(1000.0f64.to_bits() + 5);
+        let exact = Tolerance {
+            max_ulps: 0,
+            ..Default::default()
+        };
+        let loose = Tolerance {
+            max_ulps: 5,
+            ..Default::default()
+        };
+
+        assert!(default_equal(&1000.0, &one_ulp_apart));
+        assert!(!float_equal(&1000.0, &one_ulp_apart, &exact));
+        assert!(!default_equal(&1000.0, &five_ulps_apart));
+        assert!(float_equal(&1000.0, &five_ulps_apart, &loose));
+    }
+
+    #[test]
+    fn float_equal_abs_epsilon() {
+        let tolerance = Tolerance {
+            abs_epsilon: Some(0.5),
+            ..Default::default()
+        };
+
+        assert!(float_equal(&1.0f32, &1.5, &tolerance));
+        assert!(float_equal(&-0.25f32, &0.25, &tolerance));
+        assert!(!float_equal(&1.0f32, &1.6, &tolerance));
+    }
+
+    #[test]
+    fn float_equal_zero_abs_epsilon_is_exact_near_zero() {
+        let tolerance = Tolerance {
+            abs_epsilon: Some(0.0),
+            ..Default::default()
+        };
+
+        assert!(default_equal(&0.0f64, &1e-17));
+        assert!(!float_equal(&0.0f64, &1e-17, &tolerance));
+    }
+
+    #[test]
+    fn float_equal_rel_epsilon() {
+        let tolerance = Tolerance {
+            rel_epsilon: Some(0.01),
+            ..Default::default()
+        };
+
+        assert!(float_equal(&1000.0f64, &1009.0, &tolerance));
+        assert!(float_equal(&-1000.0f64, &-991.0, &tolerance));
+        assert!(!float_equal(&1000.0f64, &1011.0, &tolerance));
+        assert!(!float_equal(&1.0f64, &-1.0, &tolerance));
+    }
+
+    #[test]
+    fn numeric_equal_ignores_tolerance() {
+        let tolerance = Tolerance {
+            abs_epsilon: Some(10.0),
+            rel_epsilon: Some(1.0),
+            max_ulps: 100,
+        };
+
+        assert!(!3i32.equal(&4, &tolerance));
+        assert!(3u8.equal(&3, &tolerance));
+    }
 }
@@ -0,0 +1,362 @@
+use thiserror::Error;
+
+pub const NANOS_PER_MICRO: i128 = 1_000;
+pub const NANOS_PER_MILLI: i128 = 1_000_000;
+pub const NANOS_PER_SECOND: i128 = 1_000_000_000;
+pub const NANOS_PER_DAY: i128 = 86_400 * NANOS_PER_SECOND;
+
+const MAX_FRACTION_DIGITS: usize = 9;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DateTimeError {
+    #[error("Invalid ISO-8601 date '{0}', expected e.g. 2021-03-01T00:00:00Z")]
+    InvalidFormat(String),
+    #[error("Date is out of range: {0}")]
+    OutOfRange(String),
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+#[must_use]
+pub fn days_from_civil(year: i128, month: u32, day: u32) -> i128 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_from_march = i128::from((month + 9) % 12);
+    let day_of_year = (153 * month_from_march + 2) / 5 + i128::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    return era * 146_097 + day_of_era - 719_468;
+}
+
+/// Proleptic Gregorian (year, month, day) of days since 1970-01-01
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn civil_from_days(days: i128) -> (i128, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+
+    // Both are small positive numbers at this point
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = (if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    }) as u32;
+
+    let year = year_of_era + era * 400;
+    return (if month <= 2 { year + 1 } else { year }, month, day);
+}
+
+#[must_use]
+pub fn is_leap_year(year: i128) -> bool {
+    return year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+}
+
+#[must_use]
+pub fn days_in_month(year: i128, month: u32) -> u32 {
+    return match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+}
+
+/// Parses an ISO-8601 date to nanoseconds since the Unix epoch.
+///
+/// Accepts a date (`2021-03-01`) optionally followed by a time (`T12:30`, `T12:30:15`
+/// or `T12:30:15.123`) and an offset (`Z`, `+02:00` or `-0530`).
+/// Dates without an offset are in UTC.
+///
+/// # Errors
+/// Returns an error if the date is malformed or a field is out of range (e.g. month 13)
+pub fn parse_iso8601(input: &str) -> Result<i128, DateTimeError> {
+    let invalid = || DateTimeError::InvalidFormat(input.to_string());
+    let mut cursor = Cursor { rest: input.trim() };
+
+    let year = cursor.number(4).ok_or_else(invalid)?;
+    let month = cursor.expect('-').and_then(|()| cursor.number(2));
+    let day = cursor.expect('-').and_then(|()| cursor.number(2));
+    let (Some(month), Some(day)) = (month, day) else {
+        return Err(invalid());
+    };
+
+    let (mut hour, mut minute, mut second, mut nanos) = (0, 0, 0, 0);
+    if cursor.eat(&['T', 't', ' ']) {
+        hour = cursor.number(2).ok_or_else(invalid)?;
+        minute = cursor
+            .expect(':')
+            .and_then(|()| cursor.number(2))
+            .ok_or_else(invalid)?;
+
+        if cursor.eat(&[':']) {
+            second = cursor.number(2).ok_or_else(invalid)?;
+
+            if cursor.eat(&['.', ',']) {
+                nanos = cursor.fraction().ok_or_else(invalid)?;
+            }
+        }
+    }
+
+    let offset_seconds = cursor.offset().ok_or_else(invalid)?;
+    if !cursor.rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let year = i128::from(year);
+    let (Ok(month), Ok(day)) = (u32::try_from(month), u32::try_from(day)) else {
+        return Err(invalid());
+    };
+    if !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(DateTimeError::OutOfRange(input.to_string()));
+    }
+
+    let seconds = i128::from(hour * 3600 + minute * 60 + second) - offset_seconds;
+    return Ok(days_from_civil(year, month, day) * NANOS_PER_DAY
+        + seconds * NANOS_PER_SECOND
+        + nanos);
+}
+
+/// Formats nanoseconds since the Unix epoch as an ISO-8601 UTC date,
+/// with `fraction_digits` digits of a second (at most 9).
+#[must_use]
+pub fn format_iso8601(nanos: i128, fraction_digits: usize) -> String {
+    return format_local_iso8601(nanos, fraction_digits) + "Z";
+}
+
+/// Same as [`format_iso8601`] without the UTC designator, for times in an unknown zone
+#[must_use]
+pub fn format_local_iso8601(nanos: i128, fraction_digits: usize) -> String {
+    let (year, month, day) = civil_from_days(nanos.div_euclid(NANOS_PER_DAY));
+    let time = nanos.rem_euclid(NANOS_PER_DAY);
+
+    let seconds = time / NANOS_PER_SECOND;
+    let mut result = format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+    );
+
+    let fraction_digits = fraction_digits.min(MAX_FRACTION_DIGITS);
+    if fraction_digits > 0 {
+        let fraction = format!("{:09}", time % NANOS_PER_SECOND);
+        result.push('.');
+        result.push_str(&fraction[..fraction_digits]);
+    }
+
+    return result;
+}
+
+/// Consumes an ISO-8601 string from the start
+struct Cursor<'a> {
+    rest: &'a str,
+}
+
+impl Cursor<'_> {
+    /// Reads exactly `digits` digits
+    fn number(&mut self, digits: usize) -> Option<i64> {
+        let token = self.rest.get(..digits)?;
+        if !token.bytes().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        self.rest = &self.rest[digits..];
+        return token.parse().ok();
+    }
+
+    /// Reads a fraction of a second as nanoseconds, extra digits are truncated
+    fn fraction(&mut self) -> Option<i128> {
+        let digits = self
+            .rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len());
+        if digits == 0 {
+            return None;
+        }
+
+        let mut padded = self.rest[..digits.min(MAX_FRACTION_DIGITS)].to_string();
+        while padded.len() < MAX_FRACTION_DIGITS {
+            padded.push('0');
+        }
+
+        self.rest = &self.rest[digits..];
+        return padded.parse().ok();
+    }
+
+    /// Reads an optional UTC offset, returns it in seconds
+    fn offset(&mut self) -> Option<i128> {
+        if self.rest.is_empty() || self.eat(&['Z', 'z']) {
+            return Some(0);
+        }
+
+        let sign = match self.rest.chars().next()? {
+            '+' => 1,
+            '-' => -1,
+            _ => return None,
+        };
+        self.rest = &self.rest[1..];
+
+        let hours = self.number(2)?;
+        self.eat(&[':']);
+        let minutes = self.number(2)?;
+
+        if hours > 23 || minutes > 59 {
+            return None;
+        }
+
+        return Some(sign * i128::from(hours * 3600 + minutes * 60));
+    }
+
+    fn expect(&mut self, c: char) -> Option<()> {
+        return self.eat(&[c]).then_some(());
+    }
+
+    fn eat(&mut self, chars: &[char]) -> bool {
+        let Some(rest) = self.rest.strip_prefix(chars) else {
+            return false;
+        };
+
+        self.rest = rest;
+        return true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn civil_round_trip() {
+        for days in [-719_468, -1, 0, 1, 11_016, 18_687, 2_932_896] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn civil_known_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(1601, 1, 1), -134_774);
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(18_687), (2021, 3, 1));
+    }
+
+    #[test]
+    fn parse_date_only() {
+        assert_eq!(parse_iso8601("1970-01-01"), Ok(0));
+        assert_eq!(
+            parse_iso8601("2021-03-01"),
+            Ok(1_614_556_800 * NANOS_PER_SECOND)
+        );
+    }
+
+    #[test]
+    fn parse_date_time() {
+        assert_eq!(
+            parse_iso8601("2021-03-01T00:00:00Z"),
+            Ok(1_614_556_800 * NANOS_PER_SECOND)
+        );
+        assert_eq!(
+            parse_iso8601("2021-03-01 12:30"),
+            Ok((1_614_556_800 + 12 * 3600 + 30 * 60) * NANOS_PER_SECOND)
+        );
+        assert_eq!(
+            parse_iso8601("1969-12-31T23:59:59.5Z"),
+            Ok(-NANOS_PER_SECOND / 2)
+        );
+        assert_eq!(parse_iso8601("1970-01-01T00:00:00.000000001"), Ok(1));
+        assert_eq!(parse_iso8601("1970-01-01T00:00:00.0000000019"), Ok(1));
+    }
+
+    #[test]
+    fn parse_offsets() {
+        let utc = parse_iso8601("2021-03-01T10:00:00Z").unwrap();
+
+        assert_eq!(parse_iso8601("2021-03-01T12:00:00+02:00"), Ok(utc));
+        assert_eq!(parse_iso8601("2021-03-01T04:30:00-0530"), Ok(utc));
+    }
+
+    #[test]
+    fn parse_invalid_format() {
+        for input in [
+            "",
+            "2021",
+            "2021-3-01",
+            "2021-03-01T",
+            "2021-03-01T10",
+            "2021-03-01T10:00:00.",
+            "2021-03-01T10:00:00Y",
+            "2021-03-01T10:00:00+2",
+            "20210301",
+            "1614556800",
+        ] {
+            assert_eq!(
+                parse_iso8601(input),
+                Err(DateTimeError::InvalidFormat(input.to_string())),
+                "input {input}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_out_of_range() {
+        for input in [
+            "2021-13-01",
+            "2021-02-29",
+            "2021-03-01T24:00",
+            "2021-03-01T10:60",
+        ] {
+            assert_eq!(
+                parse_iso8601(input),
+                Err(DateTimeError::OutOfRange(input.to_string())),
+                "input {input}"
+            );
+        }
+
+        assert!(parse_iso8601("2020-02-29").is_ok());
+    }
+
+    #[test]
+    fn format_dates() {
+        assert_eq!(format_iso8601(0, 0), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_iso8601(1_614_556_800 * NANOS_PER_SECOND + 123_456_789, 3),
+            "2021-03-01T00:00:00.123Z"
+        );
+        assert_eq!(format_iso8601(-1, 9), "1969-12-31T23:59:59.999999999Z");
+        assert_eq!(
+            format_iso8601(-134_774 * NANOS_PER_DAY, 0),
+            "1601-01-01T00:00:00Z"
+        );
+        assert_eq!(format_local_iso8601(-1, 3), "1969-12-31T23:59:59.999");
+    }
+
+    #[test]
+    fn format_parse_round_trip() {
+        for nanos in [
+            0,
+            1,
+            -1,
+            1_614_556_800_123_456_789,
+            -11_644_473_600_000_000_000,
+        ] {
+            assert_eq!(parse_iso8601(&format_iso8601(nanos, 9)), Ok(nanos));
+        }
+    }
+}
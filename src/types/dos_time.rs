@@ -0,0 +1,145 @@
+use super::datetime::{
+    civil_from_days, days_from_civil, days_in_month, NANOS_PER_DAY, NANOS_PER_SECOND,
+};
+use super::timestamp::Epoch;
+
+/// Years are stored as 7 bits since 1980
+const DOS_YEARS: std::ops::RangeInclusive<i128> = 1980..=2107;
+
+/// MS-DOS date and time (e.g. FAT and ZIP), a 16-bit time followed by a 16-bit date.
+///
+/// Stored as a `u32` with the time in the lowest bits (i.e. as little endian).
+/// Seconds are stored divided by 2.
+/// DOS times are usually local time, they are printed as is without the `Z`.
+#[derive(Clone, Copy)]
+pub struct Dos;
+
+impl Dos {
+    #[allow(clippy::cast_possible_truncation)]
+    fn fields(raw: u32) -> (u16, u16) {
+        return (raw as u16, (raw >> 16) as u16);
+    }
+
+    fn pack(time: u16, date: u16) -> u32 {
+        return u32::from(time) | u32::from(date) << 16;
+    }
+}
+
+impl Epoch for Dos {
+    type Raw = u32;
+    const NAME: &'static str = "dos";
+    const FRACTION_DIGITS: usize = 0;
+
+    fn to_nanos(raw: u32) -> Option<i128> {
+        let (time, date) = Self::fields(raw);
+
+        let year = 1980 + i128::from(date >> 9);
+        let month = u32::from(date >> 5 & 0xF);
+        let day = u32::from(date & 0x1F);
+        let hour = i128::from(time >> 11);
+        let minute = i128::from(time >> 5 & 0x3F);
+        let second = i128::from(time & 0x1F) * 2;
+
+        let valid = (1..=12).contains(&month)
+            && (1..=days_in_month(year, month)).contains(&day)
+            && hour <= 23
+            && minute <= 59
+            && second <= 59;
+
+        if !valid {
+            return None;
+        }
+
+        return Some(
+            days_from_civil(year, month, day) * NANOS_PER_DAY
+                + (hour * 3600 + minute * 60 + second) * NANOS_PER_SECOND,
+        );
+    }
+
+    /// Rounds down to an even second
+    fn from_nanos(nanos: i128) -> Option<u32> {
+        let (year, month, day) = civil_from_days(nanos.div_euclid(NANOS_PER_DAY));
+        if !DOS_YEARS.contains(&year) {
+            return None;
+        }
+
+        let seconds = nanos.rem_euclid(NANOS_PER_DAY) / NANOS_PER_SECOND;
+        let time = (seconds / 3600) << 11 | (seconds % 3600 / 60) << 5 | (seconds % 60 / 2);
+        let date = (year - 1980) << 9 | i128::from(month) << 5 | i128::from(day);
+
+        return Some(Self::pack(
+            u16::try_from(time).ok()?,
+            u16::try_from(date).ok()?,
+        ));
+    }
+
+    fn raw_from_big_endian(bytes: &[u8]) -> u32 {
+        let time = u16::from_be_bytes([bytes[0], bytes[1]]);
+        let date = u16::from_be_bytes([bytes[2], bytes[3]]);
+
+        return Self::pack(time, date);
+    }
+
+    fn kind(_raw: u32) -> Option<&'static str> {
+        return Some("local");
+    }
+
+    fn is_utc(_raw: u32) -> bool {
+        return false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::endian::FromBigEndian;
+    use crate::types::timestamp::Timestamp;
+
+    // 2021-03-01 13:45:30
+    const TIME: u16 = 13 << 11 | 45 << 5 | 15;
+    const DATE: u16 = 41 << 9 | 3 << 5 | 1;
+
+    #[test]
+    fn dos() {
+        let timestamp = Timestamp::<Dos>::from_raw(Dos::pack(TIME, DATE));
+
+        assert_eq!(timestamp.to_string(), "2021-03-01T13:45:30 (local)");
+        assert_eq!(
+            "2021-03-01T13:45:31Z"
+                .parse::<Timestamp<Dos>>()
+                .unwrap()
+                .raw(),
+            Dos::pack(TIME, DATE)
+        );
+    }
+
+    #[test]
+    fn dos_big_endian_swaps_fields() {
+        let bytes = [TIME.to_be_bytes(), DATE.to_be_bytes()].concat();
+
+        let timestamp = <Timestamp<Dos> as FromBigEndian>::from_bytes(&bytes);
+
+        assert_eq!(timestamp.to_string(), "2021-03-01T13:45:30 (local)");
+    }
+
+    #[test]
+    fn dos_display_without_z() {
+        let timestamp = Timestamp::<Dos>::from_raw(Dos::pack(TIME, DATE));
+
+        assert!(!timestamp.to_string().contains('Z'));
+    }
+
+    #[test]
+    fn dos_invalid() {
+        let february_30 = 41 << 9 | 2 << 5 | 0x1E;
+        let hour_24 = 24 << 11;
+        let second_60 = 30;
+
+        assert!(Dos::to_nanos(Dos::pack(TIME, february_30)).is_none());
+        assert!(Dos::to_nanos(Dos::pack(hour_24, DATE)).is_none());
+        assert!(Dos::to_nanos(Dos::pack(second_60, DATE)).is_none());
+        assert!(Dos::to_nanos(0).is_none());
+        assert!("1979-12-31".parse::<Timestamp<Dos>>().is_err());
+        assert!("2108-01-01".parse::<Timestamp<Dos>>().is_err());
+    }
+}
@@ -269,3 +269,81 @@ impl FromBigEndian for u128 {
         }));
     }
 }
+
+/// Converts a value to its little endian byte representation,
+/// used to search for values as raw bytes.
+pub trait ToLittleEndian {
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+impl ToLittleEndian for f64 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
+
+impl ToLittleEndian for f32 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
+
+impl ToLittleEndian for i8 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
+
+impl ToLittleEndian for i16 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
+
+impl ToLittleEndian for i32 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
+
+impl ToLittleEndian for i64 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
+
+impl ToLittleEndian for i128 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
+
+impl ToLittleEndian for u8 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
+
+impl ToLittleEndian for u16 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
+
+impl ToLittleEndian for u32 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
+
+impl ToLittleEndian for u64 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
+
+impl ToLittleEndian for u128 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_le_bytes().to_vec();
+    }
+}
@@ -0,0 +1,131 @@
+use super::datetime::NANOS_PER_SECOND;
+use super::timestamp::{nanos_to_ticks, ticks_to_nanos, Epoch};
+
+/// 1980-01-06
+const GPS_EPOCH_NANOS: i128 = 315_964_800 * NANOS_PER_SECOND;
+/// 10000-01-01, later weeks are noise
+const GPS_MAX_NANOS: i128 = 253_402_300_800 * NANOS_PER_SECOND;
+const SECONDS_PER_WEEK: u32 = 7 * 86_400;
+
+/// GPS time as a week number followed by seconds of the week, both `u32`.
+///
+/// Stored as a `u64` with the week in the lowest bits (i.e. as little endian).
+/// GPS time has no leap seconds, times are printed as is and not corrected to UTC,
+/// so without the `Z`.
+#[derive(Clone, Copy)]
+pub struct Gps;
+
+impl Gps {
+    #[allow(clippy::cast_possible_truncation)]
+    fn fields(raw: u64) -> (u32, u32) {
+        return (raw as u32, (raw >> 32) as u32);
+    }
+
+    fn pack(week: u32, seconds: u32) -> u64 {
+        return u64::from(week) | u64::from(seconds) << 32;
+    }
+}
+
+impl Epoch for Gps {
+    type Raw = u64;
+    const NAME: &'static str = "gps";
+    const FRACTION_DIGITS: usize = 0;
+
+    fn to_nanos(raw: u64) -> Option<i128> {
+        let (week, seconds) = Self::fields(raw);
+        if seconds >= SECONDS_PER_WEEK {
+            return None;
+        }
+
+        let seconds = i128::from(week) * i128::from(SECONDS_PER_WEEK) + i128::from(seconds);
+        let nanos = ticks_to_nanos(seconds, NANOS_PER_SECOND) + GPS_EPOCH_NANOS;
+
+        return (nanos < GPS_MAX_NANOS).then_some(nanos);
+    }
+
+    fn from_nanos(nanos: i128) -> Option<u64> {
+        if nanos >= GPS_MAX_NANOS {
+            return None;
+        }
+
+        let seconds = nanos_to_ticks(nanos - GPS_EPOCH_NANOS, NANOS_PER_SECOND);
+        let week = u32::try_from(seconds.div_euclid(SECONDS_PER_WEEK.into())).ok()?;
+        let seconds = u32::try_from(seconds.rem_euclid(SECONDS_PER_WEEK.into())).ok()?;
+
+        return Some(Self::pack(week, seconds));
+    }
+
+    fn raw_from_big_endian(bytes: &[u8]) -> u64 {
+        let week = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let seconds = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+
+        return Self::pack(week, seconds);
+    }
+
+    fn kind(_raw: u64) -> Option<&'static str> {
+        return Some("gps");
+    }
+
+    fn is_utc(_raw: u64) -> bool {
+        return false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::datetime::parse_iso8601;
+    use crate::types::endian::FromBigEndian;
+    use crate::types::timestamp::Timestamp;
+
+    #[test]
+    fn epochs() {
+        assert_eq!(GPS_EPOCH_NANOS, parse_iso8601("1980-01-06").unwrap());
+        assert_eq!(
+            GPS_MAX_NANOS,
+            parse_iso8601("9999-12-31").unwrap() + 86_400 * NANOS_PER_SECOND
+        );
+    }
+
+    #[test]
+    fn gps() {
+        // Week 2147, Monday 12:00
+        let raw = Gps::pack(2147, 86_400 + 12 * 3600);
+        let timestamp = Timestamp::<Gps>::from_raw(raw);
+
+        assert_eq!(timestamp.to_string(), "2021-03-01T12:00:00 (gps)");
+        assert_eq!(
+            "2021-03-01T12:00:00Z"
+                .parse::<Timestamp<Gps>>()
+                .unwrap()
+                .raw(),
+            raw
+        );
+    }
+
+    #[test]
+    fn gps_big_endian_swaps_fields() {
+        let bytes: Vec<u8> = [2147u32, 86_400 + 12 * 3600]
+            .iter()
+            .flat_map(|f| f.to_be_bytes())
+            .collect();
+
+        let timestamp = <Timestamp<Gps> as FromBigEndian>::from_bytes(&bytes);
+
+        assert_eq!(timestamp.to_string(), "2021-03-01T12:00:00 (gps)");
+    }
+
+    #[test]
+    fn gps_display_without_z() {
+        let timestamp = Timestamp::<Gps>::from_raw(Gps::pack(2147, 0));
+
+        assert!(!timestamp.to_string().contains('Z'));
+    }
+
+    #[test]
+    fn gps_invalid() {
+        assert!(Gps::to_nanos(Gps::pack(2147, SECONDS_PER_WEEK)).is_none());
+        assert!(Gps::to_nanos(Gps::pack(u32::MAX, 0)).is_none());
+        assert!("1980-01-05".parse::<Timestamp<Gps>>().is_err());
+    }
+}
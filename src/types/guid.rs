@@ -0,0 +1,300 @@
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::ops::Add;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::bit_type::BitType;
+use super::compare::{Compare, Tolerance};
+use super::endian::{FromBigEndian, FromLittleEndian, ToLittleEndian};
+use super::literal_set::HashLiteralSet;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum GuidError {
+    #[error("invalid GUID '{0}', expected {{xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx}}")]
+    Invalid(String),
+}
+
+/// How a GUID is stored in memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuidLayout {
+    /// Windows `GUID` struct, `Data1`, `Data2` and `Data3` are little endian
+    Microsoft,
+    /// RFC 4122, all the fields are big endian (the same order as the text)
+    Rfc4122,
+}
+
+impl GuidLayout {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        return match self {
+            GuidLayout::Microsoft => "microsoft",
+            GuidLayout::Rfc4122 => "rfc4122",
+        };
+    }
+
+    /// Converts between the layout's bytes and the bytes in text order, works both ways
+    #[must_use]
+    fn swap(self, bytes: [u8; 16]) -> [u8; 16] {
+        let mut swapped = bytes;
+        if self == GuidLayout::Microsoft {
+            swapped[0..4].reverse();
+            swapped[4..6].reverse();
+            swapped[6..8].reverse();
+        }
+
+        return swapped;
+    }
+}
+
+/// A GUID (UUID), parsed from and displayed as text, e.g. `{9a3b2c1d-0e4f-4a5b-8c6d-7e8f90a1b2c3}`.
+///
+/// Compared by its value, the layout it was read in is only printed.
+#[derive(Clone, Copy)]
+pub struct Guid {
+    /// The bytes in text order as a big endian integer
+    value: u128,
+    layout: GuidLayout,
+}
+
+impl Guid {
+    /// Reads a GUID from the first 16 bytes in `layout`
+    ///
+    /// # Panics
+    ///
+    /// If `bytes` is shorter than 16 bytes
+    #[must_use]
+    pub fn from_layout(bytes: &[u8], layout: GuidLayout) -> Self {
+        let bytes: [u8; 16] = bytes[..16].try_into().expect("a GUID is 16 bytes");
+
+        return Guid {
+            value: u128::from_be_bytes(layout.swap(bytes)),
+            layout,
+        };
+    }
+
+    #[must_use]
+    pub fn to_layout(&self, layout: GuidLayout) -> [u8; 16] {
+        return layout.swap(self.value.to_be_bytes());
+    }
+
+    #[must_use]
+    pub fn layout(&self) -> GuidLayout {
+        return self.layout;
+    }
+
+    /// The version in the top 4 bits of `time_hi_and_version`
+    #[must_use]
+    pub fn version(&self) -> u8 {
+        return self.value.to_be_bytes()[6] >> 4;
+    }
+
+    /// Whether the variant is RFC 4122 and the version is 1 (time based) or 4 (random),
+    /// the versions of most GUIDs
+    #[must_use]
+    pub fn is_plausible(&self) -> bool {
+        let variant = self.value.to_be_bytes()[8] >> 6;
+        return variant == 0b10 && matches!(self.version(), 1 | 4);
+    }
+}
+
+impl BitType for Guid {
+    const NAME: &'static str = "guid";
+}
+
+impl Compare for Guid {
+    type Set = HashLiteralSet<Self>;
+
+    #[inline]
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
+        return self == other;
+    }
+}
+
+impl FromStr for Guid {
+    type Err = GuidError;
+
+    /// Parses 32 hex digits, hyphens and surrounding braces are optional
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let trimmed = trimmed
+            .strip_prefix('{')
+            .and_then(|inner| inner.strip_suffix('}'))
+            .unwrap_or(trimmed);
+
+        let digits: String = trimmed.chars().filter(|c| *c != '-').collect();
+        if digits.len() != 32 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(GuidError::Invalid(s.to_string()));
+        }
+
+        let value =
+            u128::from_str_radix(&digits, 16).map_err(|_| GuidError::Invalid(s.to_string()))?;
+        return Ok(Guid {
+            value,
+            layout: GuidLayout::Rfc4122,
+        });
+    }
+}
+
+impl Display for Guid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hex = format!("{:032x}", self.value);
+        return write!(
+            f,
+            "{{{}-{}-{}-{}-{}}} ({})",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32],
+            self.layout.name()
+        );
+    }
+}
+
+impl Debug for Guid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "Guid({self})");
+    }
+}
+
+impl PartialEq for Guid {
+    fn eq(&self, other: &Self) -> bool {
+        return self.value == other.value;
+    }
+}
+
+impl Eq for Guid {}
+
+impl Hash for Guid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl PartialOrd for Guid {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        return self.value.partial_cmp(&other.value);
+    }
+}
+
+/// Adds values, only required by [`num::Zero`]
+impl Add for Guid {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        return Guid {
+            value: self.value.wrapping_add(rhs.value),
+            layout: self.layout,
+        };
+    }
+}
+
+impl num::Zero for Guid {
+    fn zero() -> Self {
+        return Guid {
+            value: 0,
+            layout: GuidLayout::Rfc4122,
+        };
+    }
+
+    fn is_zero(&self) -> bool {
+        return self.value == 0;
+    }
+}
+
+/// Little endian is the Microsoft layout
+impl FromLittleEndian for Guid {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        return Guid::from_layout(bytes, GuidLayout::Microsoft);
+    }
+}
+
+/// Big endian is the RFC 4122 layout
+impl FromBigEndian for Guid {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        return Guid::from_layout(bytes, GuidLayout::Rfc4122);
+    }
+}
+
+impl ToLittleEndian for Guid {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.to_layout(GuidLayout::Microsoft).to_vec();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "{9a3b2c1d-0e4f-4a5b-8c6d-7e8f90a1b2c3}";
+    const MICROSOFT: [u8; 16] = [
+        0x1d, 0x2c, 0x3b, 0x9a, 0x4f, 0x0e, 0x5b, 0x4a, 0x8c, 0x6d, 0x7e, 0x8f, 0x90, 0xa1, 0xb2,
+        0xc3,
+    ];
+    const RFC4122: [u8; 16] = [
+        0x9a, 0x3b, 0x2c, 0x1d, 0x0e, 0x4f, 0x4a, 0x5b, 0x8c, 0x6d, 0x7e, 0x8f, 0x90, 0xa1, 0xb2,
+        0xc3,
+    ];
+
+    #[test]
+    fn parse_formats() {
+        let guid: Guid = TEXT.parse().unwrap();
+
+        assert_eq!("9A3B2C1D-0E4F-4A5B-8C6D-7E8F90A1B2C3".parse(), Ok(guid));
+        assert_eq!("9a3b2c1d0e4f4a5b8c6d7e8f90a1b2c3".parse(), Ok(guid));
+        assert_eq!(
+            "{9a3b2c1d-0e4f-4a5b-8c6d}".parse::<Guid>(),
+            Err(GuidError::Invalid("{9a3b2c1d-0e4f-4a5b-8c6d}".into()))
+        );
+        assert!("{9a3b2c1d-0e4f-4a5b-8c6d-7e8f90a1b2cg}"
+            .parse::<Guid>()
+            .is_err());
+    }
+
+    #[test]
+    fn layouts() {
+        let guid: Guid = TEXT.parse().unwrap();
+
+        assert_eq!(guid.to_layout(GuidLayout::Microsoft), MICROSOFT);
+        assert_eq!(guid.to_layout(GuidLayout::Rfc4122), RFC4122);
+        assert_eq!(<Guid as FromLittleEndian>::from_bytes(&MICROSOFT), guid);
+        assert_eq!(<Guid as FromBigEndian>::from_bytes(&RFC4122), guid);
+        assert_eq!(guid.to_bytes(), MICROSOFT.to_vec());
+    }
+
+    #[test]
+    fn display_canonical() {
+        let guid = Guid::from_layout(&MICROSOFT, GuidLayout::Microsoft);
+        assert_eq!(guid.to_string(), format!("{TEXT} (microsoft)"));
+
+        let guid = Guid::from_layout(&RFC4122, GuidLayout::Rfc4122);
+        assert_eq!(guid.to_string(), format!("{TEXT} (rfc4122)"));
+    }
+
+    #[test]
+    fn plausible_versions() {
+        let guid: Guid = TEXT.parse().unwrap();
+        assert_eq!(guid.version(), 4);
+        assert!(guid.is_plausible());
+
+        // Version 1
+        assert!("6ba7b810-9dad-11d1-80b4-00c04fd430c8"
+            .parse::<Guid>()
+            .unwrap()
+            .is_plausible());
+        // Version 3
+        assert!(!"6ba7b810-9dad-31d1-80b4-00c04fd430c8"
+            .parse::<Guid>()
+            .unwrap()
+            .is_plausible());
+        // NCS variant
+        assert!(!"9a3b2c1d-0e4f-4a5b-0c6d-7e8f90a1b2c3"
+            .parse::<Guid>()
+            .unwrap()
+            .is_plausible());
+        assert!(!<Guid as num::Zero>::zero().is_plausible());
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt::Display;
+
+use super::scan_value::ScanValue;
+
+/// Bytes matched by a hex pattern, displayed as ASCII with `.` for unprintable bytes
+#[derive(Debug, Clone, PartialEq)]
+pub struct HexMatch {
+    bytes: Vec<u8>,
+}
+
+impl HexMatch {
+    #[must_use]
+    pub fn new(bytes: Vec<u8>) -> Self {
+        return HexMatch { bytes };
+    }
+}
+
+impl ScanValue for HexMatch {
+    fn type_name(&self) -> &str {
+        return "hex";
+    }
+
+    fn size(&self) -> Option<usize> {
+        return Some(self.bytes.len());
+    }
+}
+
+impl Display for HexMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text: String = self
+            .bytes
+            .iter()
+            .map(|byte| {
+                if byte.is_ascii_graphic() || *byte == b' ' {
+                    char::from(*byte)
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+
+        return f.write_str(&text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_ascii() {
+        assert_eq!(
+            HexMatch::new(b"PK\x03\x04 a~".to_vec()).to_string(),
+            "PK.. a~"
+        );
+        assert_eq!(HexMatch::new(vec![0x7F, 0xFF]).to_string(), "..");
+    }
+}
@@ -0,0 +1,360 @@
+use std::fmt::Display;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::ops::Add;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::bit_type::{BitType, ByteOrdering};
+use super::compare::{Compare, Tolerance};
+use super::endian::{FromBigEndian, FromLittleEndian, ToLittleEndian};
+use super::literal_set::HashLiteralSet;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum IpError {
+    #[error("invalid IP address '{0}'")]
+    InvalidAddress(String),
+    #[error("invalid prefix length '{0}'")]
+    InvalidPrefix(String),
+    #[error("range '{0}' ends before it starts")]
+    EmptyRange(String),
+}
+
+/// An IPv4 address, compared as its 32-bit value.
+///
+/// Big endian is network byte order, little endian is host byte order on x86.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd)]
+pub struct Ipv4(Ipv4Addr);
+
+/// An IPv6 address, compared as its 128-bit value.
+///
+/// Big endian is network byte order, little endian is host byte order on x86.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd)]
+pub struct Ipv6(Ipv6Addr);
+
+/// An address that can be masked by a CIDR prefix
+pub trait IpAddress: Copy + PartialOrd + FromStr {
+    /// Size of the address in bits
+    const BITS: u32;
+
+    /// The address with the host bits, the bits after `prefix`, all zero or all one
+    #[must_use]
+    fn mask(self, prefix: u32, ones: bool) -> Self;
+}
+
+impl IpAddress for Ipv4 {
+    const BITS: u32 = u32::BITS;
+
+    fn mask(self, prefix: u32, ones: bool) -> Self {
+        let host = u32::MAX.checked_shr(prefix).unwrap_or(0);
+        let value = u32::from(self.0);
+        let masked = if ones { value | host } else { value & !host };
+
+        return Ipv4(Ipv4Addr::from(masked));
+    }
+}
+
+impl IpAddress for Ipv6 {
+    const BITS: u32 = u128::BITS;
+
+    fn mask(self, prefix: u32, ones: bool) -> Self {
+        let host = u128::MAX.checked_shr(prefix).unwrap_or(0);
+        let value = u128::from(self.0);
+        let masked = if ones { value | host } else { value & !host };
+
+        return Ipv6(Ipv6Addr::from(masked));
+    }
+}
+
+/// An inclusive range of addresses, parsed from a CIDR (`192.168.1.0/24`),
+/// a dotted range (`192.168.1.0-192.168.3.255`) or a single address
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IpRange<A> {
+    first: A,
+    last: A,
+}
+
+impl<A: IpAddress> IpRange<A> {
+    #[must_use]
+    pub fn first(&self) -> A {
+        return self.first;
+    }
+
+    #[must_use]
+    pub fn last(&self) -> A {
+        return self.last;
+    }
+}
+
+impl<A: IpAddress> FromStr for IpRange<A> {
+    type Err = IpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let address = |text: &str| {
+            return A::from_str(text.trim()).map_err(|_| IpError::InvalidAddress(text.to_string()));
+        };
+
+        if let Some((network, prefix)) = s.split_once('/') {
+            let prefix: u32 = prefix
+                .trim()
+                .parse()
+                .ok()
+                .filter(|prefix| *prefix <= A::BITS)
+                .ok_or_else(|| IpError::InvalidPrefix(prefix.to_string()))?;
+            let network = address(network)?;
+
+            return Ok(IpRange {
+                first: network.mask(prefix, false),
+                last: network.mask(prefix, true),
+            });
+        }
+
+        if let Some((first, last)) = s.split_once('-') {
+            let range = IpRange {
+                first: address(first)?,
+                last: address(last)?,
+            };
+            if range.last < range.first {
+                return Err(IpError::EmptyRange(s.to_string()));
+            }
+
+            return Ok(range);
+        }
+
+        let single = address(s)?;
+        return Ok(IpRange {
+            first: single,
+            last: single,
+        });
+    }
+}
+
+impl BitType for Ipv4 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Unsigned;
+    const NAME: &'static str = "ipv4";
+}
+
+impl BitType for Ipv6 {
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Unsigned;
+    const NAME: &'static str = "ipv6";
+}
+
+impl Compare for Ipv4 {
+    type Set = HashLiteralSet<Self>;
+
+    #[inline]
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
+        return self == other;
+    }
+}
+
+impl Compare for Ipv6 {
+    type Set = HashLiteralSet<Self>;
+
+    #[inline]
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
+        return self == other;
+    }
+}
+
+impl FromStr for Ipv4 {
+    type Err = IpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return s
+            .trim()
+            .parse()
+            .map(Ipv4)
+            .map_err(|_| IpError::InvalidAddress(s.to_string()));
+    }
+}
+
+impl FromStr for Ipv6 {
+    type Err = IpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return s
+            .trim()
+            .parse()
+            .map(Ipv6)
+            .map_err(|_| IpError::InvalidAddress(s.to_string()));
+    }
+}
+
+impl Display for Ipv4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.0);
+    }
+}
+
+/// Printed in the RFC 5952 notation, e.g. `2001:db8::1`
+impl Display for Ipv6 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.0);
+    }
+}
+
+/// Adds values, only required by [`num::Zero`]
+impl Add for Ipv4 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let value = u32::from(self.0).wrapping_add(u32::from(rhs.0));
+        return Ipv4(Ipv4Addr::from(value));
+    }
+}
+
+/// Adds values, only required by [`num::Zero`]
+impl Add for Ipv6 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let value = u128::from(self.0).wrapping_add(u128::from(rhs.0));
+        return Ipv6(Ipv6Addr::from(value));
+    }
+}
+
+impl num::Zero for Ipv4 {
+    fn zero() -> Self {
+        return Ipv4(Ipv4Addr::UNSPECIFIED);
+    }
+
+    fn is_zero(&self) -> bool {
+        return self.0.is_unspecified();
+    }
+}
+
+impl num::Zero for Ipv6 {
+    fn zero() -> Self {
+        return Ipv6(Ipv6Addr::UNSPECIFIED);
+    }
+
+    fn is_zero(&self) -> bool {
+        return self.0.is_unspecified();
+    }
+}
+
+/// Host byte order on x86
+impl FromLittleEndian for Ipv4 {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        return Ipv4(Ipv4Addr::from(<u32 as FromLittleEndian>::from_bytes(bytes)));
+    }
+}
+
+/// Network byte order
+impl FromBigEndian for Ipv4 {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        return Ipv4(Ipv4Addr::from(<u32 as FromBigEndian>::from_bytes(bytes)));
+    }
+}
+
+impl ToLittleEndian for Ipv4 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return u32::from(self.0).to_le_bytes().to_vec();
+    }
+}
+
+/// Host byte order on x86
+impl FromLittleEndian for Ipv6 {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        return Ipv6(Ipv6Addr::from(<u128 as FromLittleEndian>::from_bytes(
+            bytes,
+        )));
+    }
+}
+
+/// Network byte order
+impl FromBigEndian for Ipv6 {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        return Ipv6(Ipv6Addr::from(<u128 as FromBigEndian>::from_bytes(bytes)));
+    }
+}
+
+impl ToLittleEndian for Ipv6 {
+    fn to_bytes(&self) -> Vec<u8> {
+        return u128::from(self.0).to_le_bytes().to_vec();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ipv4(text: &str) -> Ipv4 {
+        return text.parse().unwrap();
+    }
+
+    fn ipv6(text: &str) -> Ipv6 {
+        return text.parse().unwrap();
+    }
+
+    #[test]
+    fn byte_orders() {
+        let network = [192, 168, 1, 10];
+        assert_eq!(
+            <Ipv4 as FromBigEndian>::from_bytes(&network),
+            ipv4("192.168.1.10")
+        );
+        assert_eq!(
+            <Ipv4 as FromLittleEndian>::from_bytes(&network),
+            ipv4("10.1.168.192")
+        );
+        assert_eq!(ipv4("192.168.1.10").to_bytes(), vec![10, 1, 168, 192]);
+
+        let mut network = [0u8; 16];
+        network[..2].copy_from_slice(&[0x20, 0x01]);
+        network[15] = 1;
+        assert_eq!(
+            <Ipv6 as FromBigEndian>::from_bytes(&network),
+            ipv6("2001::1")
+        );
+    }
+
+    #[test]
+    fn display_standard_notation() {
+        assert_eq!(ipv4(" 10.0.0.1 ").to_string(), "10.0.0.1");
+        assert_eq!(
+            ipv6("2001:0db8:0000:0000:0000:0000:0000:0001").to_string(),
+            "2001:db8::1"
+        );
+        assert_eq!(
+            "300.0.0.1".parse::<Ipv4>(),
+            Err(IpError::InvalidAddress("300.0.0.1".into()))
+        );
+    }
+
+    #[test]
+    fn parse_cidr() {
+        let range: IpRange<Ipv4> = "192.168.1.77/24".parse().unwrap();
+        assert_eq!(range.first(), ipv4("192.168.1.0"));
+        assert_eq!(range.last(), ipv4("192.168.1.255"));
+
+        let all: IpRange<Ipv4> = "1.2.3.4/0".parse().unwrap();
+        assert_eq!(all.first(), ipv4("0.0.0.0"));
+        assert_eq!(all.last(), ipv4("255.255.255.255"));
+
+        let range: IpRange<Ipv6> = "2001:db8::/32".parse().unwrap();
+        assert_eq!(range.first(), ipv6("2001:db8::"));
+        assert_eq!(range.last(), ipv6("2001:db8:ffff:ffff:ffff:ffff:ffff:ffff"));
+
+        assert_eq!(
+            "10.0.0.0/33".parse::<IpRange<Ipv4>>(),
+            Err(IpError::InvalidPrefix("33".into()))
+        );
+    }
+
+    #[test]
+    fn parse_dotted_range() {
+        let range: IpRange<Ipv4> = "192.168.1.0 - 192.168.3.255".parse().unwrap();
+        assert_eq!(range.first(), ipv4("192.168.1.0"));
+        assert_eq!(range.last(), ipv4("192.168.3.255"));
+
+        let single: IpRange<Ipv4> = "10.0.0.1".parse().unwrap();
+        assert_eq!(single.first(), single.last());
+
+        assert_eq!(
+            "10.0.0.2-10.0.0.1".parse::<IpRange<Ipv4>>(),
+            Err(IpError::EmptyRange("10.0.0.2-10.0.0.1".into()))
+        );
+    }
+}
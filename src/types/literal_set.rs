@@ -0,0 +1,165 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use super::bit_type::Float;
+use super::compare::{Compare, Tolerance};
+
+/// A set of literals to match values against.
+///
+/// [`LiteralSet::contains`] is the same as calling [`Compare::equal`]
+/// with every literal, without going over all of them.
+pub trait LiteralSet<T>: Send + Sync {
+    fn new(literals: &[T], tolerance: Tolerance) -> Self;
+    fn contains(&self, value: &T) -> bool;
+}
+
+/// Integers are compared exactly, so literals are simply hashed
+pub struct HashLiteralSet<T> {
+    literals: HashSet<T>,
+}
+
+impl<T> LiteralSet<T> for HashLiteralSet<T>
+where
+    T: Hash + Eq + Copy + Send + Sync,
+{
+    fn new(literals: &[T], _tolerance: Tolerance) -> Self {
+        return HashLiteralSet {
+            literals: literals.iter().copied().collect(),
+        };
+    }
+
+    #[inline]
+    fn contains(&self, value: &T) -> bool {
+        return self.literals.contains(value);
+    }
+}
+
+/// Floats are compared approximately, so can't be hashed.
+///
+/// Literals are sorted, the farther a literal is from a value the less
+/// likely it is to be approximately equal, so only the closest literal on
+/// each side of the value has to be compared.
+pub struct SortedLiteralSet<T> {
+    sorted: Vec<T>,
+    tolerance: Tolerance,
+    // NaN is equal to NaN but can't be sorted
+    has_nan: bool,
+}
+
+impl<T: Float + Compare> LiteralSet<T> for SortedLiteralSet<T> {
+    fn new(literals: &[T], tolerance: Tolerance) -> Self {
+        let mut sorted: Vec<T> = literals.iter().copied().filter(|x| !x.is_nan()).collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).expect("NaN values are filtered"));
+        sorted.dedup();
+
+        return SortedLiteralSet {
+            sorted,
+            tolerance,
+            has_nan: literals.iter().any(|x| x.is_nan()),
+        };
+    }
+
+    #[inline]
+    fn contains(&self, value: &T) -> bool {
+        if value.is_nan() {
+            return self.has_nan;
+        }
+
+        let index = self.sorted.partition_point(|literal| literal < value);
+
+        let above = self.sorted.get(index);
+        let below = index.checked_sub(1).and_then(|i| self.sorted.get(i));
+
+        return [above, below]
+            .into_iter()
+            .flatten()
+            .any(|literal| value.equal(literal, &self.tolerance));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_set_contains_exact() {
+        let set = HashLiteralSet::new(&[3i32, -7, 1000], Tolerance::default());
+
+        assert!(set.contains(&3));
+        assert!(set.contains(&-7));
+        assert!(set.contains(&1000));
+        assert!(!set.contains(&4));
+    }
+
+    #[test]
+    fn sorted_set_contains_approximate() {
+        let set = SortedLiteralSet::new(&[33.5f64, 2.71, -1.0, 3.14, 2.71], Tolerance::default());
+
+        assert!(set.contains(&2.71));
+        assert!(set.contains(&(3.14 + f64::EPSILON)));
+        assert!(set.contains(&(-1.0 - f64::EPSILON)));
+        assert!(set.contains(&33.5));
+        assert!(!set.contains(&3.15));
+        assert!(!set.contains(&-2.71));
+        assert!(!set.contains(&100.0));
+        assert!(!set.contains(&-100.0));
+    }
+
+    #[test]
+    fn sorted_set_special_values() {
+        let set = SortedLiteralSet::new(&[f32::NAN, f32::NEG_INFINITY, -0.0], Tolerance::default());
+
+        assert!(set.contains(&f32::NAN));
+        assert!(set.contains(&f32::NEG_INFINITY));
+        assert!(set.contains(&0.0));
+        assert!(!set.contains(&f32::INFINITY));
+        assert!(!set.contains(&-1.0));
+    }
+
+    #[test]
+    fn sorted_set_same_as_equal() {
+        let literals = [0.5f64, 1.0, 1.0 + f64::EPSILON * 3.0, 7.25, 1e-300, -1e-300];
+        let set = SortedLiteralSet::new(&literals, Tolerance::default());
+
+        let mut value = 1.0 - f64::EPSILON * 8.0;
+        for _ in 0..32 {
+            let expected = literals
+                .iter()
+                .any(|literal| value.equal(literal, &Tolerance::default()));
+            assert_eq!(set.contains(&value), expected, "value {value}");
+            value += f64::EPSILON / 2.0;
+        }
+
+        for value in [0.0, -0.0, 1e-310, 0.5, 0.25, 7.0, 7.25, f64::MAX] {
+            let expected = literals
+                .iter()
+                .any(|literal| value.equal(literal, &Tolerance::default()));
+            assert_eq!(set.contains(&value), expected, "value {value}");
+        }
+    }
+
+    #[test]
+    fn sorted_set_same_as_equal_with_tolerance() {
+        let tolerance = Tolerance {
+            max_ulps: 0,
+            abs_epsilon: Some(0.0),
+            rel_epsilon: Some(0.05),
+        };
+        let literals = [-10.0f32, -1.0, 1.0, 1.5, 10.0, 11.0];
+        let set = SortedLiteralSet::new(&literals, tolerance);
+
+        for step in -240i16..=240 {
+            let value = f32::from(step) / 20.0;
+            let expected = literals
+                .iter()
+                .any(|literal| value.equal(literal, &tolerance));
+            assert_eq!(set.contains(&value), expected, "value {value}");
+        }
+    }
+
+    #[test]
+    fn empty_sets_contain_nothing() {
+        assert!(!HashLiteralSet::<u8>::new(&[], Tolerance::default()).contains(&0));
+        assert!(!SortedLiteralSet::<f64>::new(&[], Tolerance::default()).contains(&0.0));
+    }
+}
@@ -1,3 +1,20 @@
+pub mod apple_time;
 pub mod bit_type;
 pub mod compare;
+pub mod datetime;
+pub mod dos_time;
 pub mod endian;
+pub mod gps_time;
+pub mod guid;
+pub mod hex_match;
+pub mod ip;
+pub mod literal_set;
+pub mod regex_match;
+pub mod rule_match;
+pub mod scan_value;
+pub mod string_match;
+pub mod struct_match;
+pub mod text_number;
+pub mod timestamp;
+pub mod timestamp_match;
+pub mod windows_time;
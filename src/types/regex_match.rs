@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+use super::scan_value::ScanValue;
+use super::string_match::StringEncoding;
+
+/// Text matched by a regex, with the encoding the data was decoded from
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegexMatch {
+    text: String,
+    encoding: StringEncoding,
+    /// Size of the matched data in bytes
+    size: usize,
+}
+
+impl RegexMatch {
+    /// Matched raw bytes, displayed as ASCII with `.` for unprintable bytes
+    #[must_use]
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let text = bytes
+            .iter()
+            .map(|byte| {
+                if byte.is_ascii_graphic() || *byte == b' ' {
+                    char::from(*byte)
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+
+        return RegexMatch {
+            text,
+            encoding: StringEncoding::Utf8,
+            size: bytes.len(),
+        };
+    }
+
+    /// Matched decoded text, `size` is the size of the encoded text.
+    /// Control characters are displayed as `.`
+    #[must_use]
+    pub fn from_text(text: &str, encoding: StringEncoding, size: usize) -> Self {
+        let text = text
+            .chars()
+            .map(|c| if c.is_control() { '.' } else { c })
+            .collect();
+
+        return RegexMatch {
+            text,
+            encoding,
+            size,
+        };
+    }
+}
+
+impl ScanValue for RegexMatch {
+    fn type_name(&self) -> &str {
+        return match self.encoding {
+            StringEncoding::Utf8 => "regex",
+            StringEncoding::Utf16Le => "regex-utf16le",
+            StringEncoding::Utf16Be => "regex-utf16be",
+        };
+    }
+
+    fn size(&self) -> Option<usize> {
+        return Some(self.size);
+    }
+}
+
+impl Display for RegexMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str(&self.text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_escapes() {
+        let bytes = RegexMatch::from_bytes(b"ab\x00\xFF c");
+        assert_eq!(bytes.to_string(), "ab.. c");
+        assert_eq!(bytes.type_name(), "regex");
+
+        let text = RegexMatch::from_text("é\n1", StringEncoding::Utf16Le, 6);
+        assert_eq!(text.to_string(), "é.1");
+        assert_eq!(text.type_name(), "regex-utf16le");
+        assert_eq!(text.size(), Some(6));
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::Display;
+
+use super::scan_value::ScanValue;
+use crate::common::Endianness;
+
+/// A value matched by a rule of a rules file, kept as its printed text
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleMatch {
+    rule: String,
+    type_name: String,
+    value: String,
+    /// Size of the matched data in bytes
+    size: usize,
+    /// Endianness of the rule, None for text and bytes
+    endianness: Option<Endianness>,
+}
+
+impl RuleMatch {
+    #[must_use]
+    pub fn new(
+        rule: &str,
+        type_name: &str,
+        value: String,
+        size: usize,
+        endianness: Option<Endianness>,
+    ) -> Self {
+        return RuleMatch {
+            rule: rule.to_string(),
+            type_name: type_name.to_string(),
+            value,
+            size,
+            endianness,
+        };
+    }
+}
+
+impl ScanValue for RuleMatch {
+    fn type_name(&self) -> &str {
+        return &self.type_name;
+    }
+
+    fn size(&self) -> Option<usize> {
+        return Some(self.size);
+    }
+
+    fn endianness(&self) -> Option<Endianness> {
+        return self.endianness;
+    }
+
+    fn rule(&self) -> Option<&str> {
+        return Some(&self.rule);
+    }
+}
+
+impl Display for RuleMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str(&self.value);
+    }
+}
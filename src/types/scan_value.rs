@@ -0,0 +1,31 @@
+use std::fmt::Display;
+
+use super::bit_type::BitType;
+use crate::common::Endianness;
+
+/// A value found by the [`crate::scanner::Scanner`]
+pub trait ScanValue: Display + Clone + Send {
+    /// Name of the value's type in the output
+    fn type_name(&self) -> &str;
+
+    /// Size in bytes, None if it's the whole chunk read by the processor
+    fn size(&self) -> Option<usize> {
+        return None;
+    }
+
+    /// Endianness the value was read in, None if it's not known by the value
+    fn endianness(&self) -> Option<Endianness> {
+        return None;
+    }
+
+    /// Name of the rule that matched the value, see [`crate::rules::Rules`]
+    fn rule(&self) -> Option<&str> {
+        return None;
+    }
+}
+
+impl<T: BitType> ScanValue for T {
+    fn type_name(&self) -> &str {
+        return T::NAME;
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::Display;
+
+use super::scan_value::ScanValue;
+
+/// Text encodings searched by a string search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StringEncoding {
+    #[value(name = "utf8")]
+    Utf8,
+    #[value(name = "utf16le")]
+    Utf16Le,
+    #[value(name = "utf16be")]
+    Utf16Be,
+}
+
+impl StringEncoding {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        return match self {
+            StringEncoding::Utf8 => "utf8",
+            StringEncoding::Utf16Le => "utf16le",
+            StringEncoding::Utf16Be => "utf16be",
+        };
+    }
+
+    #[must_use]
+    pub fn encode(self, text: &str) -> Vec<u8> {
+        return match self {
+            StringEncoding::Utf8 => text.as_bytes().to_vec(),
+            StringEncoding::Utf16Le => text.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+            StringEncoding::Utf16Be => text.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+        };
+    }
+
+    /// Decodes the character at the start of `bytes`, returns it with its size in bytes.
+    /// None if `bytes` doesn't start with a valid character.
+    #[must_use]
+    pub fn decode_char(self, bytes: &[u8]) -> Option<(char, usize)> {
+        if self == StringEncoding::Utf8 {
+            let size = match bytes.first()? {
+                0x00..=0x7F => 1,
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                0xF0..=0xF7 => 4,
+                _ => return None,
+            };
+
+            let text = std::str::from_utf8(bytes.get(..size)?).ok()?;
+            return text.chars().next().map(|c| (c, size));
+        }
+
+        let first = self.utf16_unit(bytes, 0)?;
+        if let Some(Ok(c)) = char::decode_utf16([first]).next() {
+            return Some((c, 2));
+        }
+
+        // A surrogate pair
+        let second = self.utf16_unit(bytes, 2)?;
+        let c = char::decode_utf16([first, second]).next()?.ok()?;
+        return Some((c, 4));
+    }
+
+    fn utf16_unit(self, bytes: &[u8], index: usize) -> Option<u16> {
+        let unit: [u8; 2] = bytes.get(index..index + 2)?.try_into().ok()?;
+        return match self {
+            StringEncoding::Utf16Be => Some(u16::from_be_bytes(unit)),
+            _ => Some(u16::from_le_bytes(unit)),
+        };
+    }
+}
+
+/// Text matched by a string search, with the encoding it was found in
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringMatch {
+    text: String,
+    encoding: StringEncoding,
+    /// Size of the encoded text in bytes
+    size: usize,
+}
+
+impl StringMatch {
+    #[must_use]
+    pub fn new(text: String, encoding: StringEncoding, size: usize) -> Self {
+        return StringMatch {
+            text,
+            encoding,
+            size,
+        };
+    }
+
+    #[must_use]
+    pub fn encoding(&self) -> StringEncoding {
+        return self.encoding;
+    }
+}
+
+impl ScanValue for StringMatch {
+    fn type_name(&self) -> &str {
+        return self.encoding.name();
+    }
+
+    fn size(&self) -> Option<usize> {
+        return Some(self.size);
+    }
+}
+
+impl Display for StringMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str(&self.text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode() {
+        for encoding in [
+            StringEncoding::Utf8,
+            StringEncoding::Utf16Le,
+            StringEncoding::Utf16Be,
+        ] {
+            let bytes = encoding.encode("aé€😀");
+            let mut decoded = String::new();
+            let mut index = 0;
+            while let Some((c, size)) = encoding.decode_char(&bytes[index..]) {
+                decoded.push(c);
+                index += size;
+            }
+
+            assert_eq!(decoded, "aé€😀");
+            assert_eq!(index, bytes.len());
+        }
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(StringEncoding::Utf8.decode_char(&[0xC3]), None);
+        assert_eq!(StringEncoding::Utf8.decode_char(&[0x80, 0x41]), None);
+        // Unpaired surrogates
+        assert_eq!(StringEncoding::Utf16Le.decode_char(&[0x3D, 0xD8]), None);
+        assert_eq!(
+            StringEncoding::Utf16Be.decode_char(&[0xDE, 0x00, 0x00, 0x41]),
+            None
+        );
+        assert_eq!(
+            StringEncoding::Utf16Be.decode_char(&[0x00, 0x41]),
+            Some(('A', 2))
+        );
+    }
+}
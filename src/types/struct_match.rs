@@ -0,0 +1,74 @@
+use std::fmt::Display;
+
+use super::scan_value::ScanValue;
+use crate::common::Endianness;
+
+/// A record matched by a struct layout, the fields are kept as their printed values
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructMatch {
+    /// Names and values of the fields in the layout's order
+    fields: Vec<(String, String)>,
+    /// Size of the record in bytes
+    size: usize,
+    /// Endianness of every field, None if fields are read in different endiannesses
+    endianness: Option<Endianness>,
+}
+
+impl StructMatch {
+    #[must_use]
+    pub fn new(fields: Vec<(String, String)>, size: usize, endianness: Option<Endianness>) -> Self {
+        return StructMatch {
+            fields,
+            size,
+            endianness,
+        };
+    }
+
+    #[must_use]
+    pub fn fields(&self) -> &[(String, String)] {
+        return &self.fields;
+    }
+}
+
+impl ScanValue for StructMatch {
+    fn type_name(&self) -> &str {
+        return "struct";
+    }
+
+    fn size(&self) -> Option<usize> {
+        return Some(self.size);
+    }
+
+    fn endianness(&self) -> Option<Endianness> {
+        return self.endianness;
+    }
+}
+
+impl Display for StructMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"))
+            .collect();
+
+        return write!(f, "{{{}}}", fields.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_fields() {
+        let record = StructMatch::new(
+            vec![("id".into(), "7".into()), ("lat".into(), "40.5".into())],
+            12,
+            None,
+        );
+
+        assert_eq!(record.to_string(), "{id: 7, lat: 40.5}");
+        assert_eq!(record.size(), Some(12));
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use super::bit_type::BitType;
+use super::scan_value::ScanValue;
+use super::string_match::StringEncoding;
+
+/// A number parsed from its text representation, with the encoding of the text
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextNumber<T> {
+    value: T,
+    encoding: StringEncoding,
+    /// Size of the encoded text in bytes
+    size: usize,
+}
+
+impl<T: BitType> TextNumber<T> {
+    #[must_use]
+    pub fn new(value: T, encoding: StringEncoding, size: usize) -> Self {
+        return TextNumber {
+            value,
+            encoding,
+            size,
+        };
+    }
+
+    #[must_use]
+    pub fn value(&self) -> T {
+        return self.value;
+    }
+}
+
+impl<T: BitType> ScanValue for TextNumber<T> {
+    fn type_name(&self) -> &str {
+        return T::NAME;
+    }
+
+    fn size(&self) -> Option<usize> {
+        return Some(self.size);
+    }
+}
+
+impl<T: BitType> Display for TextNumber<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{} ({})", self.value, self.encoding.name());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_encoding() {
+        let number = TextNumber::new(10.5f64, StringEncoding::Utf16Le, 8);
+
+        assert_eq!(number.to_string(), "10.5 (utf16le)");
+        assert_eq!(number.type_name(), "f64");
+        assert_eq!(number.size(), Some(8));
+    }
+}
@@ -0,0 +1,380 @@
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::ops::Add;
+use std::str::FromStr;
+
+use super::bit_type::{BitType, ByteOrdering};
+use super::compare::{Compare, Tolerance};
+use super::datetime::{
+    format_iso8601, format_local_iso8601, parse_iso8601, DateTimeError, NANOS_PER_MICRO,
+    NANOS_PER_MILLI, NANOS_PER_SECOND,
+};
+use super::endian::{FromBigEndian, FromLittleEndian, ToLittleEndian};
+use super::literal_set::HashLiteralSet;
+
+/// How a timestamp type is stored, converts the raw value to and from
+/// nanoseconds since the Unix epoch (1970-01-01T00:00:00Z).
+pub trait Epoch: Copy + Send + Sync + 'static {
+    type Raw: BitType;
+
+    /// Name of the type in the cli and output
+    const NAME: &'static str;
+    /// Digits of a second that are printed
+    const FRACTION_DIGITS: usize;
+    /// Ordering of the most significant byte, if the raw value orders like the time
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Unordered;
+
+    /// Returns None if the raw value is not a valid time
+    fn to_nanos(raw: Self::Raw) -> Option<i128>;
+    /// Returns None if the time can't be represented
+    fn from_nanos(nanos: i128) -> Option<Self::Raw>;
+
+    /// Extra information stored with the time, printed after it
+    fn kind(_raw: Self::Raw) -> Option<&'static str> {
+        return None;
+    }
+
+    /// Whether the time is known to be UTC, other times are printed without the `Z`
+    fn is_utc(_raw: Self::Raw) -> bool {
+        return true;
+    }
+
+    /// Converts big endian bytes, structs override this to swap every field separately
+    #[must_use]
+    fn raw_from_big_endian(bytes: &[u8]) -> Self::Raw {
+        return <Self::Raw as FromBigEndian>::from_bytes(bytes);
+    }
+}
+
+/// A timestamp stored as an [`Epoch`]'s raw value.
+///
+/// Parsed from and displayed as ISO-8601 UTC dates,
+/// and compared by the time it represents.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub struct Timestamp<E: Epoch> {
+    raw: E::Raw,
+}
+
+impl<E: Epoch> Timestamp<E> {
+    #[must_use]
+    pub fn from_raw(raw: E::Raw) -> Self {
+        return Timestamp { raw };
+    }
+
+    #[must_use]
+    pub fn raw(&self) -> E::Raw {
+        return self.raw;
+    }
+
+    #[must_use]
+    pub fn nanos(&self) -> Option<i128> {
+        return E::to_nanos(self.raw);
+    }
+}
+
+impl<E: Epoch> BitType for Timestamp<E> {
+    const BYTE_ORDERING: ByteOrdering = E::BYTE_ORDERING;
+    const NAME: &'static str = E::NAME;
+}
+
+impl<E: Epoch> Compare for Timestamp<E> {
+    type Set = HashLiteralSet<Self>;
+
+    #[inline]
+    fn equal(&self, other: &Self, _tolerance: &Tolerance) -> bool {
+        return self == other;
+    }
+
+    #[inline]
+    fn nanos(&self) -> Option<i128> {
+        return E::to_nanos(self.raw);
+    }
+}
+
+impl<E: Epoch> FromStr for Timestamp<E> {
+    type Err = DateTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let nanos = parse_iso8601(s)?;
+        let raw = E::from_nanos(nanos).ok_or_else(|| DateTimeError::OutOfRange(s.to_string()))?;
+
+        return Ok(Timestamp { raw });
+    }
+}
+
+impl<E: Epoch> Display for Timestamp<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(nanos) = self.nanos() else {
+            return write!(f, "invalid ({})", self.raw);
+        };
+
+        if E::is_utc(self.raw) {
+            f.write_str(&format_iso8601(nanos, E::FRACTION_DIGITS))?;
+        } else {
+            f.write_str(&format_local_iso8601(nanos, E::FRACTION_DIGITS))?;
+        }
+        if let Some(kind) = E::kind(self.raw) {
+            write!(f, " ({kind})")?;
+        }
+
+        return Ok(());
+    }
+}
+
+impl<E: Epoch> Debug for Timestamp<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}({self})", E::NAME);
+    }
+}
+
+impl<E: Epoch> PartialEq for Timestamp<E> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.nanos() == other.nanos();
+    }
+}
+
+impl<E: Epoch> Eq for Timestamp<E> {}
+
+impl<E: Epoch> Hash for Timestamp<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.nanos().hash(state);
+    }
+}
+
+/// Invalid timestamps can't be ordered, so are excluded by range filters
+impl<E: Epoch> PartialOrd for Timestamp<E> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        return self.nanos()?.partial_cmp(&other.nanos()?);
+    }
+}
+
+/// Adds raw values, only required by [`num::Zero`]
+impl<E: Epoch> Add for Timestamp<E> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        return Timestamp {
+            raw: self.raw + rhs.raw,
+        };
+    }
+}
+
+impl<E: Epoch> num::Zero for Timestamp<E> {
+    fn zero() -> Self {
+        return Timestamp {
+            raw: E::Raw::zero(),
+        };
+    }
+
+    fn is_zero(&self) -> bool {
+        return self.raw.is_zero();
+    }
+}
+
+impl<E: Epoch> FromLittleEndian for Timestamp<E> {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        return Timestamp {
+            raw: <E::Raw as FromLittleEndian>::from_bytes(bytes),
+        };
+    }
+}
+
+impl<E: Epoch> FromBigEndian for Timestamp<E> {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        return Timestamp {
+            raw: E::raw_from_big_endian(bytes),
+        };
+    }
+}
+
+impl<E: Epoch> ToLittleEndian for Timestamp<E> {
+    fn to_bytes(&self) -> Vec<u8> {
+        return self.raw.to_bytes();
+    }
+}
+
+#[inline]
+pub(super) fn ticks_to_nanos(ticks: i128, tick_nanos: i128) -> i128 {
+    return ticks * tick_nanos;
+}
+
+/// Rounds down to a whole tick
+#[inline]
+pub(super) fn nanos_to_ticks(nanos: i128, tick_nanos: i128) -> i128 {
+    return nanos.div_euclid(tick_nanos);
+}
+
+/// 32-bit Unix time (`time_t`) in seconds
+#[derive(Clone, Copy)]
+pub struct Unix32;
+
+impl Epoch for Unix32 {
+    type Raw = i32;
+    const NAME: &'static str = "unix32";
+    const FRACTION_DIGITS: usize = 0;
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Signed;
+
+    fn to_nanos(raw: i32) -> Option<i128> {
+        return Some(ticks_to_nanos(raw.into(), NANOS_PER_SECOND));
+    }
+
+    fn from_nanos(nanos: i128) -> Option<i32> {
+        return nanos_to_ticks(nanos, NANOS_PER_SECOND).try_into().ok();
+    }
+}
+
+/// 64-bit Unix time in seconds
+#[derive(Clone, Copy)]
+pub struct Unix64;
+
+impl Epoch for Unix64 {
+    type Raw = i64;
+    const NAME: &'static str = "unix64";
+    const FRACTION_DIGITS: usize = 0;
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Signed;
+
+    fn to_nanos(raw: i64) -> Option<i128> {
+        return Some(ticks_to_nanos(raw.into(), NANOS_PER_SECOND));
+    }
+
+    fn from_nanos(nanos: i128) -> Option<i64> {
+        return nanos_to_ticks(nanos, NANOS_PER_SECOND).try_into().ok();
+    }
+}
+
+/// 64-bit Unix time in milliseconds (e.g. Java and JavaScript)
+#[derive(Clone, Copy)]
+pub struct Unix64Ms;
+
+impl Epoch for Unix64Ms {
+    type Raw = i64;
+    const NAME: &'static str = "unix64-ms";
+    const FRACTION_DIGITS: usize = 3;
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Signed;
+
+    fn to_nanos(raw: i64) -> Option<i128> {
+        return Some(ticks_to_nanos(raw.into(), NANOS_PER_MILLI));
+    }
+
+    fn from_nanos(nanos: i128) -> Option<i64> {
+        return nanos_to_ticks(nanos, NANOS_PER_MILLI).try_into().ok();
+    }
+}
+
+/// 64-bit Unix time in microseconds (e.g. `PRTime`)
+#[derive(Clone, Copy)]
+pub struct Unix64Us;
+
+impl Epoch for Unix64Us {
+    type Raw = i64;
+    const NAME: &'static str = "unix64-us";
+    const FRACTION_DIGITS: usize = 6;
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Signed;
+
+    fn to_nanos(raw: i64) -> Option<i128> {
+        return Some(ticks_to_nanos(raw.into(), NANOS_PER_MICRO));
+    }
+
+    fn from_nanos(nanos: i128) -> Option<i64> {
+        return nanos_to_ticks(nanos, NANOS_PER_MICRO).try_into().ok();
+    }
+}
+
+/// 64-bit Unix time in nanoseconds
+#[derive(Clone, Copy)]
+pub struct Unix64Ns;
+
+impl Epoch for Unix64Ns {
+    type Raw = i64;
+    const NAME: &'static str = "unix64-ns";
+    const FRACTION_DIGITS: usize = 9;
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Signed;
+
+    fn to_nanos(raw: i64) -> Option<i128> {
+        return Some(raw.into());
+    }
+
+    fn from_nanos(nanos: i128) -> Option<i64> {
+        return nanos.try_into().ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_unix32() {
+        let timestamp: Timestamp<Unix32> = "2021-03-01T00:00:00Z".parse().unwrap();
+
+        assert_eq!(timestamp.raw(), 1_614_556_800);
+    }
+
+    #[test]
+    fn parse_unix64_ms_rounds_down() {
+        let timestamp: Timestamp<Unix64Ms> = "1970-01-01T00:00:01.2345Z".parse().unwrap();
+        let negative: Timestamp<Unix64Ms> = "1969-12-31T23:59:59.9995Z".parse().unwrap();
+
+        assert_eq!(timestamp.raw(), 1234);
+        assert_eq!(negative.raw(), -1);
+    }
+
+    #[test]
+    fn parse_out_of_range() {
+        let result = "2038-01-19T03:14:08Z".parse::<Timestamp<Unix32>>();
+
+        assert_eq!(
+            result.err(),
+            Some(DateTimeError::OutOfRange(
+                "2038-01-19T03:14:08Z".to_string()
+            ))
+        );
+        assert!("2038-01-19T03:14:07Z".parse::<Timestamp<Unix32>>().is_ok());
+    }
+
+    #[test]
+    fn display_utc() {
+        assert_eq!(
+            Timestamp::<Unix64>::from_raw(1_614_556_800).to_string(),
+            "2021-03-01T00:00:00Z"
+        );
+        assert_eq!(
+            Timestamp::<Unix64Ms>::from_raw(1_614_556_800_123).to_string(),
+            "2021-03-01T00:00:00.123Z"
+        );
+        assert_eq!(
+            Timestamp::<Unix64Us>::from_raw(-1).to_string(),
+            "1969-12-31T23:59:59.999999Z"
+        );
+        assert_eq!(
+            Timestamp::<Unix64Ns>::from_raw(1).to_string(),
+            "1970-01-01T00:00:00.000000001Z"
+        );
+    }
+
+    #[test]
+    fn compare_by_time() {
+        let first: Timestamp<Unix64Us> = "2021-03-01".parse().unwrap();
+        let second: Timestamp<Unix64Us> = "2021-03-01T00:00:00.000001".parse().unwrap();
+
+        assert!(first < second);
+        assert!(first.equal(&first, &Tolerance::default()));
+        assert!(!first.equal(&second, &Tolerance::default()));
+    }
+
+    #[test]
+    fn endian_conversion() {
+        let bytes = 1_614_556_800i32.to_be_bytes();
+
+        let timestamp = <Timestamp<Unix32> as FromBigEndian>::from_bytes(&bytes);
+
+        assert_eq!(timestamp.to_string(), "2021-03-01T00:00:00Z");
+        assert_eq!(
+            timestamp.to_bytes(),
+            1_614_556_800i32.to_le_bytes().to_vec()
+        );
+        assert_eq!(size_of::<Timestamp<Unix64Ms>>(), size_of::<i64>());
+    }
+}
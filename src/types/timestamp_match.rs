@@ -0,0 +1,47 @@
+use std::fmt::Display;
+
+use super::scan_value::ScanValue;
+use crate::common::Endianness;
+
+/// A timestamp found by searching every encoding, kept as its printed text
+/// with the name of the encoding it matched (e.g. `unix32-le`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimestampMatch {
+    encoding: String,
+    value: String,
+    /// Size of the encoding in bytes
+    size: usize,
+    endianness: Endianness,
+}
+
+impl TimestampMatch {
+    #[must_use]
+    pub fn new(encoding: &str, value: String, size: usize, endianness: Endianness) -> Self {
+        return TimestampMatch {
+            encoding: encoding.to_string(),
+            value,
+            size,
+            endianness,
+        };
+    }
+}
+
+impl ScanValue for TimestampMatch {
+    fn type_name(&self) -> &str {
+        return &self.encoding;
+    }
+
+    fn size(&self) -> Option<usize> {
+        return Some(self.size);
+    }
+
+    fn endianness(&self) -> Option<Endianness> {
+        return Some(self.endianness);
+    }
+}
+
+impl Display for TimestampMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str(&self.value);
+    }
+}
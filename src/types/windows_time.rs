@@ -0,0 +1,389 @@
+use super::bit_type::ByteOrdering;
+use super::datetime::{
+    civil_from_days, days_from_civil, days_in_month, NANOS_PER_DAY, NANOS_PER_MILLI,
+    NANOS_PER_SECOND,
+};
+use super::timestamp::{nanos_to_ticks, ticks_to_nanos, Epoch};
+
+/// Windows ticks are 100 nanoseconds
+const TICK_NANOS: i128 = 100;
+
+/// 1601-01-01
+const FILETIME_EPOCH_NANOS: i128 = -11_644_473_600 * NANOS_PER_SECOND;
+/// 0001-01-01
+const CLR_EPOCH_NANOS: i128 = -62_135_596_800 * NANOS_PER_SECOND;
+/// 1899-12-30
+const OLE_EPOCH_NANOS: i128 = -25_569 * NANOS_PER_DAY;
+
+/// Valid OLE automation dates are 0100-01-01 up to 9999-12-31
+const OLE_MIN_DAYS: f64 = -657_435.0;
+const OLE_MAX_DAYS: f64 = 2_958_466.0;
+const OLE_MILLIS_PER_DAY: f64 = 86_400_000.0;
+
+/// The top 2 bits of a `DateTime` are its kind
+const CLR_KIND_SHIFT: u32 = 62;
+const CLR_TICKS_MASK: u64 = (1 << CLR_KIND_SHIFT) - 1;
+/// `DateTime.MaxValue.Ticks`, 9999-12-31T23:59:59.9999999
+const CLR_MAX_TICKS: u64 = 3_155_378_975_999_999_999;
+const CLR_KIND_UTC: u64 = 1;
+
+/// `SYSTEMTIME` supports the years 1601 to 30827
+const SYSTEMTIME_YEARS: std::ops::RangeInclusive<u16> = 1601..=30827;
+const SYSTEMTIME_FIELDS: usize = 8;
+
+/// `FILETIME`, 64-bit count of 100 nanosecond ticks since 1601-01-01
+#[derive(Clone, Copy)]
+pub struct Filetime;
+
+impl Epoch for Filetime {
+    type Raw = u64;
+    const NAME: &'static str = "filetime";
+    const FRACTION_DIGITS: usize = 7;
+    const BYTE_ORDERING: ByteOrdering = ByteOrdering::Unsigned;
+
+    fn to_nanos(raw: u64) -> Option<i128> {
+        // Windows rejects times with the top bit set
+        if i64::try_from(raw).is_err() {
+            return None;
+        }
+
+        return Some(ticks_to_nanos(raw.into(), TICK_NANOS) + FILETIME_EPOCH_NANOS);
+    }
+
+    fn from_nanos(nanos: i128) -> Option<u64> {
+        let ticks = nanos_to_ticks(nanos - FILETIME_EPOCH_NANOS, TICK_NANOS);
+        return i64::try_from(ticks).ok()?.try_into().ok();
+    }
+}
+
+/// `SYSTEMTIME` struct, 8 `u16` fields: year, month, day of week, day,
+/// hour, minute, second and milliseconds.
+///
+/// Stored as a `u128` with the first field in the lowest bits (i.e. as little endian).
+/// The day of week is not validated, as many writers leave it empty.
+#[derive(Clone, Copy)]
+pub struct SystemTime;
+
+impl SystemTime {
+    #[allow(clippy::cast_possible_truncation)]
+    fn fields(raw: u128) -> [u16; SYSTEMTIME_FIELDS] {
+        return std::array::from_fn(|i| (raw >> (16 * i)) as u16);
+    }
+
+    fn pack(fields: [u16; SYSTEMTIME_FIELDS]) -> u128 {
+        return fields
+            .iter()
+            .enumerate()
+            .fold(0, |raw, (i, field)| raw | u128::from(*field) << (16 * i));
+    }
+}
+
+impl Epoch for SystemTime {
+    type Raw = u128;
+    const NAME: &'static str = "systemtime";
+    const FRACTION_DIGITS: usize = 3;
+
+    fn to_nanos(raw: u128) -> Option<i128> {
+        let [year, month, day_of_week, day, hour, minute, second, millis] = Self::fields(raw);
+
+        let valid = SYSTEMTIME_YEARS.contains(&year)
+            && (1..=12).contains(&month)
+            && day_of_week <= 6
+            && (1..=days_in_month(year.into(), month.into())).contains(&u32::from(day))
+            && hour <= 23
+            && minute <= 59
+            && second <= 59
+            && millis <= 999;
+
+        if !valid {
+            return None;
+        }
+
+        let seconds = i128::from(hour) * 3600 + i128::from(minute) * 60 + i128::from(second);
+        return Some(
+            days_from_civil(year.into(), month.into(), day.into()) * NANOS_PER_DAY
+                + seconds * NANOS_PER_SECOND
+                + i128::from(millis) * NANOS_PER_MILLI,
+        );
+    }
+
+    fn from_nanos(nanos: i128) -> Option<u128> {
+        let days = nanos.div_euclid(NANOS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        let year = u16::try_from(year)
+            .ok()
+            .filter(|y| SYSTEMTIME_YEARS.contains(y))?;
+
+        let time = nanos.rem_euclid(NANOS_PER_DAY);
+        let seconds = time / NANOS_PER_SECOND;
+        // 1970-01-01 was a Thursday, Sunday is 0
+        let day_of_week = (days + 4).rem_euclid(7);
+
+        let fields = [
+            i128::from(year),
+            month.into(),
+            day_of_week,
+            day.into(),
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60,
+            time % NANOS_PER_SECOND / NANOS_PER_MILLI,
+        ]
+        .map(|field| u16::try_from(field).expect("fields are in range"));
+
+        return Some(Self::pack(fields));
+    }
+
+    fn raw_from_big_endian(bytes: &[u8]) -> u128 {
+        let fields = std::array::from_fn(|i| u16::from_be_bytes([bytes[2 * i], bytes[2 * i + 1]]));
+        return Self::pack(fields);
+    }
+}
+
+/// OLE automation date (`VT_DATE`), a double of days since 1899-12-30.
+///
+/// The fraction is the time of day even for negative dates, so -1.25 is 1899-12-29 06:00.
+/// Times are rounded to milliseconds.
+#[derive(Clone, Copy)]
+pub struct OleDate;
+
+impl Epoch for OleDate {
+    type Raw = f64;
+    const NAME: &'static str = "ole-date";
+    const FRACTION_DIGITS: usize = 3;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn to_nanos(raw: f64) -> Option<i128> {
+        if !(raw > OLE_MIN_DAYS && raw < OLE_MAX_DAYS) {
+            return None;
+        }
+
+        let days = raw.trunc();
+        let millis = ((raw - days).abs() * OLE_MILLIS_PER_DAY).round();
+
+        return Some(
+            OLE_EPOCH_NANOS + (days as i128) * NANOS_PER_DAY + (millis as i128) * NANOS_PER_MILLI,
+        );
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn from_nanos(nanos: i128) -> Option<f64> {
+        let relative = nanos - OLE_EPOCH_NANOS;
+        let days = relative.div_euclid(NANOS_PER_DAY) as f64;
+        let time = relative.rem_euclid(NANOS_PER_DAY) as f64 / NANOS_PER_DAY as f64;
+
+        let raw = if days < 0.0 { days - time } else { days + time };
+        return (raw > OLE_MIN_DAYS && raw < OLE_MAX_DAYS).then_some(raw);
+    }
+}
+
+/// .NET `DateTime` ticks, 100 nanoseconds since 0001-01-01.
+///
+/// The top 2 bits are the `DateTimeKind` (unspecified, utc or local), local and
+/// unspecified times can't be converted to UTC so are printed as is, without a `Z`.
+#[derive(Clone, Copy)]
+pub struct DotnetTicks;
+
+impl Epoch for DotnetTicks {
+    type Raw = u64;
+    const NAME: &'static str = "dotnet-ticks";
+    const FRACTION_DIGITS: usize = 7;
+
+    fn to_nanos(raw: u64) -> Option<i128> {
+        let ticks = raw & CLR_TICKS_MASK;
+        if ticks > CLR_MAX_TICKS {
+            return None;
+        }
+
+        return Some(ticks_to_nanos(ticks.into(), TICK_NANOS) + CLR_EPOCH_NANOS);
+    }
+
+    /// Parsed times are in UTC
+    fn from_nanos(nanos: i128) -> Option<u64> {
+        let ticks = nanos_to_ticks(nanos - CLR_EPOCH_NANOS, TICK_NANOS);
+        let ticks = u64::try_from(ticks).ok().filter(|t| *t <= CLR_MAX_TICKS)?;
+
+        return Some(ticks | CLR_KIND_UTC << CLR_KIND_SHIFT);
+    }
+
+    fn kind(raw: u64) -> Option<&'static str> {
+        return Some(match raw >> CLR_KIND_SHIFT {
+            0 => "unspecified",
+            1 => "utc",
+            _ => "local",
+        });
+    }
+
+    fn is_utc(raw: u64) -> bool {
+        return raw >> CLR_KIND_SHIFT == CLR_KIND_UTC;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::datetime::parse_iso8601;
+    use crate::types::endian::FromBigEndian;
+    use crate::types::timestamp::Timestamp;
+
+    fn date(s: &str) -> i128 {
+        return parse_iso8601(s).unwrap();
+    }
+
+    #[test]
+    fn epochs() {
+        assert_eq!(FILETIME_EPOCH_NANOS, date("1601-01-01"));
+        assert_eq!(CLR_EPOCH_NANOS, date("0001-01-01"));
+        assert_eq!(OLE_EPOCH_NANOS, date("1899-12-30"));
+    }
+
+    #[test]
+    fn filetime() {
+        let timestamp = Timestamp::<Filetime>::from_raw(132_590_304_001_234_567);
+
+        assert_eq!(timestamp.to_string(), "2021-03-01T00:00:00.1234567Z");
+        assert_eq!(
+            "2021-03-01T00:00:00.1234567Z"
+                .parse::<Timestamp<Filetime>>()
+                .unwrap()
+                .raw(),
+            132_590_304_001_234_567
+        );
+        assert_eq!(
+            Timestamp::<Filetime>::from_raw(0).to_string(),
+            "1601-01-01T00:00:00.0000000Z"
+        );
+        assert!(Timestamp::<Filetime>::from_raw(u64::MAX).nanos().is_none());
+        assert!("1600-12-31".parse::<Timestamp<Filetime>>().is_err());
+    }
+
+    #[test]
+    fn systemtime() {
+        // 2021-03-01 (Monday) 13:45:30.250
+        let fields = [2021u16, 3, 1, 1, 13, 45, 30, 250];
+        let bytes: Vec<u8> = fields.iter().flat_map(|f| f.to_le_bytes()).collect();
+        let raw = u128::from_le_bytes(bytes.try_into().unwrap());
+
+        let timestamp = Timestamp::<SystemTime>::from_raw(raw);
+
+        assert_eq!(timestamp.to_string(), "2021-03-01T13:45:30.250Z");
+        assert_eq!(
+            "2021-03-01T13:45:30.25Z"
+                .parse::<Timestamp<SystemTime>>()
+                .unwrap()
+                .raw(),
+            raw
+        );
+    }
+
+    #[test]
+    fn systemtime_big_endian_swaps_fields() {
+        let fields = [2021u16, 3, 1, 1, 13, 45, 30, 250];
+        let bytes: Vec<u8> = fields.iter().flat_map(|f| f.to_be_bytes()).collect();
+
+        let timestamp = <Timestamp<SystemTime> as FromBigEndian>::from_bytes(&bytes);
+
+        assert_eq!(timestamp.to_string(), "2021-03-01T13:45:30.250Z");
+    }
+
+    #[test]
+    fn systemtime_invalid() {
+        let valid = [2021u16, 3, 1, 1, 13, 45, 30, 250];
+        assert!(SystemTime::to_nanos(SystemTime::pack(valid)).is_some());
+
+        for (field, value) in [
+            (0, 1600),
+            (1, 13),
+            (2, 7),
+            (3, 32),
+            (4, 24),
+            (5, 60),
+            (6, 60),
+            (7, 1000),
+        ] {
+            let mut fields = valid;
+            fields[field] = value;
+            assert!(
+                SystemTime::to_nanos(SystemTime::pack(fields)).is_none(),
+                "field {field}"
+            );
+        }
+
+        let february_29 = [2021u16, 2, 0, 29, 0, 0, 0, 0];
+        assert!(SystemTime::to_nanos(SystemTime::pack(february_29)).is_none());
+    }
+
+    #[test]
+    fn ole_date() {
+        assert_eq!(
+            Timestamp::<OleDate>::from_raw(0.0).to_string(),
+            "1899-12-30T00:00:00.000Z"
+        );
+        assert_eq!(
+            Timestamp::<OleDate>::from_raw(44256.5).to_string(),
+            "2021-03-01T12:00:00.000Z"
+        );
+        assert_eq!(
+            Timestamp::<OleDate>::from_raw(-1.25).to_string(),
+            "1899-12-29T06:00:00.000Z"
+        );
+        assert_eq!(
+            "2021-03-01T12:00:00Z"
+                .parse::<Timestamp<OleDate>>()
+                .unwrap()
+                .raw()
+                .to_bits(),
+            44256.5f64.to_bits()
+        );
+        assert_eq!(
+            "1899-12-29T06:00:00Z"
+                .parse::<Timestamp<OleDate>>()
+                .unwrap()
+                .raw()
+                .to_bits(),
+            (-1.25f64).to_bits()
+        );
+    }
+
+    #[test]
+    fn ole_date_invalid() {
+        assert!(Timestamp::<OleDate>::from_raw(f64::NAN).nanos().is_none());
+        assert!(Timestamp::<OleDate>::from_raw(f64::INFINITY)
+            .nanos()
+            .is_none());
+        assert!(Timestamp::<OleDate>::from_raw(1e300).nanos().is_none());
+        assert!(Timestamp::<OleDate>::from_raw(-657_435.0).nanos().is_none());
+        assert!(Timestamp::<OleDate>::from_raw(-657_434.0).nanos().is_some());
+    }
+
+    #[test]
+    fn clr_ticks_kinds() {
+        let ticks = 0x08d8_dc44_f50b_c000u64;
+
+        let unspecified = Timestamp::<DotnetTicks>::from_raw(ticks);
+        let utc = Timestamp::<DotnetTicks>::from_raw(ticks | 1 << 62);
+        let local = Timestamp::<DotnetTicks>::from_raw(ticks | 2 << 62);
+
+        assert_eq!(
+            unspecified.to_string(),
+            "2021-03-01T00:00:00.0000000 (unspecified)"
+        );
+        assert_eq!(utc.to_string(), "2021-03-01T00:00:00.0000000Z (utc)");
+        assert_eq!(local.to_string(), "2021-03-01T00:00:00.0000000 (local)");
+        assert!(unspecified == local);
+    }
+
+    #[test]
+    fn clr_ticks_parse_and_limits() {
+        let timestamp: Timestamp<DotnetTicks> = "2021-03-01".parse().unwrap();
+
+        assert_eq!(timestamp.raw(), 0x08d8_dc44_f50b_c000 | 1 << 62);
+        assert!(Timestamp::<DotnetTicks>::from_raw(CLR_MAX_TICKS + 1)
+            .nanos()
+            .is_none());
+        assert_eq!(
+            Timestamp::<DotnetTicks>::from_raw(CLR_MAX_TICKS).to_string(),
+            "9999-12-31T23:59:59.9999999 (unspecified)"
+        );
+    }
+}
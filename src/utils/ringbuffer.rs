@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 
-/// Ring buffer using a limited size VecDeque
+/// Ring buffer using a limited size `VecDeque`
 /// Could probably do fancy u8 specific optimizations, such as multiple values push/pop
 pub(crate) struct RingBuffer<T> {
     buffer: VecDeque<T>,
@@ -0,0 +1,152 @@
+use std::ops::RangeInclusive;
+
+use super::processors::{ChunkSize, Processor};
+use super::timestamp_decoder::TimestampDecoder;
+use crate::types::timestamp_match::TimestampMatch;
+
+/// Decodes every offset with many timestamp encodings at once, returns the match
+/// of the first encoding whose time is in the range.
+///
+/// Encodings smaller than the largest one are also matched at the end of the data.
+pub struct AnyTimestampProcessor {
+    decoders: Vec<Box<dyn TimestampDecoder>>,
+    /// Nanoseconds since the Unix epoch
+    range: RangeInclusive<i128>,
+}
+
+impl Processor<TimestampMatch> for AnyTimestampProcessor {
+    #[inline]
+    fn consume(&mut self, bytes: &[u8]) -> Option<TimestampMatch> {
+        for decoder in &mut self.decoders {
+            let Some(value_data) = bytes.get(..decoder.size()) else {
+                continue;
+            };
+
+            let in_range = decoder
+                .nanos(value_data)
+                .is_some_and(|nanos| self.range.contains(&nanos));
+            if in_range {
+                let value = decoder.display(value_data);
+                return Some(TimestampMatch::new(
+                    decoder.name(),
+                    value,
+                    decoder.size(),
+                    decoder.endianness(),
+                ));
+            }
+        }
+
+        return None;
+    }
+
+    fn chunk_size(&self) -> ChunkSize {
+        let size = self.decoders.iter().map(|decoder| decoder.size()).max();
+        return ChunkSize::Size(size.unwrap_or(1));
+    }
+
+    fn min_chunk_size(&self) -> ChunkSize {
+        let size = self.decoders.iter().map(|decoder| decoder.size()).min();
+        return ChunkSize::Size(size.unwrap_or(1));
+    }
+}
+
+impl AnyTimestampProcessor {
+    /// Matches times between `min` and `max` (inclusive), in nanoseconds since the Unix epoch
+    #[must_use]
+    pub fn new(
+        decoders: Vec<Box<dyn TimestampDecoder>>,
+        min: Option<i128>,
+        max: Option<i128>,
+    ) -> Self {
+        return AnyTimestampProcessor {
+            decoders,
+            range: min.unwrap_or(i128::MIN)..=max.unwrap_or(i128::MAX),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{DataType, Endianness};
+    use crate::types::datetime::parse_iso8601;
+    use crate::types::scan_value::ScanValue;
+    use crate::workers::timestamp_decoder::{all_decoders, create_decoder};
+
+    fn processor(decoders: Vec<Box<dyn TimestampDecoder>>) -> AnyTimestampProcessor {
+        return AnyTimestampProcessor::new(
+            decoders,
+            parse_iso8601("2021-03-01").ok(),
+            parse_iso8601("2021-03-03").ok(),
+        );
+    }
+
+    /// Consumes every offset like the scanner, returns the offsets and output of matches
+    fn scan(mut processor: AnyTimestampProcessor, data: &[u8]) -> Vec<(usize, String)> {
+        let chunk_size = processor.chunk_size().unwrap();
+        let min_size = processor.min_chunk_size().unwrap();
+
+        return (0..=data.len().saturating_sub(min_size))
+            .filter_map(|index| {
+                let chunk = &data[index..data.len().min(index + chunk_size)];
+                let result = processor.consume(chunk)?;
+                Some((index, format!("{}: {result}", result.type_name())))
+            })
+            .collect();
+    }
+
+    #[test]
+    fn consume_labels_encodings() {
+        let unix32 = 1_614_600_000i32.to_be_bytes();
+        let hfs_plus = 3_697_444_800u32.to_le_bytes();
+        let data = [[0xFFu8; 3].as_slice(), &unix32, &[0xFF; 5], &hfs_plus].concat();
+
+        assert_eq!(
+            scan(processor(all_decoders()), &data),
+            vec![
+                (3, "unix32-be: 2021-03-01T12:00:00Z".into()),
+                (12, "hfs-plus-le: 2021-03-01T12:00:00Z".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn consume_first_encoding_wins() {
+        let decoders = vec![
+            create_decoder(&DataType::Unix64, Endianness::Little).unwrap(),
+            create_decoder(&DataType::Unix32, Endianness::Little).unwrap(),
+        ];
+        let data = [1_614_600_000u64.to_le_bytes().as_slice(), &[0xFF; 4]].concat();
+
+        let mut processor = processor(decoders);
+        let result = processor.consume(&data[..8]).unwrap();
+
+        assert_eq!(
+            result,
+            TimestampMatch::new(
+                "unix64-le",
+                "2021-03-01T12:00:00Z".into(),
+                8,
+                Endianness::Little
+            )
+        );
+    }
+
+    #[test]
+    fn consume_smaller_encodings_at_end() {
+        let data = [[0xFFu8; 4].as_slice(), &1_614_600_000u32.to_le_bytes()].concat();
+
+        assert_eq!(
+            scan(processor(all_decoders()), &data),
+            vec![(4, "unix32-le: 2021-03-01T12:00:00Z".into())]
+        );
+    }
+
+    #[test]
+    fn chunk_sizes() {
+        let processor = processor(all_decoders());
+
+        assert_eq!(processor.chunk_size(), ChunkSize::Size(16));
+        assert_eq!(processor.min_chunk_size(), ChunkSize::Size(4));
+    }
+}
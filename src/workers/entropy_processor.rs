@@ -1,4 +1,4 @@
-use std::{cmp, collections::VecDeque, marker::PhantomData};
+use std::marker::PhantomData;
 
 use crate::utils::ringbuffer::RingBuffer;
 
@@ -28,7 +28,7 @@ pub struct EntropyProcessor<T> {
     phantom: PhantomData<T>, // TODO(danilan): Remove
 }
 
-impl<T> Processor<T> for EntropyProcessor<T> {
+impl<T: Send> Processor<T> for EntropyProcessor<T> {
     fn consume(&mut self, bytes: &[u8]) -> Option<T> {
         self.add_bytes(bytes);
         return None;
@@ -39,11 +39,17 @@ impl<T> Processor<T> for EntropyProcessor<T> {
     }
 }
 
-pub trait EntropyProducer {
+pub trait EntropyProducer: Send {
     fn entropy(&self) -> Option<f64>;
 }
 
-impl<T> EntropyProducer for EntropyProcessor<T> {
+/// A processor that also produces the entropy of the bytes it consumed,
+/// scanners feed it and read the entropy at every match
+pub trait EntropyConsumer<T>: Processor<T> + EntropyProducer {}
+
+impl<T, P> EntropyConsumer<T> for P where P: Processor<T> + EntropyProducer {}
+
+impl<T: Send> EntropyProducer for EntropyProcessor<T> {
     #[allow(clippy::cast_precision_loss)]
     fn entropy(&self) -> Option<f64> {
         if self.buffer.len() < self.minimum_consumed_bytes {
@@ -126,7 +132,7 @@ mod tests {
         let mut processor: EntropyProcessor<()> = EntropyProcessor::with_minimum_consumed(100, 1);
 
         processor.consume(&[1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8]);
-        assert_eq!(processor.entropy(), Some(3.321928094887362));
+        assert_eq!(processor.entropy(), Some(3.321_928_094_887_362));
     }
 
     #[test]
@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+
+use super::processors::{ChunkSize, Processor};
+use crate::common::Endianness;
+use crate::types::guid::{Guid, GuidLayout};
+
+const GUID_SIZE: usize = 16;
+
+/// Reads GUIDs in both the Microsoft and the RFC 4122 layouts.
+///
+/// Returns the GUID in the first layout (Microsoft first) that is one of the literals,
+/// or when there are no literals that is plausible if `plausible` is set.
+/// Otherwise the GUID is read in the layout of `endianness`, little endian is Microsoft.
+#[derive(Clone)]
+pub struct GuidProcessor {
+    literals: HashSet<Guid>,
+    plausible: bool,
+    layout: GuidLayout,
+}
+
+impl Processor<Guid> for GuidProcessor {
+    #[inline]
+    fn consume(&mut self, bytes: &[u8]) -> Option<Guid> {
+        if !self.literals.is_empty() {
+            return [GuidLayout::Microsoft, GuidLayout::Rfc4122]
+                .into_iter()
+                .map(|layout| Guid::from_layout(bytes, layout))
+                .find(|guid| self.literals.contains(guid));
+        }
+
+        if self.plausible {
+            return [GuidLayout::Microsoft, GuidLayout::Rfc4122]
+                .into_iter()
+                .map(|layout| Guid::from_layout(bytes, layout))
+                .find(Guid::is_plausible);
+        }
+
+        return Some(Guid::from_layout(bytes, self.layout));
+    }
+
+    fn chunk_size(&self) -> ChunkSize {
+        return ChunkSize::Size(GUID_SIZE);
+    }
+}
+
+impl GuidProcessor {
+    #[must_use]
+    pub fn new(literals: &[Guid], plausible: bool, endianness: Endianness) -> Self {
+        let layout = match endianness {
+            Endianness::Little => GuidLayout::Microsoft,
+            Endianness::Big => GuidLayout::Rfc4122,
+        };
+
+        return GuidProcessor {
+            literals: literals.iter().copied().collect(),
+            plausible,
+            layout,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guid(text: &str) -> Guid {
+        return text.parse().unwrap();
+    }
+
+    #[test]
+    fn consume_literal_in_both_layouts() {
+        let literal = guid("{9a3b2c1d-0e4f-4a5b-8c6d-7e8f90a1b2c3}");
+        let mut processor = GuidProcessor::new(&[literal], false, Endianness::Little);
+
+        let microsoft = processor
+            .consume(&literal.to_layout(GuidLayout::Microsoft))
+            .unwrap();
+        assert_eq!(microsoft, literal);
+        assert_eq!(microsoft.layout(), GuidLayout::Microsoft);
+
+        let rfc = processor
+            .consume(&literal.to_layout(GuidLayout::Rfc4122))
+            .unwrap();
+        assert_eq!(rfc, literal);
+        assert_eq!(rfc.layout(), GuidLayout::Rfc4122);
+
+        assert_eq!(processor.consume(&[0; 16]), None);
+    }
+
+    #[test]
+    fn consume_plausible() {
+        let mut processor = GuidProcessor::new(&[], true, Endianness::Little);
+
+        // Version 4 only in the RFC 4122 layout
+        let random = guid("9a3b2c1d-0e4f-4a5b-8c6d-7e8f90a1b2c3");
+        let found = processor
+            .consume(&random.to_layout(GuidLayout::Rfc4122))
+            .unwrap();
+        assert_eq!(found, random);
+        assert_eq!(found.layout(), GuidLayout::Rfc4122);
+
+        assert_eq!(processor.consume(&[0xFF; 16]), None);
+    }
+
+    #[test]
+    fn consume_endianness_layout() {
+        let mut processor = GuidProcessor::new(&[], false, Endianness::Big);
+        let bytes: Vec<u8> = (0..16).collect();
+
+        assert_eq!(
+            processor.consume(&bytes),
+            Some(guid("00010203-0405-0607-0809-0a0b0c0d0e0f"))
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use super::processors::{ChunkSize, Processor};
+use crate::hex::HexPattern;
+use crate::types::hex_match::HexMatch;
+
+/// Matches a [`HexPattern`] at the start of every chunk
+pub struct HexProcessor {
+    pattern: HexPattern,
+}
+
+impl Processor<HexMatch> for HexProcessor {
+    #[inline]
+    fn consume(&mut self, bytes: &[u8]) -> Option<HexMatch> {
+        let size = self.pattern.match_size(bytes)?;
+        return Some(HexMatch::new(bytes[..size].to_vec()));
+    }
+
+    fn chunk_size(&self) -> ChunkSize {
+        return ChunkSize::Size(self.pattern.max_size());
+    }
+
+    fn min_chunk_size(&self) -> ChunkSize {
+        return ChunkSize::Size(self.pattern.min_size());
+    }
+}
+
+impl HexProcessor {
+    #[must_use]
+    pub fn new(pattern: HexPattern) -> Self {
+        return HexProcessor { pattern };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consume_returns_match() {
+        let mut processor = HexProcessor::new(HexPattern::compile("AA{0,2}BB").unwrap());
+
+        assert_eq!(processor.chunk_size(), ChunkSize::Size(4));
+        assert_eq!(processor.min_chunk_size(), ChunkSize::Size(2));
+        assert_eq!(
+            processor.consume(&[0xAA, 0x00, 0xBB, 0xBB]),
+            Some(HexMatch::new(vec![0xAA, 0x00, 0xBB]))
+        );
+        assert_eq!(processor.consume(&[0xAA, 0x00, 0x00, 0x00]), None);
+    }
+}
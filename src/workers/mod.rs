@@ -1,4 +1,16 @@
 // TODO(danilan): Fix visibility everywhere
+pub mod any_timestamp_processor;
 pub mod entropy_processor;
+pub mod guid_processor;
+pub mod hex_processor;
 pub mod native_processor;
+pub mod prefilter;
 pub mod processors;
+pub mod regex_processor;
+pub mod regex_search;
+pub mod rule_processor;
+pub mod search;
+pub mod string_processor;
+pub mod struct_processor;
+pub mod text_number_processor;
+pub mod timestamp_decoder;
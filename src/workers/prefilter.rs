@@ -0,0 +1,341 @@
+use memchr::{memchr, memchr2, memchr3, memmem};
+
+use crate::common::Endianness;
+use crate::filters::configuration::Configuration;
+use crate::types::bit_type::ByteOrdering;
+use crate::types::compare::Compare;
+use crate::types::endian::ToLittleEndian;
+
+/// Rejects offsets by looking at raw bytes, before converting them to a value.
+///
+/// Derived from a [`Configuration`]: integer ranges and literal sets limit the values
+/// of the most significant byte, and a single integer literal is searched as a byte pattern.
+/// Anything else (floats, expressions, exclusions, entropy) leaves every offset as a candidate,
+/// the regular filters still decide if a candidate matches.
+#[derive(Clone, Default)]
+pub enum Prefilter {
+    /// Every offset is a candidate
+    #[default]
+    Any,
+    /// The most significant byte, at `index` in the value, must be allowed
+    MostSignificantByte {
+        index: usize,
+        allowed: Box<[bool; 256]>,
+        // Allowed bytes when there are only a few, these are searched with memchr
+        needles: Vec<u8>,
+    },
+    /// The value's bytes must be exactly the literal's bytes
+    Literal(Box<memmem::Finder<'static>>),
+}
+
+impl Prefilter {
+    /// # Panics
+    /// Panics if `T` has no bytes
+    #[must_use]
+    pub fn new<T: Compare>(config: &Configuration<T>, endianness: Endianness) -> Self {
+        if T::BYTE_ORDERING == ByteOrdering::Unordered || config.expression.is_some() {
+            return Prefilter::Any;
+        }
+
+        if let [literal] = config.literals.as_slice() {
+            let pattern = to_bytes(literal, endianness);
+            return Prefilter::Literal(Box::new(memmem::Finder::new(&pattern).into_owned()));
+        }
+
+        let size = T::zero().to_bytes().len();
+        let index = if endianness == Endianness::Little {
+            size - 1
+        } else {
+            0
+        };
+
+        if !config.literals.is_empty() {
+            let mut allowed = Box::new([false; 256]);
+            for literal in &config.literals {
+                allowed[*literal.to_bytes().last().unwrap() as usize] = true;
+            }
+
+            return Self::most_significant_byte(index, allowed);
+        }
+
+        if config.minimum.is_none() && config.maximum.is_none() {
+            return Prefilter::Any;
+        }
+
+        let ordered = |byte: u8| match T::BYTE_ORDERING {
+            // Moves the sign bit so i8 ordering becomes u8 ordering
+            ByteOrdering::Signed => byte ^ 0x80,
+            _ => byte,
+        };
+        let most_significant = |value: T| ordered(*value.to_bytes().last().unwrap());
+
+        let low = config.minimum.map_or(u8::MIN, most_significant);
+        let high = config.maximum.map_or(u8::MAX, most_significant);
+
+        let mut allowed = Box::new([false; 256]);
+        for byte in 0..=u8::MAX {
+            allowed[byte as usize] = (low..=high).contains(&ordered(byte));
+        }
+
+        return Self::most_significant_byte(index, allowed);
+    }
+
+    /// Candidates start with `prefix`, every offset is a candidate if it's empty
+    #[must_use]
+    pub fn with_prefix(prefix: &[u8]) -> Self {
+        if prefix.is_empty() {
+            return Prefilter::Any;
+        }
+
+        return Prefilter::Literal(Box::new(memmem::Finder::new(prefix).into_owned()));
+    }
+
+    /// Candidates start with one of `bytes`
+    #[must_use]
+    pub fn with_first_bytes(bytes: &[u8]) -> Self {
+        let mut allowed = Box::new([false; 256]);
+        for byte in bytes {
+            allowed[*byte as usize] = true;
+        }
+
+        return Self::most_significant_byte(0, allowed);
+    }
+
+    fn most_significant_byte(index: usize, allowed: Box<[bool; 256]>) -> Self {
+        let needles: Vec<u8> = (0..=u8::MAX).filter(|b| allowed[*b as usize]).collect();
+
+        return Prefilter::MostSignificantByte {
+            index,
+            allowed,
+            needles: if needles.len() <= 3 { needles } else { vec![] },
+        };
+    }
+
+    /// Finds the next candidate offset in `from..positions`, where `positions` is the
+    /// amount of offsets in `data` that a whole value fits in.
+    #[must_use]
+    #[inline]
+    pub fn next_candidate(&self, data: &[u8], from: usize, positions: usize) -> Option<usize> {
+        if from >= positions {
+            return None;
+        }
+
+        return match self {
+            Prefilter::Any => Some(from),
+            Prefilter::MostSignificantByte {
+                index,
+                allowed,
+                needles,
+            } => {
+                let haystack = &data[from + index..positions + index];
+                let found = match needles.as_slice() {
+                    [] => haystack.iter().position(|byte| allowed[*byte as usize]),
+                    [first] => memchr(*first, haystack),
+                    [first, second] => memchr2(*first, *second, haystack),
+                    [first, second, third] => memchr3(*first, *second, *third, haystack),
+                    _ => unreachable!("at most three needles are kept"),
+                };
+
+                found.map(|position| from + position)
+            }
+            Prefilter::Literal(finder) => {
+                let end = positions + finder.needle().len() - 1;
+                finder
+                    .find(&data[from..end])
+                    .map(|position| from + position)
+            }
+        };
+    }
+}
+
+fn to_bytes<T: ToLittleEndian>(value: &T, endianness: Endianness) -> Vec<u8> {
+    let mut bytes = value.to_bytes();
+    if endianness == Endianness::Big {
+        bytes.reverse();
+    }
+
+    return bytes;
+}
+
+#[cfg(test)]
+mod tests {
+    use assertor::{assert_that, VecAssertion};
+
+    use super::*;
+    use crate::filters::expression::Expression;
+
+    fn candidates(prefilter: &Prefilter, data: &[u8], size: usize) -> Vec<usize> {
+        let positions = data.len() + 1 - size;
+        let mut result = vec![];
+        let mut from = 0;
+
+        while let Some(candidate) = prefilter.next_candidate(data, from, positions) {
+            result.push(candidate);
+            from = candidate + 1;
+        }
+
+        return result;
+    }
+
+    #[test]
+    fn prefix_finds_candidates() {
+        let prefilter = Prefilter::with_prefix(&[0xAA, 0xBB]);
+        let data = [0xAA, 0xBB, 0x00, 0xAA, 0xAA, 0xBB, 0x01, 0xAA, 0xBB];
+
+        assert_that!(candidates(&prefilter, &data, 3)).contains_exactly_in_order(vec![0, 4]);
+        assert!(matches!(Prefilter::with_prefix(&[]), Prefilter::Any));
+    }
+
+    #[test]
+    fn first_bytes_finds_candidates() {
+        let prefilter = Prefilter::with_first_bytes(&[0x00, 0x41, 0x61]);
+        let data = [0x41, 0x01, 0x61, 0x02, 0x00, 0x42];
+
+        assert_that!(candidates(&prefilter, &data, 1)).contains_exactly_in_order(vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn float_returns_any() {
+        let config = Configuration {
+            literals: vec![3.5f64],
+            ..Default::default()
+        };
+
+        let prefilter = Prefilter::new(&config, Endianness::Little);
+
+        assert!(matches!(prefilter, Prefilter::Any));
+    }
+
+    #[test]
+    fn exclusions_return_any() {
+        let config = Configuration::<u32> {
+            exclude_zero: true,
+            exclude_literal: Some(3),
+            ..Default::default()
+        };
+
+        let prefilter = Prefilter::new(&config, Endianness::Little);
+
+        assert!(matches!(prefilter, Prefilter::Any));
+    }
+
+    #[test]
+    fn expression_returns_any() {
+        let config = Configuration::<u32> {
+            literals: vec![3],
+            expression: Some(Expression::parse(">=10 || ==3").unwrap()),
+            ..Default::default()
+        };
+
+        let prefilter = Prefilter::new(&config, Endianness::Little);
+
+        assert!(matches!(prefilter, Prefilter::Any));
+    }
+
+    #[test]
+    fn literal_little_endian_finds_pattern() {
+        let config = Configuration::<u16> {
+            literals: vec![0x0102],
+            ..Default::default()
+        };
+        let data = [2u8, 1u8, 2u8, 2u8, 1u8, 1u8];
+
+        let prefilter = Prefilter::new(&config, Endianness::Little);
+
+        assert_that!(candidates(&prefilter, &data, 2)).contains_exactly_in_order(vec![0, 3]);
+    }
+
+    #[test]
+    fn literal_big_endian_finds_pattern() {
+        let config = Configuration::<u16> {
+            literals: vec![0x0102],
+            ..Default::default()
+        };
+        let data = [2u8, 1u8, 2u8, 2u8, 1u8, 2u8];
+
+        let prefilter = Prefilter::new(&config, Endianness::Big);
+
+        assert_that!(candidates(&prefilter, &data, 2)).contains_exactly_in_order(vec![1, 4]);
+    }
+
+    #[test]
+    fn literal_set_allows_most_significant_bytes() {
+        let config = Configuration::<u16> {
+            literals: vec![0x0102, 0x0305, 0x0107],
+            ..Default::default()
+        };
+        let data = [2u8, 1u8, 2u8, 3u8, 4u8, 1u8];
+
+        let prefilter = Prefilter::new(&config, Endianness::Little);
+
+        assert_that!(candidates(&prefilter, &data, 2)).contains_exactly_in_order(vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn literal_set_big_endian() {
+        let config = Configuration::<i32> {
+            literals: (0..10).map(|x| x << 24).collect(),
+            ..Default::default()
+        };
+        let data = [9u8, 0u8, 0u8, 0u8, 10u8, 0u8, 0u8, 0u8];
+
+        let prefilter = Prefilter::new(&config, Endianness::Big);
+
+        assert_that!(candidates(&prefilter, &data, 4)).contains_exactly_in_order(vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn unsigned_range_little_endian() {
+        let config = Configuration::<u16> {
+            minimum: Some(0x0100),
+            maximum: Some(0x02FF),
+            ..Default::default()
+        };
+        let data = [0u8, 1u8, 3u8, 2u8, 0xFFu8];
+
+        let prefilter = Prefilter::new(&config, Endianness::Little);
+
+        assert_that!(candidates(&prefilter, &data, 2)).contains_exactly_in_order(vec![0, 2]);
+    }
+
+    #[test]
+    fn unsigned_minimum_big_endian() {
+        let config = Configuration::<u16> {
+            minimum: Some(0x0300),
+            ..Default::default()
+        };
+        let data = [1u8, 3u8, 2u8, 0xF0u8, 0u8];
+
+        let prefilter = Prefilter::new(&config, Endianness::Big);
+
+        assert_that!(candidates(&prefilter, &data, 2)).contains_exactly_in_order(vec![1, 3]);
+    }
+
+    #[test]
+    fn signed_range_crosses_zero() {
+        let config = Configuration::<i16> {
+            minimum: Some(-300),
+            maximum: Some(300),
+            ..Default::default()
+        };
+        // Most significant bytes of -300 and 300 are 0xFE and 0x01
+        let data = [0u8, 0xFEu8, 0x80u8, 0x7Fu8, 0xFFu8, 0x01u8, 0x02u8, 0u8];
+
+        let prefilter = Prefilter::new(&config, Endianness::Little);
+
+        assert_that!(candidates(&prefilter, &data, 2)).contains_exactly_in_order(vec![0, 3, 4, 6]);
+    }
+
+    #[test]
+    fn signed_maximum_allows_negatives() {
+        let config = Configuration::<i8> {
+            maximum: Some(-100),
+            ..Default::default()
+        };
+        let data = [0x80u8, 0x9Cu8, 0x9Du8, 0u8, 0x7Fu8];
+
+        let prefilter = Prefilter::new(&config, Endianness::Little);
+
+        assert_that!(candidates(&prefilter, &data, 1)).contains_exactly_in_order(vec![0, 1]);
+    }
+}
@@ -1,8 +1,31 @@
 /// Processes a fixed size of byte chunks
 // TODO(danilan): change interface to not return directly
-pub trait Processor<T> {
+pub trait Processor<T>: Send {
     fn consume(&mut self, bytes: &[u8]) -> Option<T>;
     fn chunk_size(&self) -> ChunkSize;
+
+    /// Smallest chunk that can be consumed, shorter chunks are passed at the end of the data
+    fn min_chunk_size(&self) -> ChunkSize {
+        return self.chunk_size();
+    }
+}
+
+impl<T, P> Processor<T> for Box<P>
+where
+    P: Processor<T> + ?Sized,
+{
+    #[inline]
+    fn consume(&mut self, bytes: &[u8]) -> Option<T> {
+        return (**self).consume(bytes);
+    }
+
+    fn chunk_size(&self) -> ChunkSize {
+        return (**self).chunk_size();
+    }
+
+    fn min_chunk_size(&self) -> ChunkSize {
+        return (**self).min_chunk_size();
+    }
 }
 
 /// Represents the chunk size the Processor works in.
@@ -16,6 +39,8 @@ pub enum ChunkSize {
 }
 
 impl ChunkSize {
+    /// # Panics
+    /// Panics if the chunk size is [`ChunkSize::Any`]
     #[must_use]
     pub fn unwrap(self) -> usize {
         match self {
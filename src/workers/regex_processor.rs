@@ -0,0 +1,87 @@
+use regex::bytes::Regex;
+
+use super::processors::{ChunkSize, Processor};
+use super::regex_search::MAX_MATCH_SIZE;
+use crate::types::regex_match::RegexMatch;
+
+/// Matches a regex at the start of every chunk, for searches that pass every offset
+/// to several processors. [`super::regex_search::RegexSearch`] is faster for a single regex.
+///
+/// Offsets inside a match are skipped so matches don't overlap,
+/// so the processor must be passed every offset in order.
+pub struct RegexProcessor {
+    /// The regex anchored to the start of the chunk
+    regex: Regex,
+    /// Offsets left in the last match
+    skip: usize,
+}
+
+impl Processor<RegexMatch> for RegexProcessor {
+    #[inline]
+    fn consume(&mut self, bytes: &[u8]) -> Option<RegexMatch> {
+        if self.skip > 0 {
+            self.skip -= 1;
+            return None;
+        }
+
+        let found = self.regex.find(bytes)?;
+        if found.is_empty() {
+            return None;
+        }
+
+        self.skip = found.len() - 1;
+        return Some(RegexMatch::from_bytes(found.as_bytes()));
+    }
+
+    fn chunk_size(&self) -> ChunkSize {
+        return ChunkSize::Size(MAX_MATCH_SIZE);
+    }
+
+    fn min_chunk_size(&self) -> ChunkSize {
+        return ChunkSize::Size(1);
+    }
+}
+
+impl RegexProcessor {
+    /// Matches raw bytes without Unicode, like `--regex`
+    ///
+    /// # Errors
+    /// Returns an error if the pattern is not a valid regex
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        let regex = regex::bytes::RegexBuilder::new(&format!("^(?:{pattern})"))
+            .unicode(false)
+            .build()?;
+
+        return Ok(RegexProcessor { regex, skip: 0 });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consume_at_start_without_overlap() {
+        let mut processor = RegexProcessor::new("12334+").unwrap();
+        let data = b"x123344 1233";
+
+        let found: Vec<(usize, String)> = (0..data.len())
+            .filter_map(|index| {
+                let result = processor.consume(&data[index..])?;
+                Some((index, result.to_string()))
+            })
+            .collect();
+
+        assert_eq!(found, vec![(1, "123344".to_string())]);
+    }
+
+    #[test]
+    fn consume_skips_empty_matches() {
+        let mut processor = RegexProcessor::new("a*").unwrap();
+
+        assert_eq!(processor.consume(b"bbb"), None);
+        assert_eq!(processor.consume(b"aab").unwrap().to_string(), "aa");
+        assert_eq!(processor.consume(b"ab"), None);
+        assert_eq!(processor.consume(b"b"), None);
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use regex::bytes::Regex;
+
+use super::search::Search;
+use crate::types::regex_match::RegexMatch;
+use crate::types::string_match::StringEncoding;
+
+/// Matches in streams are cut at this size, mapped files are searched as a whole
+pub const MAX_MATCH_SIZE: usize = 4096;
+
+/// Searches a regex in the data, run by the [`crate::scanner::Scanner`].
+///
+/// Unlike processors the regex searches the data instead of matching
+/// every offset, so matches don't overlap.
+/// Data can be decoded from UTF-16 before searching, then both even and odd
+/// offsets are decoded and the regex matches the decoded text.
+pub struct RegexSearch {
+    regex: Regex,
+    encoding: StringEncoding,
+
+    /// Position to resume searching from after the last match, for every alignment of UTF-16
+    resume: [usize; 2],
+    /// Position in the source of the end of the data searched so far
+    searched: usize,
+    /// Matches found in the data searched last that weren't returned yet
+    pending: VecDeque<(Range<usize>, RegexMatch)>,
+}
+
+impl Search<RegexMatch> for RegexSearch {
+    fn max_size(&self) -> usize {
+        return MAX_MATCH_SIZE;
+    }
+
+    fn min_size(&self) -> usize {
+        return 1;
+    }
+
+    /// The data is searched once, on the first search of data that wasn't searched yet.
+    /// Matches continue after the last match instead of `start`.
+    fn find(
+        &mut self,
+        data: &[u8],
+        _start: usize,
+        limit: usize,
+        position: usize,
+    ) -> Option<(Range<usize>, RegexMatch)> {
+        if position + limit > self.searched {
+            self.pending = self.find_all(data, position, limit);
+            self.searched = position + limit;
+        }
+
+        return self.pending.pop_front();
+    }
+}
+
+impl RegexSearch {
+    /// `regex` matches raw bytes with [`StringEncoding::Utf8`] and decoded text otherwise
+    #[must_use]
+    pub fn new(regex: Regex, encoding: StringEncoding) -> Self {
+        return RegexSearch {
+            regex,
+            encoding,
+            resume: [0, 1],
+            searched: 0,
+            pending: VecDeque::new(),
+        };
+    }
+
+    /// Non empty matches that start before `limit` in order, where `position`
+    /// is the position of `data` in the source
+    fn find_all(
+        &mut self,
+        data: &[u8],
+        position: usize,
+        limit: usize,
+    ) -> VecDeque<(Range<usize>, RegexMatch)> {
+        if self.encoding == StringEncoding::Utf8 {
+            return self.find_bytes(data, position, limit).into();
+        }
+
+        let mut matches = self.find_utf16(data, position, limit, 0);
+        matches.extend(self.find_utf16(data, position, limit, 1));
+        matches.sort_by_key(|(range, _)| range.start);
+        return matches.into();
+    }
+
+    /// Non empty matches of raw bytes that start before `limit`
+    fn find_bytes(
+        &mut self,
+        data: &[u8],
+        position: usize,
+        limit: usize,
+    ) -> Vec<(Range<usize>, RegexMatch)> {
+        let mut matches = vec![];
+        let mut start = self.resume[0].max(position) - position;
+
+        while let Some(found) = self.regex.find_at(data, start) {
+            if found.start() >= limit {
+                break;
+            }
+
+            if found.is_empty() {
+                start = found.end() + 1;
+                if start > data.len() {
+                    break;
+                }
+                continue;
+            }
+
+            matches.push((found.range(), RegexMatch::from_bytes(found.as_bytes())));
+            start = found.end();
+        }
+
+        self.resume[0] = position + start.max(limit);
+        return matches;
+    }
+
+    /// Non empty matches in UTF-16 text that start before `limit`,
+    /// the text is decoded from offsets with the parity of `alignment`
+    fn find_utf16(
+        &mut self,
+        data: &[u8],
+        position: usize,
+        limit: usize,
+        alignment: usize,
+    ) -> Vec<(Range<usize>, RegexMatch)> {
+        let first = (alignment + 2 - position % 2) % 2;
+        if first >= data.len() {
+            return vec![];
+        }
+
+        let (text, offsets) = decode_utf16(&data[first..], self.encoding);
+        let offsets: Vec<usize> = offsets.iter().map(|offset| offset + first).collect();
+
+        let resume = self.resume[alignment].max(position + first) - position;
+        let text_limit = offsets.partition_point(|offset| *offset < limit);
+
+        let mut matches = vec![];
+        let mut start = offsets.partition_point(|offset| *offset < resume);
+        while let Some(found) = self.regex.find_at(&text, start) {
+            if found.start() >= text_limit {
+                break;
+            }
+
+            if found.is_empty() {
+                start = found.end() + 1;
+                if start > text.len() {
+                    break;
+                }
+                continue;
+            }
+
+            let range = offsets[found.start()]..offsets[found.end()];
+            let decoded = String::from_utf8_lossy(found.as_bytes());
+            let result = RegexMatch::from_text(&decoded, self.encoding, range.len());
+
+            matches.push((range, result));
+            start = found.end();
+        }
+
+        let end = offsets[start.min(offsets.len() - 1)];
+        self.resume[alignment] = position + end.max(limit);
+        return matches;
+    }
+}
+
+/// Decodes UTF-16 to UTF-8 text, invalid characters are replaced with U+FFFD.
+///
+/// Returns the text and the offset in `bytes` of every byte in the text,
+/// followed by the offset of the end of the text.
+fn decode_utf16(bytes: &[u8], encoding: StringEncoding) -> (Vec<u8>, Vec<usize>) {
+    let units = bytes.chunks_exact(2).map(|unit| match encoding {
+        StringEncoding::Utf16Be => u16::from_be_bytes([unit[0], unit[1]]),
+        _ => u16::from_le_bytes([unit[0], unit[1]]),
+    });
+
+    let mut text = Vec::with_capacity(bytes.len());
+    let mut offsets = Vec::with_capacity(bytes.len() + 1);
+    let mut offset = 0;
+    let mut buffer = [0; 4];
+
+    for decoded in char::decode_utf16(units) {
+        let (c, size) = match decoded {
+            Ok(c) => (c, c.len_utf16() * 2),
+            Err(_) => (char::REPLACEMENT_CHARACTER, 2),
+        };
+
+        let encoded = c.encode_utf8(&mut buffer);
+        text.extend_from_slice(encoded.as_bytes());
+        offsets.extend(std::iter::repeat(offset).take(encoded.len()));
+        offset += size;
+    }
+
+    offsets.push(offset);
+    return (text, offsets);
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::bytes::RegexBuilder;
+
+    use super::*;
+
+    fn regex(pattern: &str) -> Regex {
+        return RegexBuilder::new(pattern).unicode(false).build().unwrap();
+    }
+
+    /// Searches all of `data` like the scanner does at the end of the data
+    fn find(mut search: RegexSearch, data: &[u8]) -> Vec<(Range<usize>, RegexMatch)> {
+        let mut matches = vec![];
+        let mut next = 0;
+        while let Some((range, result)) = search.find(data, next, data.len(), 0) {
+            next = range.start + 1;
+            matches.push((range, result));
+        }
+
+        return matches;
+    }
+
+    #[test]
+    fn find_bytes() {
+        let data = b"\x00id=12;id=345\xFF\xFEid=";
+
+        let matches = find(
+            RegexSearch::new(regex(r"id=\d+|\xFF\xFE"), StringEncoding::Utf8),
+            data,
+        );
+
+        assert_eq!(
+            matches,
+            vec![
+                (1..6, RegexMatch::from_bytes(b"id=12")),
+                (7..13, RegexMatch::from_bytes(b"id=345")),
+                (13..15, RegexMatch::from_bytes(b"\xFF\xFE")),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_utf16_both_alignments() {
+        let first = StringEncoding::Utf16Le.encode("user=bob");
+        let second = StringEncoding::Utf16Le.encode("user=alice");
+        let data = [b"x".as_slice(), &first, &[0xFF; 3], &second].concat();
+
+        let matches = find(
+            RegexSearch::new(Regex::new(r"user=\w+").unwrap(), StringEncoding::Utf16Le),
+            &data,
+        );
+
+        assert_eq!(
+            matches,
+            vec![
+                (
+                    1..17,
+                    RegexMatch::from_text("user=bob", StringEncoding::Utf16Le, 16)
+                ),
+                (
+                    20..40,
+                    RegexMatch::from_text("user=alice", StringEncoding::Utf16Le, 20)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_resumes_after_last_match() {
+        let data = b"aaaa;aaaa";
+        let mut search = RegexSearch::new(regex("a+"), StringEncoding::Utf8);
+
+        // The first part of the data, a match can't start after its limit
+        assert_eq!(
+            search.find(&data[..7], 0, 2, 0),
+            Some((0..4, RegexMatch::from_bytes(b"aaaa")))
+        );
+        assert_eq!(search.find(&data[..7], 1, 2, 0), None);
+
+        // The rest of the data, read again from the limit
+        assert_eq!(
+            search.find(&data[2..], 0, 7, 2),
+            Some((3..7, RegexMatch::from_bytes(b"aaaa")))
+        );
+    }
+
+    #[test]
+    fn decode_utf16_offsets() {
+        let (text, offsets) = decode_utf16(
+            &[0x61, 0x00, 0xE9, 0x00, 0x00, 0xD8],
+            StringEncoding::Utf16Le,
+        );
+
+        assert_eq!(text, "aé\u{FFFD}".as_bytes());
+        assert_eq!(offsets, vec![0, 2, 2, 4, 4, 4, 6]);
+    }
+}
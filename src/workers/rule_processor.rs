@@ -0,0 +1,227 @@
+use std::marker::PhantomData;
+
+use super::processors::{ChunkSize, Processor};
+use crate::common::Endianness;
+use crate::filters::filter::Filter;
+use crate::hex::HexPattern;
+use crate::types::rule_match::RuleMatch;
+use crate::types::scan_value::ScanValue;
+
+/// A rule's processor and filter, the type of the value is erased so rules
+/// of different types are kept together
+trait RuleMatcher: Send {
+    fn consume(&mut self, rule: &str, bytes: &[u8]) -> Option<RuleMatch>;
+    fn chunk_size(&self) -> usize;
+    fn min_chunk_size(&self) -> usize;
+}
+
+struct Matcher<T, P, F> {
+    processor: P,
+    filter: F,
+    endianness: Option<Endianness>,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T, P, F> RuleMatcher for Matcher<T, P, F>
+where
+    T: ScanValue,
+    P: Processor<T>,
+    F: Filter<T>,
+{
+    #[inline]
+    fn consume(&mut self, rule: &str, bytes: &[u8]) -> Option<RuleMatch> {
+        if bytes.len() < self.min_chunk_size() {
+            return None;
+        }
+
+        let chunk = &bytes[..bytes.len().min(self.chunk_size())];
+        let value = self.processor.consume(chunk)?;
+        if !self.filter.include(value.clone()) {
+            return None;
+        }
+
+        let size = value.size().unwrap_or(chunk.len());
+        return Some(RuleMatch::new(
+            rule,
+            value.type_name(),
+            value.to_string(),
+            size,
+            self.endianness,
+        ));
+    }
+
+    fn chunk_size(&self) -> usize {
+        return self.processor.chunk_size().unwrap();
+    }
+
+    fn min_chunk_size(&self) -> usize {
+        return self.processor.min_chunk_size().unwrap();
+    }
+}
+
+/// Runs the rules of a rules file in a single pass, returns the match of the first rule
+/// that matches at an offset.
+///
+/// Every rule is passed every offset, so rules with stateful processors
+/// (e.g. numbers in text) see all the data. Files that don't start with the magic
+/// don't match any rule, the magic is checked at the first offset.
+pub struct RuleProcessor {
+    rules: Vec<(String, Box<dyn RuleMatcher>)>,
+    magic: Option<HexPattern>,
+    /// Whether the data starts with the magic, None until the first offset is consumed
+    has_magic: Option<bool>,
+}
+
+impl Processor<RuleMatch> for RuleProcessor {
+    #[inline]
+    fn consume(&mut self, bytes: &[u8]) -> Option<RuleMatch> {
+        let magic = &self.magic;
+        let has_magic = *self.has_magic.get_or_insert_with(|| {
+            return magic
+                .as_ref()
+                .map_or(true, |magic| magic.match_size(bytes).is_some());
+        });
+
+        if !has_magic {
+            return None;
+        }
+
+        let mut found = None;
+        for (name, matcher) in &mut self.rules {
+            let result = matcher.consume(name, bytes);
+            if found.is_none() {
+                found = result;
+            }
+        }
+
+        return found;
+    }
+
+    fn chunk_size(&self) -> ChunkSize {
+        let magic_size = self.magic.as_ref().map_or(1, HexPattern::max_size);
+        let size = self
+            .rules
+            .iter()
+            .map(|(_, matcher)| matcher.chunk_size())
+            .fold(magic_size, usize::max);
+
+        return ChunkSize::Size(size);
+    }
+
+    fn min_chunk_size(&self) -> ChunkSize {
+        let size = self
+            .rules
+            .iter()
+            .map(|(_, matcher)| matcher.min_chunk_size())
+            .min()
+            .unwrap_or(1);
+
+        return ChunkSize::Size(size);
+    }
+}
+
+impl RuleProcessor {
+    #[must_use]
+    pub fn new(magic: Option<HexPattern>) -> Self {
+        return RuleProcessor {
+            rules: vec![],
+            magic,
+            has_magic: None,
+        };
+    }
+
+    /// Adds a rule, values of `processor` that pass `filter` are matched with the rule's name.
+    /// `endianness` is the endianness the processor reads values in, None for text and bytes.
+    pub fn add<T, P, F>(
+        &mut self,
+        name: &str,
+        processor: P,
+        filter: F,
+        endianness: Option<Endianness>,
+    ) where
+        T: ScanValue + 'static,
+        P: Processor<T> + 'static,
+        F: Filter<T> + 'static,
+    {
+        let matcher = Matcher {
+            processor,
+            filter,
+            endianness,
+            phantom: PhantomData,
+        };
+
+        self.rules.push((name.to_string(), Box::new(matcher)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::filter::IncludeAll;
+    use crate::workers::native_processor::NativeProcessor;
+    use crate::workers::string_processor::StringProcessor;
+
+    struct Min(u16);
+
+    impl Filter<u16> for Min {
+        fn include(&self, result: u16) -> bool {
+            return result >= self.0;
+        }
+    }
+
+    /// Consumes every offset like the scanner, returns the offsets and output of matches
+    fn scan(mut processor: RuleProcessor, data: &[u8]) -> Vec<(usize, String)> {
+        let chunk_size = processor.chunk_size().unwrap();
+
+        return (0..data.len())
+            .filter_map(|index| {
+                let chunk = &data[index..data.len().min(index + chunk_size)];
+                let result = processor.consume(chunk)?;
+                let rule = result.rule().unwrap();
+                Some((index, format!("{rule} {}: {result}", result.type_name())))
+            })
+            .collect();
+    }
+
+    fn processor(magic: Option<&str>) -> RuleProcessor {
+        let magic = magic.map(|magic| HexPattern::compile(magic).unwrap());
+        let mut processor = RuleProcessor::new(magic);
+        processor.add(
+            "big",
+            NativeProcessor::<u16>::new(Endianness::Little),
+            Min(0x5000),
+            Some(Endianness::Little),
+        );
+        processor.add("name", StringProcessor::new("AB", false), IncludeAll, None);
+        processor.add(
+            "any",
+            NativeProcessor::<u16>::new(Endianness::Little),
+            IncludeAll,
+            Some(Endianness::Little),
+        );
+
+        return processor;
+    }
+
+    #[test]
+    fn consume_tags_first_rule() {
+        assert_eq!(
+            scan(processor(None), b"AB\x00\x60\x01"),
+            vec![
+                (0, "name utf8: AB".into()),
+                (1, "any u16: 66".into()),
+                (2, "big u16: 24576".into()),
+                (3, "any u16: 352".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn consume_requires_magic() {
+        assert_eq!(scan(processor(Some("7F454C46")), b"\x00AB"), vec![]);
+        assert_eq!(
+            scan(processor(Some("00")), b"\x00AB"),
+            vec![(0, "any u16: 16640".into()), (1, "name utf8: AB".into())]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::ops::Range;
+
+use super::prefilter::Prefilter;
+use super::processors::Processor;
+use crate::types::scan_value::ScanValue;
+
+/// Finds the values a [`crate::scanner::Scanner`] matches in the data it reads,
+/// the scanner handles reading, the context and the entropy around every match.
+pub trait Search<T>: Send {
+    /// Largest size of a match, before the end of the data offsets are only
+    /// searched where a match this large fits in the data read
+    fn max_size(&self) -> usize;
+
+    /// Smallest size of a match, shorter offsets at the end of the data are not searched
+    fn min_size(&self) -> usize;
+
+    /// Finds the first match starting in `start..limit` of `data`, returns its range in `data`.
+    ///
+    /// `position` is the position of `data` in the source. Matches are searched in order,
+    /// the next search starts after the first byte of the last match.
+    fn find(
+        &mut self,
+        data: &[u8],
+        start: usize,
+        limit: usize,
+        position: usize,
+    ) -> Option<(Range<usize>, T)>;
+}
+
+/// Passes every offset to a processor, skipping offsets rejected by a [`Prefilter`]
+pub struct ProcessorSearch<R> {
+    processor: R,
+    prefilter: Prefilter,
+}
+
+impl<R> ProcessorSearch<R> {
+    #[must_use]
+    pub fn new(processor: R) -> Self {
+        return ProcessorSearch {
+            processor,
+            prefilter: Prefilter::Any,
+        };
+    }
+
+    /// The prefilter must not reject offsets the processor would match
+    #[must_use]
+    pub fn with_prefilter(mut self, prefilter: Prefilter) -> Self {
+        self.prefilter = prefilter;
+        return self;
+    }
+}
+
+impl<T, R> Search<T> for ProcessorSearch<R>
+where
+    T: ScanValue,
+    R: Processor<T>,
+{
+    fn max_size(&self) -> usize {
+        return self.processor.chunk_size().unwrap();
+    }
+
+    fn min_size(&self) -> usize {
+        return self.processor.min_chunk_size().unwrap();
+    }
+
+    #[inline]
+    fn find(
+        &mut self,
+        data: &[u8],
+        start: usize,
+        limit: usize,
+        _position: usize,
+    ) -> Option<(Range<usize>, T)> {
+        let chunk_size = self.processor.chunk_size().unwrap();
+
+        let mut next = start;
+        while let Some(index) = self.prefilter.next_candidate(data, next, limit) {
+            next = index + 1;
+
+            let value_data = &data[index..data.len().min(index + chunk_size)];
+            if let Some(result) = self.processor.consume(value_data) {
+                let size = result.size().unwrap_or(value_data.len());
+                return Some((index..index + size, result));
+            }
+        }
+
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Endianness;
+    use crate::workers::native_processor::NativeProcessor;
+
+    #[test]
+    fn find_skips_rejected_offsets() {
+        let mut search = ProcessorSearch::new(NativeProcessor::<u16>::new(Endianness::Little))
+            .with_prefilter(Prefilter::with_first_bytes(&[0x07]));
+        let data = [0x01, 0x07, 0x00, 0x07, 0x01];
+
+        assert_eq!(search.find(&data, 0, 4, 0), Some((1..3, 7)));
+        assert_eq!(search.find(&data, 2, 4, 0), Some((3..5, 0x107)));
+        assert_eq!(search.find(&data, 4, 4, 0), None);
+    }
+}
@@ -0,0 +1,243 @@
+use super::processors::{ChunkSize, Processor};
+use crate::types::string_match::{StringEncoding, StringMatch};
+
+/// Encodings in the order they're tried, UTF-16 first since a single
+/// character can also match as UTF-8 inside its UTF-16 encoding.
+const ENCODINGS: [StringEncoding; 3] = [
+    StringEncoding::Utf16Le,
+    StringEncoding::Utf16Be,
+    StringEncoding::Utf8,
+];
+
+/// Matches a literal string in UTF-8, UTF-16LE and UTF-16BE at the start of every chunk.
+///
+/// The size of a match depends on its encoding, and when ignoring case on the
+/// characters matched, so chunks are as large as the longest possible match.
+pub struct StringProcessor {
+    literal: String,
+    ignore_case: bool,
+    /// The literal in every encoding
+    encoded: Vec<(StringEncoding, Vec<u8>)>,
+}
+
+impl Processor<StringMatch> for StringProcessor {
+    #[inline]
+    fn consume(&mut self, bytes: &[u8]) -> Option<StringMatch> {
+        let found = ENCODINGS
+            .iter()
+            .find_map(|encoding| self.match_encoding(*encoding, bytes))?;
+
+        // `\x00A\x00B\x00` is "AB" in UTF-16BE here and in UTF-16LE at the next offset,
+        // only the little endian match is kept so the same text isn't matched twice
+        if found.encoding() == StringEncoding::Utf16Be
+            && self
+                .match_encoding(StringEncoding::Utf16Le, &bytes[1..])
+                .is_some()
+        {
+            return None;
+        }
+
+        return Some(found);
+    }
+
+    fn chunk_size(&self) -> ChunkSize {
+        // One more byte to find a little endian match after a big endian one
+        if self.ignore_case {
+            // Any character is at most 4 bytes in every encoding
+            return ChunkSize::Size(self.literal.chars().count() * 4 + 1);
+        }
+
+        let size = self.encoded.iter().map(|(_, e)| e.len()).max().unwrap();
+        return ChunkSize::Size(size + 1);
+    }
+
+    fn min_chunk_size(&self) -> ChunkSize {
+        if self.ignore_case {
+            return ChunkSize::Size(self.literal.chars().count());
+        }
+
+        return ChunkSize::Size(self.encoded.iter().map(|(_, e)| e.len()).min().unwrap());
+    }
+}
+
+impl StringProcessor {
+    /// # Panics
+    /// Panics if `literal` is empty
+    #[must_use]
+    pub fn new(literal: &str, ignore_case: bool) -> Self {
+        assert!(!literal.is_empty(), "string literal must not be empty");
+
+        return StringProcessor {
+            literal: literal.to_string(),
+            ignore_case,
+            encoded: ENCODINGS
+                .iter()
+                .map(|encoding| (*encoding, encoding.encode(literal)))
+                .collect(),
+        };
+    }
+
+    /// Bytes a match can start with, in any encoding
+    #[must_use]
+    pub fn first_bytes(&self) -> Vec<u8> {
+        let Some(first) = self.literal.chars().next() else {
+            return vec![];
+        };
+
+        let mut chars = vec![first];
+        if self.ignore_case {
+            chars = ('\0'..=char::MAX)
+                .filter(|c| chars_equal(*c, first))
+                .collect();
+        }
+
+        let mut bytes: Vec<u8> = chars
+            .iter()
+            .flat_map(|c| ENCODINGS.map(|encoding| encoding.encode(&c.to_string())[0]))
+            .collect();
+        bytes.sort_unstable();
+        bytes.dedup();
+
+        return bytes;
+    }
+
+    fn match_encoding(&self, encoding: StringEncoding, bytes: &[u8]) -> Option<StringMatch> {
+        if self.ignore_case {
+            return self.match_ignore_case(encoding, bytes);
+        }
+
+        let (_, encoded) = self.encoded.iter().find(|(e, _)| *e == encoding)?;
+        if !bytes.starts_with(encoded) {
+            return None;
+        }
+
+        return Some(StringMatch::new(
+            self.literal.clone(),
+            encoding,
+            encoded.len(),
+        ));
+    }
+
+    fn match_ignore_case(&self, encoding: StringEncoding, bytes: &[u8]) -> Option<StringMatch> {
+        let mut text = String::with_capacity(self.literal.len());
+        let mut size = 0;
+
+        for expected in self.literal.chars() {
+            let (c, char_size) = encoding.decode_char(&bytes[size..])?;
+            if !chars_equal(c, expected) {
+                return None;
+            }
+
+            text.push(c);
+            size += char_size;
+        }
+
+        return Some(StringMatch::new(text, encoding, size));
+    }
+}
+
+fn chars_equal(a: char, b: char) -> bool {
+    return a == b || a.to_lowercase().eq(b.to_lowercase());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::scan_value::ScanValue;
+
+    fn consume(processor: &mut StringProcessor, bytes: &[u8]) -> Option<(String, String, usize)> {
+        let result = processor.consume(bytes)?;
+        return Some((
+            result.to_string(),
+            result.type_name().to_string(),
+            result.size().unwrap(),
+        ));
+    }
+
+    #[test]
+    fn consume_every_encoding() {
+        let mut processor = StringProcessor::new("Hé", false);
+
+        assert_eq!(processor.chunk_size(), ChunkSize::Size(5));
+        assert_eq!(processor.min_chunk_size(), ChunkSize::Size(3));
+        assert_eq!(
+            consume(&mut processor, b"H\xC3\xA9\x00"),
+            Some(("Hé".into(), "utf8".into(), 3))
+        );
+        assert_eq!(
+            consume(&mut processor, b"H\x00\xE9\x00"),
+            Some(("Hé".into(), "utf16le".into(), 4))
+        );
+        assert_eq!(
+            consume(&mut processor, b"\x00H\x00\xE9"),
+            Some(("Hé".into(), "utf16be".into(), 4))
+        );
+        assert_eq!(consume(&mut processor, b"h\xC3\xA9\x00"), None);
+    }
+
+    #[test]
+    fn consume_ignore_case() {
+        let mut processor = StringProcessor::new("Hé", true);
+
+        assert_eq!(processor.chunk_size(), ChunkSize::Size(9));
+        assert_eq!(processor.min_chunk_size(), ChunkSize::Size(2));
+        assert_eq!(
+            consume(&mut processor, b"h\xC3\x89"),
+            Some(("hÉ".into(), "utf8".into(), 3))
+        );
+        assert_eq!(
+            consume(&mut processor, b"\x00h\x00\xC9"),
+            Some(("hÉ".into(), "utf16be".into(), 4))
+        );
+        assert_eq!(consume(&mut processor, b"hx"), None);
+    }
+
+    #[test]
+    fn consume_prefers_utf16() {
+        let mut processor = StringProcessor::new("A", false);
+
+        assert_eq!(
+            consume(&mut processor, b"A\x00"),
+            Some(("A".into(), "utf16le".into(), 2))
+        );
+        assert_eq!(
+            consume(&mut processor, b"A"),
+            Some(("A".into(), "utf8".into(), 1))
+        );
+    }
+
+    #[test]
+    fn consume_utf16le_not_also_utf16be() {
+        let data = b"\x00A\x00B\x00";
+        for ignore_case in [false, true] {
+            let mut processor = StringProcessor::new("AB", ignore_case);
+            let chunk_size = processor.chunk_size().unwrap();
+
+            let matches: Vec<_> = (0..data.len())
+                .filter_map(|index| {
+                    let chunk = &data[index..data.len().min(index + chunk_size)];
+                    Some((index, consume(&mut processor, chunk)?))
+                })
+                .collect();
+
+            assert_eq!(matches, vec![(1, ("AB".into(), "utf16le".into(), 4))]);
+        }
+    }
+
+    #[test]
+    fn first_bytes() {
+        assert_eq!(
+            StringProcessor::new("ab", false).first_bytes(),
+            vec![0x00, 0x61]
+        );
+        assert_eq!(
+            StringProcessor::new("ab", true).first_bytes(),
+            vec![0x00, 0x41, 0x61]
+        );
+        // The Kelvin sign is a capital k
+        assert_eq!(
+            StringProcessor::new("k", true).first_bytes(),
+            vec![0x00, 0x21, 0x2A, 0x4B, 0x6B, 0xE2]
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use std::mem;
+use std::sync::Arc;
+
+use super::processors::{ChunkSize, Processor};
+use crate::common::{DataType, Endianness};
+use crate::filters::configuration::Configuration;
+use crate::filters::expression::Expression;
+use crate::filters::filter::Filter;
+use crate::filters::tree::FilterTree;
+use crate::layout::{FieldSpec, LayoutError, StructLayout};
+use crate::types::apple_time::{Cocoa, HfsPlus};
+use crate::types::compare::{Compare, Tolerance};
+use crate::types::dos_time::Dos;
+use crate::types::endian::{FromBigEndian, FromLittleEndian};
+use crate::types::gps_time::Gps;
+use crate::types::guid::Guid;
+use crate::types::ip::{Ipv4, Ipv6};
+use crate::types::struct_match::StructMatch;
+use crate::types::timestamp::{Timestamp, Unix32, Unix64, Unix64Ms, Unix64Ns, Unix64Us};
+use crate::types::windows_time::{DotnetTicks, Filetime, OleDate, SystemTime};
+
+/// Size of a GUID in memory, the [`Guid`] type also keeps its layout
+const GUID_SIZE: usize = 16;
+
+/// A field of a record, the type is erased so fields of different types are kept together
+trait Field: Send + Sync {
+    /// Size of the field in bytes
+    fn size(&self) -> usize;
+
+    fn endianness(&self) -> Endianness;
+
+    /// Whether the field's value in `bytes` passes its filter
+    fn include(&self, bytes: &[u8]) -> bool;
+
+    /// The field's value in `bytes` as printed
+    fn format(&self, bytes: &[u8]) -> String;
+}
+
+struct TypedField<T: Compare + 'static> {
+    endianness: Endianness,
+    size: usize,
+    filter: Option<FilterTree<T>>,
+}
+
+impl<T: Compare + 'static> TypedField<T> {
+    fn create(
+        spec: &FieldSpec,
+        endianness: Endianness,
+        size: usize,
+        tolerance: Tolerance,
+    ) -> Result<Arc<dyn Field>, LayoutError> {
+        let expression = spec
+            .filter
+            .as_ref()
+            .map(|filter| Expression::<T>::parse(filter))
+            .transpose()
+            .map_err(|err| LayoutError::InvalidFilter(spec.name.clone(), err.to_string()))?;
+
+        let config = Configuration {
+            minimum: None,
+            maximum: None,
+            literals: vec![],
+            exclude_zero: false,
+            exclude_literal: None,
+            entropy: None,
+            expression,
+            tolerance,
+            date_minimum: None,
+            date_maximum: None,
+        };
+
+        return Ok(Arc::new(TypedField {
+            endianness: spec.endianness.unwrap_or(endianness),
+            size,
+            filter: config.create_filter(),
+        }));
+    }
+
+    fn read(&self, bytes: &[u8]) -> T {
+        if self.endianness == Endianness::Big {
+            return <T as FromBigEndian>::from_bytes(bytes);
+        }
+
+        return <T as FromLittleEndian>::from_bytes(bytes);
+    }
+}
+
+impl<T: Compare + 'static> Field for TypedField<T> {
+    fn size(&self) -> usize {
+        return self.size;
+    }
+
+    fn endianness(&self) -> Endianness {
+        return self.endianness;
+    }
+
+    #[inline]
+    fn include(&self, bytes: &[u8]) -> bool {
+        return self
+            .filter
+            .as_ref()
+            .map_or(true, |filter| filter.include(self.read(bytes)));
+    }
+
+    fn format(&self, bytes: &[u8]) -> String {
+        return self.read(bytes).to_string();
+    }
+}
+
+/// Creates a field of the spec's type, every type is read like `--data-type` reads it
+fn create_field(
+    spec: &FieldSpec,
+    endianness: Endianness,
+    tolerance: Tolerance,
+) -> Result<Arc<dyn Field>, LayoutError> {
+    fn typed<T: Compare + 'static>(
+        spec: &FieldSpec,
+        endianness: Endianness,
+        tolerance: Tolerance,
+    ) -> Result<Arc<dyn Field>, LayoutError> {
+        return TypedField::<T>::create(spec, endianness, mem::size_of::<T>(), tolerance);
+    }
+
+    return match spec.data_type {
+        DataType::I8 => typed::<i8>(spec, endianness, tolerance),
+        DataType::I16 => typed::<i16>(spec, endianness, tolerance),
+        DataType::I32 => typed::<i32>(spec, endianness, tolerance),
+        DataType::I64 => typed::<i64>(spec, endianness, tolerance),
+        DataType::I128 => typed::<i128>(spec, endianness, tolerance),
+        DataType::U8 => typed::<u8>(spec, endianness, tolerance),
+        DataType::U16 => typed::<u16>(spec, endianness, tolerance),
+        DataType::U32 => typed::<u32>(spec, endianness, tolerance),
+        DataType::U64 => typed::<u64>(spec, endianness, tolerance),
+        DataType::U128 => typed::<u128>(spec, endianness, tolerance),
+        DataType::F32 => typed::<f32>(spec, endianness, tolerance),
+        DataType::F64 => typed::<f64>(spec, endianness, tolerance),
+        DataType::Unix32 => typed::<Timestamp<Unix32>>(spec, endianness, tolerance),
+        DataType::Unix64 => typed::<Timestamp<Unix64>>(spec, endianness, tolerance),
+        DataType::Unix64Ms => typed::<Timestamp<Unix64Ms>>(spec, endianness, tolerance),
+        DataType::Unix64Us => typed::<Timestamp<Unix64Us>>(spec, endianness, tolerance),
+        DataType::Unix64Ns => typed::<Timestamp<Unix64Ns>>(spec, endianness, tolerance),
+        DataType::Filetime => typed::<Timestamp<Filetime>>(spec, endianness, tolerance),
+        DataType::Systemtime => typed::<Timestamp<SystemTime>>(spec, endianness, tolerance),
+        DataType::OleDate => typed::<Timestamp<OleDate>>(spec, endianness, tolerance),
+        DataType::DotnetTicks => typed::<Timestamp<DotnetTicks>>(spec, endianness, tolerance),
+        DataType::Cocoa => typed::<Timestamp<Cocoa>>(spec, endianness, tolerance),
+        DataType::HfsPlus => typed::<Timestamp<HfsPlus>>(spec, endianness, tolerance),
+        DataType::Gps => typed::<Timestamp<Gps>>(spec, endianness, tolerance),
+        DataType::Dos => typed::<Timestamp<Dos>>(spec, endianness, tolerance),
+        DataType::Guid => TypedField::<Guid>::create(spec, endianness, GUID_SIZE, tolerance),
+        DataType::Ipv4 => typed::<Ipv4>(spec, endianness, tolerance),
+        DataType::Ipv6 => typed::<Ipv6>(spec, endianness, tolerance),
+    };
+}
+
+/// Reads records of a [`StructLayout`], returns the records where every field passes its filter.
+///
+/// Fields are consecutive without padding, each is read in its own endianness
+/// or the endianness of the search.
+#[derive(Clone)]
+pub struct StructProcessor {
+    fields: Vec<(String, Arc<dyn Field>)>,
+    size: usize,
+    /// Endianness of every field, None if fields are read in different endiannesses
+    endianness: Option<Endianness>,
+}
+
+impl Processor<StructMatch> for StructProcessor {
+    #[inline]
+    fn consume(&mut self, bytes: &[u8]) -> Option<StructMatch> {
+        if bytes.len() < self.size {
+            return None;
+        }
+
+        let mut offset = 0;
+        for (_, field) in &self.fields {
+            if !field.include(&bytes[offset..offset + field.size()]) {
+                return None;
+            }
+            offset += field.size();
+        }
+
+        let mut offset = 0;
+        let mut values = Vec::with_capacity(self.fields.len());
+        for (name, field) in &self.fields {
+            values.push((
+                name.clone(),
+                field.format(&bytes[offset..offset + field.size()]),
+            ));
+            offset += field.size();
+        }
+
+        return Some(StructMatch::new(values, self.size, self.endianness));
+    }
+
+    fn chunk_size(&self) -> ChunkSize {
+        return ChunkSize::Size(self.size);
+    }
+}
+
+impl StructProcessor {
+    /// # Errors
+    /// Returns an error if a field's filter can't be parsed as the field's type
+    pub fn new(
+        layout: &StructLayout,
+        endianness: Endianness,
+        tolerance: Tolerance,
+    ) -> Result<Self, LayoutError> {
+        let fields = layout
+            .fields()
+            .iter()
+            .map(|spec| {
+                Ok((
+                    spec.name.clone(),
+                    create_field(spec, endianness, tolerance)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, LayoutError>>()?;
+
+        let size = fields.iter().map(|(_, field)| field.size()).sum();
+        let first = fields[0].1.endianness();
+        let endianness = fields
+            .iter()
+            .all(|(_, field)| field.endianness() == first)
+            .then_some(first);
+
+        return Ok(StructProcessor {
+            fields,
+            size,
+            endianness,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::guid::GuidLayout;
+    use crate::types::scan_value::ScanValue;
+
+    fn processor(layout: &str) -> StructProcessor {
+        let layout = StructLayout::parse(layout).unwrap();
+        return StructProcessor::new(&layout, Endianness::Little, Tolerance::default()).unwrap();
+    }
+
+    fn record(id: u32, lat: f64, port: u16) -> Vec<u8> {
+        let mut bytes = id.to_le_bytes().to_vec();
+        bytes.extend(lat.to_le_bytes());
+        bytes.extend(port.to_be_bytes());
+        return bytes;
+    }
+
+    #[test]
+    fn consume_matching_record() {
+        let mut processor = processor("{ id: u32 ==7, lat: f64 >=40 && <=41, port: u16 be }");
+        assert_eq!(processor.chunk_size(), ChunkSize::Size(14));
+
+        let found = processor.consume(&record(7, 40.5, 443)).unwrap();
+        assert_eq!(found.to_string(), "{id: 7, lat: 40.5, port: 443}");
+        assert_eq!(found.endianness(), None);
+
+        assert_eq!(processor.consume(&record(8, 40.5, 443)), None);
+        assert_eq!(processor.consume(&record(7, 42.0, 443)), None);
+        assert_eq!(processor.consume(&record(7, 40.5, 443)[..13]), None);
+    }
+
+    #[test]
+    fn consume_guid_and_ip() {
+        let mut processor =
+            processor("{ id: guid be ==9a3b2c1d-0e4f-4a5b-8c6d-7e8f90a1b2c3, addr: ipv4 be }");
+        let guid: Guid = "9a3b2c1d-0e4f-4a5b-8c6d-7e8f90a1b2c3".parse().unwrap();
+
+        let mut bytes = guid.to_layout(GuidLayout::Rfc4122).to_vec();
+        bytes.extend([10, 0, 0, 1]);
+
+        let found = processor.consume(&bytes).unwrap();
+        assert_eq!(
+            found.to_string(),
+            "{id: {9a3b2c1d-0e4f-4a5b-8c6d-7e8f90a1b2c3} (rfc4122), addr: 10.0.0.1}"
+        );
+        assert_eq!(found.endianness(), Some(Endianness::Big));
+    }
+
+    #[test]
+    fn new_invalid_filter() {
+        let layout = StructLayout::parse("{ id: u8 >=300 }").unwrap();
+
+        assert!(matches!(
+            StructProcessor::new(&layout, Endianness::Little, Tolerance::default()),
+            Err(LayoutError::InvalidFilter(name, _)) if name == "id"
+        ));
+    }
+}
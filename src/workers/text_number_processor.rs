@@ -0,0 +1,411 @@
+use std::marker::PhantomData;
+
+use super::processors::{ChunkSize, Processor};
+use crate::types::bit_type::BitType;
+use crate::types::string_match::StringEncoding;
+use crate::types::text_number::TextNumber;
+
+/// Longer tokens are not numbers, only a few more characters than an i128 or a precise f64
+const MAX_TOKEN_CHARS: usize = 64;
+
+/// Read by [`Text::get`] for characters that are not ASCII
+const NON_ASCII: u8 = 0x80;
+
+const ENCODINGS: [StringEncoding; 3] = [
+    StringEncoding::Utf16Le,
+    StringEncoding::Utf16Be,
+    StringEncoding::Utf8,
+];
+
+/// Finds numbers written as text in ASCII (UTF-8), UTF-16LE and UTF-16BE, and parses them to `T`.
+///
+/// Numbers are decimal (`-12`, `10.722`, `.5`), scientific (`1.5e-3`) or hex (`0x1F`) tokens
+/// that are not part of a word, so `abc123` and `1.2.3` are not matched.
+///
+/// A token starts where the preceding character is not a letter, digit or `.`, so the
+/// processor keeps the last consumed bytes and must be passed every offset in order.
+/// Offsets inside a token are skipped, so only the whole token is matched.
+///
+/// UTF-16BE text followed by a NUL is the same bytes as UTF-16LE text one byte later,
+/// such text is matched as UTF-16LE.
+///
+/// [`TextNumberProcessor::dotted_quads`] finds IPv4 addresses (`192.168.1.10`) instead.
+pub struct TextNumberProcessor<T> {
+    /// Last two consumed bytes, the last one is the byte before the current offset
+    previous: [u8; 2],
+    /// Amount of consumed bytes, up to the size of `previous`
+    consumed: usize,
+    /// Offsets left in the last token
+    skip: usize,
+    /// Length in characters of the token at the start of the text
+    token_length: fn(&Text) -> Option<usize>,
+    phantom: PhantomData<T>,
+}
+
+impl<T: BitType> Processor<TextNumber<T>> for TextNumberProcessor<T> {
+    #[inline]
+    fn consume(&mut self, bytes: &[u8]) -> Option<TextNumber<T>> {
+        let first = *bytes.first()?;
+        let result = self.consume_token(bytes);
+
+        self.previous = [self.previous[1], first];
+        self.consumed = (self.consumed + 1).min(self.previous.len());
+
+        return result;
+    }
+
+    fn chunk_size(&self) -> ChunkSize {
+        // The longest token and the two characters after it, which must not continue it,
+        // and a byte to read UTF-16LE one byte later
+        return ChunkSize::Size((MAX_TOKEN_CHARS + 2) * 2 + 1);
+    }
+
+    fn min_chunk_size(&self) -> ChunkSize {
+        return ChunkSize::Size(1);
+    }
+}
+
+impl<T: BitType> Default for TextNumberProcessor<T> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl<T: BitType> TextNumberProcessor<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        return TextNumberProcessor {
+            previous: [0; 2],
+            consumed: 0,
+            skip: 0,
+            token_length,
+            phantom: PhantomData,
+        };
+    }
+
+    /// Finds dotted quads, IPv4 addresses written as text, e.g. `192.168.1.10`
+    #[must_use]
+    pub fn dotted_quads() -> Self {
+        return TextNumberProcessor {
+            token_length: dotted_quad_length,
+            ..Self::new()
+        };
+    }
+
+    fn consume_token(&mut self, bytes: &[u8]) -> Option<TextNumber<T>> {
+        if self.skip > 0 {
+            self.skip -= 1;
+            return None;
+        }
+
+        for encoding in ENCODINGS {
+            let text = Text::new(bytes, encoding);
+            if !matches!(text.get(0), Some(b'0'..=b'9' | b'.' | b'+' | b'-')) {
+                continue;
+            }
+
+            let previous = self.previous_char(encoding);
+            if previous.is_some_and(|c| c.is_ascii_alphanumeric() || c == b'.') {
+                continue;
+            }
+
+            // A character between NULs is UTF-16 text, which is matched as UTF-16 or
+            // is part of a UTF-16 word
+            if encoding == StringEncoding::Utf8
+                && previous == Some(0)
+                && bytes.get(1).map_or(true, |byte| *byte == 0)
+            {
+                continue;
+            }
+
+            let Some(length) = (self.token_length)(&text) else {
+                continue;
+            };
+
+            // UTF-16BE followed by a NUL is also UTF-16LE one byte later, which is more common
+            if encoding == StringEncoding::Utf16Be
+                && (self.token_length)(&Text::new(&bytes[1..], StringEncoding::Utf16Le))
+                    == Some(length)
+            {
+                continue;
+            }
+
+            let size = length * text.unit();
+            self.skip = size - 1;
+
+            return parse::<T>(&text, length).map(|value| TextNumber::new(value, encoding, size));
+        }
+
+        return None;
+    }
+
+    fn previous_char(&self, encoding: StringEncoding) -> Option<u8> {
+        let unit = Text::new(&[], encoding).unit();
+        if self.consumed < unit {
+            return None;
+        }
+
+        return Text::new(&self.previous[self.previous.len() - unit..], encoding).get(0);
+    }
+}
+
+/// ASCII characters of text in an encoding
+struct Text<'a> {
+    bytes: &'a [u8],
+    encoding: StringEncoding,
+}
+
+impl<'a> Text<'a> {
+    fn new(bytes: &'a [u8], encoding: StringEncoding) -> Self {
+        return Text { bytes, encoding };
+    }
+
+    /// Size of a character in bytes
+    fn unit(&self) -> usize {
+        return match self.encoding {
+            StringEncoding::Utf8 => 1,
+            _ => 2,
+        };
+    }
+
+    /// The character at `index`, [`NON_ASCII`] if it's not ASCII and None after the end
+    fn get(&self, index: usize) -> Option<u8> {
+        let (low, high) = match self.encoding {
+            StringEncoding::Utf8 => (*self.bytes.get(index)?, 0),
+            StringEncoding::Utf16Le => {
+                let unit = self.bytes.get(index * 2..index * 2 + 2)?;
+                (unit[0], unit[1])
+            }
+            StringEncoding::Utf16Be => {
+                let unit = self.bytes.get(index * 2..index * 2 + 2)?;
+                (unit[1], unit[0])
+            }
+        };
+
+        if high != 0 || !low.is_ascii() {
+            return Some(NON_ASCII);
+        }
+
+        return Some(low);
+    }
+
+    /// Amount of characters matching `predicate` from `start`
+    fn count(&self, start: usize, predicate: fn(&u8) -> bool) -> usize {
+        return (start..self.bytes.len() / self.unit())
+            .take_while(|index| self.get(*index).is_some_and(|c| predicate(&c)))
+            .count();
+    }
+}
+
+/// Length in characters of the number token at the start of `text`
+fn token_length(text: &Text) -> Option<usize> {
+    let mut end = 0;
+
+    if text.get(0) == Some(b'0') && matches!(text.get(1), Some(b'x' | b'X')) {
+        let digits = text.count(2, u8::is_ascii_hexdigit);
+        if digits > 0 {
+            end = 2 + digits;
+        }
+    }
+
+    if end == 0 {
+        if matches!(text.get(0), Some(b'+' | b'-')) {
+            end = 1;
+        }
+
+        let integer = text.count(end, u8::is_ascii_digit);
+        end += integer;
+
+        let mut fraction = 0;
+        if text.get(end) == Some(b'.') {
+            fraction = text.count(end + 1, u8::is_ascii_digit);
+            if fraction > 0 {
+                end += 1 + fraction;
+            }
+        }
+
+        if integer + fraction == 0 {
+            return None;
+        }
+
+        if matches!(text.get(end), Some(b'e' | b'E')) {
+            let sign = usize::from(matches!(text.get(end + 1), Some(b'+' | b'-')));
+            let exponent = text.count(end + 1 + sign, u8::is_ascii_digit);
+            if exponent > 0 {
+                end += 1 + sign + exponent;
+            }
+        }
+    }
+
+    if end > MAX_TOKEN_CHARS || continues_token(text, end) {
+        return None;
+    }
+
+    return Some(end);
+}
+
+/// Length in characters of the dotted quad at the start of `text`, four groups of up to
+/// three digits separated by dots. The values are checked when parsing.
+fn dotted_quad_length(text: &Text) -> Option<usize> {
+    let mut end = 0;
+
+    for group in 0..4 {
+        if group > 0 {
+            if text.get(end) != Some(b'.') {
+                return None;
+            }
+            end += 1;
+        }
+
+        let digits = text.count(end, u8::is_ascii_digit);
+        if !(1..=3).contains(&digits) {
+            return None;
+        }
+        end += digits;
+    }
+
+    if continues_token(text, end) {
+        return None;
+    }
+
+    return Some(end);
+}
+
+/// A token followed by letters, digits or a fraction is part of a word or a version
+fn continues_token(text: &Text, end: usize) -> bool {
+    let next = text.get(end);
+    return next.is_some_and(|c| c.is_ascii_alphanumeric())
+        || (next == Some(b'.') && text.get(end + 1).is_some_and(|c| c.is_ascii_digit()));
+}
+
+fn parse<T: BitType>(text: &Text, length: usize) -> Option<T> {
+    let token: String = (0..length)
+        .filter_map(|index| text.get(index))
+        .map(char::from)
+        .collect();
+
+    if let Some(digits) = token.strip_prefix("0x").or(token.strip_prefix("0X")) {
+        return u128::from_str_radix(digits, 16)
+            .ok()?
+            .to_string()
+            .parse()
+            .ok();
+    }
+
+    return token.parse().ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ip::Ipv4;
+    use crate::types::scan_value::ScanValue;
+
+    /// Consumes every offset like the scanner, returns the offsets and values of matches
+    fn scan<T: BitType>(data: &[u8]) -> Vec<(usize, String, usize)> {
+        return scan_with(TextNumberProcessor::<T>::new(), data);
+    }
+
+    fn scan_with<T: BitType>(
+        mut processor: TextNumberProcessor<T>,
+        data: &[u8],
+    ) -> Vec<(usize, String, usize)> {
+        let chunk_size = processor.chunk_size().unwrap();
+
+        return (0..data.len())
+            .filter_map(|index| {
+                let chunk = &data[index..data.len().min(index + chunk_size)];
+                let result = processor.consume(chunk)?;
+                Some((index, result.to_string(), result.size().unwrap()))
+            })
+            .collect();
+    }
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        return StringEncoding::Utf16Le.encode(text);
+    }
+
+    #[test]
+    fn scan_decimal() {
+        assert_eq!(
+            scan::<f64>(b"x=10.5, y=-3 .25\n+7"),
+            vec![
+                (2, "10.5 (utf8)".into(), 4),
+                (10, "-3 (utf8)".into(), 2),
+                (13, "0.25 (utf8)".into(), 3),
+                (17, "7 (utf8)".into(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_hex_and_scientific() {
+        assert_eq!(
+            scan::<f64>(b"0x1F 1.5e-3 2E2"),
+            vec![
+                (0, "31 (utf8)".into(), 4),
+                (5, "0.0015 (utf8)".into(), 6),
+                (12, "200 (utf8)".into(), 3),
+            ]
+        );
+        assert_eq!(scan::<u8>(b"0x1F 0xFFF"), vec![(0, "31 (utf8)".into(), 4)]);
+    }
+
+    #[test]
+    fn scan_skips_words() {
+        assert_eq!(
+            scan::<i32>(b"abc123 12ab 1.2.3 v2 10-20 1.5 7."),
+            vec![
+                (21, "10 (utf8)".into(), 2),
+                (24, "20 (utf8)".into(), 2),
+                (31, "7 (utf8)".into(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_utf16() {
+        let mut data = utf16le("id 42, a12");
+        data.extend(StringEncoding::Utf16Be.encode(" -1.5"));
+
+        assert_eq!(
+            scan::<f32>(&data),
+            vec![
+                (6, "42 (utf16le)".into(), 4),
+                (22, "-1.5 (utf16be)".into(), 8)
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_utf16be_followed_by_nul() {
+        let mut data = StringEncoding::Utf16Be.encode(" 42");
+        data.extend([0, 0]);
+
+        assert_eq!(scan::<u32>(&data), vec![(3, "42 (utf16le)".into(), 4)]);
+    }
+
+    #[test]
+    fn scan_too_long() {
+        let long = "1".repeat(MAX_TOKEN_CHARS + 1);
+        assert_eq!(scan::<f64>(long.as_bytes()), vec![]);
+
+        let longest = "1".repeat(MAX_TOKEN_CHARS);
+        assert_eq!(scan::<f64>(&utf16le(&longest)).len(), 1);
+    }
+
+    #[test]
+    fn scan_dotted_quads() {
+        let mut data = b"host 10.0.0.1, v1.2.3.4.5 300.1.1.1 8.8.8.8".to_vec();
+        data.extend(utf16le(" 192.168.1.10"));
+
+        assert_eq!(
+            scan_with(TextNumberProcessor::<Ipv4>::dotted_quads(), &data),
+            vec![
+                (5, "10.0.0.1 (utf8)".into(), 8),
+                (36, "8.8.8.8 (utf8)".into(), 7),
+                (45, "192.168.1.10 (utf16le)".into(), 24),
+            ]
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use clap::ValueEnum;
+
+use super::native_processor::NativeProcessor;
+use super::processors::Processor;
+use crate::common::{DataType, Endianness};
+use crate::types::apple_time::{Cocoa, HfsPlus};
+use crate::types::dos_time::Dos;
+use crate::types::gps_time::Gps;
+use crate::types::timestamp::{Epoch, Timestamp, Unix32, Unix64, Unix64Ms, Unix64Ns, Unix64Us};
+use crate::types::windows_time::{DotnetTicks, Filetime, OleDate, SystemTime};
+
+/// Decodes a single timestamp encoding (type and endianness),
+/// used to search all encodings in a single pass.
+pub trait TimestampDecoder: Send {
+    /// Name of the encoding in the output, e.g. `unix32-le`
+    fn name(&self) -> &str;
+    fn size(&self) -> usize;
+    fn endianness(&self) -> Endianness;
+
+    /// Nanoseconds since the Unix epoch, None for invalid times
+    fn nanos(&mut self, bytes: &[u8]) -> Option<i128>;
+    fn display(&mut self, bytes: &[u8]) -> String;
+}
+
+struct EpochDecoder<E: Epoch> {
+    name: String,
+    endianness: Endianness,
+    processor: NativeProcessor<Timestamp<E>>,
+}
+
+impl<E: Epoch> EpochDecoder<E> {
+    fn boxed(endianness: Endianness) -> Box<dyn TimestampDecoder> {
+        let suffix = match endianness {
+            Endianness::Little => "le",
+            Endianness::Big => "be",
+        };
+
+        return Box::new(EpochDecoder::<E> {
+            name: format!("{}-{suffix}", E::NAME),
+            endianness,
+            processor: NativeProcessor::new(endianness),
+        });
+    }
+}
+
+impl<E: Epoch> TimestampDecoder for EpochDecoder<E> {
+    fn name(&self) -> &str {
+        return &self.name;
+    }
+
+    fn size(&self) -> usize {
+        return self.processor.chunk_size().unwrap();
+    }
+
+    fn endianness(&self) -> Endianness {
+        return self.endianness;
+    }
+
+    #[inline]
+    fn nanos(&mut self, bytes: &[u8]) -> Option<i128> {
+        return self.processor.consume(bytes)?.nanos();
+    }
+
+    fn display(&mut self, bytes: &[u8]) -> String {
+        return self
+            .processor
+            .consume(bytes)
+            .map_or_else(String::new, |timestamp| timestamp.to_string());
+    }
+}
+
+/// Creates a decoder of a timestamp data type, None for numeric types
+#[must_use]
+pub fn create_decoder(
+    data_type: &DataType,
+    endianness: Endianness,
+) -> Option<Box<dyn TimestampDecoder>> {
+    let decoder = match data_type {
+        DataType::Unix32 => EpochDecoder::<Unix32>::boxed(endianness),
+        DataType::Unix64 => EpochDecoder::<Unix64>::boxed(endianness),
+        DataType::Unix64Ms => EpochDecoder::<Unix64Ms>::boxed(endianness),
+        DataType::Unix64Us => EpochDecoder::<Unix64Us>::boxed(endianness),
+        DataType::Unix64Ns => EpochDecoder::<Unix64Ns>::boxed(endianness),
+        DataType::Filetime => EpochDecoder::<Filetime>::boxed(endianness),
+        DataType::Systemtime => EpochDecoder::<SystemTime>::boxed(endianness),
+        DataType::OleDate => EpochDecoder::<OleDate>::boxed(endianness),
+        DataType::DotnetTicks => EpochDecoder::<DotnetTicks>::boxed(endianness),
+        DataType::Cocoa => EpochDecoder::<Cocoa>::boxed(endianness),
+        DataType::HfsPlus => EpochDecoder::<HfsPlus>::boxed(endianness),
+        DataType::Gps => EpochDecoder::<Gps>::boxed(endianness),
+        DataType::Dos => EpochDecoder::<Dos>::boxed(endianness),
+        _ => return None,
+    };
+
+    return Some(decoder);
+}
+
+/// Decoders of every timestamp data type in both endiannesses
+#[must_use]
+pub fn all_decoders() -> Vec<Box<dyn TimestampDecoder>> {
+    return DataType::value_variants()
+        .iter()
+        .filter(|data_type| data_type.is_timestamp())
+        .flat_map(|data_type| {
+            [Endianness::Little, Endianness::Big]
+                .into_iter()
+                .filter_map(|endianness| create_decoder(data_type, endianness))
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_timestamp_types_have_decoders() {
+        let decoders = all_decoders();
+
+        let timestamp_types = DataType::value_variants()
+            .iter()
+            .filter(|data_type| data_type.is_timestamp())
+            .count();
+        assert_eq!(decoders.len(), timestamp_types * 2);
+        assert!(create_decoder(&DataType::U32, Endianness::Little).is_none());
+    }
+
+    #[test]
+    fn decoder_names_and_sizes() {
+        let little = create_decoder(&DataType::Unix32, Endianness::Little).unwrap();
+        let big = create_decoder(&DataType::Systemtime, Endianness::Big).unwrap();
+
+        assert_eq!(little.name(), "unix32-le");
+        assert_eq!(little.size(), 4);
+        assert_eq!(big.name(), "systemtime-be");
+        assert_eq!(big.size(), 16);
+    }
+
+    #[test]
+    fn decode_both_endians() {
+        let mut little = create_decoder(&DataType::Unix32, Endianness::Little).unwrap();
+        let mut big = create_decoder(&DataType::Unix32, Endianness::Big).unwrap();
+        let bytes = 1_614_556_800i32.to_be_bytes();
+
+        assert_eq!(big.nanos(&bytes), Some(1_614_556_800 * 1_000_000_000));
+        assert_eq!(big.display(&bytes), "2021-03-01T00:00:00Z");
+        assert_ne!(little.nanos(&bytes), big.nanos(&bytes));
+    }
+
+    #[test]
+    fn decode_invalid_is_none() {
+        let mut decoder = create_decoder(&DataType::Dos, Endianness::Little).unwrap();
+
+        assert_eq!(decoder.nanos(&[0, 0, 0, 0]), None);
+    }
+}